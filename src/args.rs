@@ -1,12 +1,17 @@
 use std::io::IsTerminal;
-use std::path::PathBuf;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 
-use crate::tcolor::{RippySchema, enable_ansi_support, ERROR_COLOR, WARN_COLOR};
+use crate::config::{find_config_path, read_config, ConfigValue};
+use crate::error::{ErrorKind, RippyError};
+use crate::tcolor::{RippySchema, WARN_COLOR, color_name, format_ansi_warning, try_enable_ansi_support};
 use crate::{ansi_color, concat_str};
-use crate::tree::{Tree, TreeCounts};
+use crate::tree::{ExtSummary, Snapshot, StyleKind, Tree, TreeCounts, TreeGlyphs, NumberFormat, ASCII_GLYPHS, UNICODE_GLYPHS, US_NUMBER_FORMAT, normalize_path, normalize_path_lexically};
 
 use clap::parser::ValueSource;
+use encoding_rs::Encoding;
 use clap::{value_parser, Arg, ArgAction, Command};
+use globset::{GlobBuilder, GlobMatcher};
 use regex::{Regex, RegexSet};
 
 /// Returns the full version and build info for rippy in the format of:
@@ -19,12 +24,78 @@ use regex::{Regex, RegexSet};
 const RELEASE_INFO: Option<&str> = option_env!("RELEASE_INFO");
 
 /// Sorting keys and whether or not they're in ascending (true) or descending (false) order.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SortKey {
     Date(bool),
     Name(bool),
     Size(bool),
     Type(bool),
+    Score(bool),
+}
+
+/// Naming convention file names are checked against by `--name-style`, keeping the files that break it instead when inverted.
+#[derive(Debug, Clone)]
+pub struct NameStyle {
+    pub pattern: Regex,
+    pub is_invert: bool,
+}
+
+impl NameStyle {
+    /// Creates the check for the convention named, `snake`, `kebab`, `camel` or `pascal`, each allowing a single lowercase extension.
+    pub fn new(style: &str, is_invert: bool) -> Option<Self> {
+        let pattern = match style.to_ascii_lowercase().as_str() {
+            "snake" => r"^[a-z0-9_]+(\.[a-z0-9]+)?$",
+            "kebab" => r"^[a-z0-9-]+(\.[a-z0-9]+)?$",
+            "camel" => r"^[a-z][a-zA-Z0-9]*(\.[a-z0-9]+)?$",
+            "pascal" => r"^[A-Z][a-zA-Z0-9]*(\.[a-z0-9]+)?$",
+            _ => return None,
+        };
+        Some(NameStyle { pattern: Regex::new(pattern).ok()?, is_invert })
+    }
+    /// Returns true if the file name should be kept, following the convention or breaking it when inverted.
+    pub fn is_match(&self, name: &str) -> bool {
+        self.pattern.is_match(name) != self.is_invert
+    }
+}
+
+/// Form of the path written for each entry exported to JSON with `--json-paths`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JsonPaths {
+    Relative,
+    Base(PathBuf),
+    Absolute,
+}
+
+/// Levels of the tree that `--reverse-levels` reverses the ordering of, with directories holding only files being the leaf level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReverseLevels {
+    Files,
+    Dirs,
+}
+
+/// Gradients entry names can be colored on in place of their usual colors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorScale {
+    Date,
+}
+
+/// Orderings of the breakdown by extension, with sizes and counts descending and names ascending.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SummarySort {
+    Name,
+    Count,
+    Size,
+}
+
+impl SummarySort {
+    /// Provides the comparator to sort the extensions by, falling back to their names so ties are listed alphabetically.
+    pub fn compare(&self) -> fn(&ExtSummary, &ExtSummary) -> std::cmp::Ordering {
+        match self {
+            SummarySort::Name => |a: &ExtSummary, b: &ExtSummary| a.extension.cmp(&b.extension),
+            SummarySort::Count => |a: &ExtSummary, b: &ExtSummary| b.files.cmp(&a.files).then_with(|| a.extension.cmp(&b.extension)),
+            SummarySort::Size => |a: &ExtSummary, b: &ExtSummary| b.size.cmp(&a.size).then_with(|| a.extension.cmp(&b.extension)),
+        }
+    }
 }
 
 impl SortKey {
@@ -39,56 +110,230 @@ impl SortKey {
                SortKey::Type(false) => |a: &Tree, b: &Tree| a.entry_type.cmp(&b.entry_type).reverse(),
                SortKey::Name(true) => |a: &Tree, b: &Tree| a.name.cmp(&b.name),
                SortKey::Name(false) => |a: &Tree, b: &Tree| a.name.cmp(&b.name).reverse(),
+               // Ascending ranks the closest fuzzy matches first, with ties listed by name
+               SortKey::Score(true) => |a: &Tree, b: &Tree| b.fuzzy_score.cmp(&a.fuzzy_score).then_with(|| a.name.cmp(&b.name)),
+               SortKey::Score(false) => |a: &Tree, b: &Tree| b.fuzzy_score.cmp(&a.fuzzy_score).reverse().then_with(|| a.name.cmp(&b.name)),
          }
      }
  }
 
+impl std::fmt::Display for SortKey {
+    /// Writes the key by its `--sort-by` name followed by the order, with fuzzy scores ordered by closeness.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let order = |is_ascending: bool| if is_ascending { "ascending" } else { "descending" };
+        match *self {
+            SortKey::Date(is_ascending) => write!(f, "date {}", order(is_ascending)),
+            SortKey::Name(is_ascending) => write!(f, "name {}", order(is_ascending)),
+            SortKey::Size(is_ascending) => write!(f, "size {}", order(is_ascending)),
+            SortKey::Type(is_ascending) => write!(f, "type {}", order(is_ascending)),
+            SortKey::Score(true) => write!(f, "score closest first"),
+            SortKey::Score(false) => write!(f, "score closest last"),
+        }
+    }
+}
+
 /// Primary struct holding all rippy arguments after parsing to expected types
 #[derive(Debug)]
 pub struct RippyArgs {
     pub directory: PathBuf,
+    pub is_file_root: bool, // Directory provided is a single file rendered as the only entry
     pub pattern: Option<Regex>,
+    pub pattern_text: Option<String>, // Pattern as provided before any flags are applied, for display
     pub is_search: bool,
     pub ignore_patterns: Option<RegexSet>,
     pub include_all: bool,
     pub include_patterns: Option<RegexSet>,
+    pub dotfile_include_patterns: Option<RegexSet>, // Include patterns starting with a dot, which list hidden files without `--all`
+    pub path_glob: Option<GlobMatcher>,
     pub max_depth: usize,
     pub max_files: usize,
-    pub output: String,
+    pub output: Vec<String>,
+    pub excluded_paths: Vec<PathBuf>, // Output files within the root, as absolute normalized paths, kept out of the results
     pub indent: usize,
     pub sort_by: fn(&Tree, &Tree) -> std::cmp::Ordering,
+    pub sort_leaves_by: fn(&Tree, &Tree) -> std::cmp::Ordering, // Ordering within directories holding only files
+    pub sort_by_key: SortKey, // Key and order behind `sort_by`, for display
+    pub sort_leaves_by_key: SortKey,
     pub is_dir_detail: bool,
     pub show_full_path: bool,
     pub show_relative_path: bool,
     pub show_size: bool,
     pub show_date: bool,
+    pub is_stat_date: bool, // Last modified times are read, to display or to sort by even when not displayed
+    pub is_stat_size: bool, // Sizes are read, to display or to recognize empty files and total extensions even when not displayed
+    pub is_size_sort: bool, // File sizes are read to sort by even when not displayed
     pub date_format: String,
     pub show_elapsed: bool,
     pub is_grayscale: bool,
     pub is_quote: bool,
     pub is_flat: bool,
+    pub is_table: bool,
     pub is_window: bool,
     pub is_just_counts: bool,
+    pub is_count_root: bool,
+    pub is_legend: bool,
+    pub fuzzy: Option<String>,
+    pub exec_extensions: Vec<String>,
+    pub opaque_dirs: Vec<String>,
+    pub ellipsis: String,
+    pub mark: Option<(String, String)>,
+    pub word_radius: Option<usize>,
+    pub is_multiline: bool,
+    pub is_collapse: bool,
+    pub max_entries: usize,
+    pub is_shallow_size: bool,
+    pub is_ext_colors: bool,
+    pub ext_colors: Vec<(String, &'static str)>,
+    pub is_ascii_space: bool,
+    pub is_select: bool,
+    pub is_rippyignore: bool,
+    pub is_show_unreadable: bool,
+    pub relative_to: Option<PathBuf>,
+    pub json_paths: Option<JsonPaths>, // Adds the path of each entry to JSON exports in the form requested
+    pub is_depth_summary: bool,
+    pub is_deterministic: bool,
+    pub is_deep_size: bool,
+    pub is_one_file_system: bool,
+    pub min_children: usize,
+    pub is_bare_json: bool,
+    pub glyphs: TreeGlyphs,
+    pub is_ext_count: bool,
+    pub is_flag_empty: bool,
+    pub is_no_empty_files: bool,
+    pub is_line_numbers: bool,
+    pub is_no_bold: bool,
+    pub is_time_phases: bool,
+    pub is_breadth_first: bool,
+    pub is_summary_top: bool,
+    pub is_dereference: bool,
+    pub is_no_summary_on_empty: bool,
+    pub is_bom: bool,
+    pub is_native_separators: bool,
+    pub is_window_below: bool,
+    pub is_verbose: bool,
+    pub is_du: bool,
+    pub number_format: NumberFormat,
+    pub exclude_dir_patterns: Option<RegexSet>,
+    pub top_dirs: Option<usize>,
+    pub is_json_errors: bool,
+    pub filename_regex: Option<Regex>,
+    pub name_style: Option<NameStyle>,
+    pub max_name_length: Option<usize>,
+    pub is_notify: bool,
+    pub is_group_similar: bool,
+    pub is_ascii: bool,
+    pub is_no_root_bold: bool,
+    pub listed_paths: Option<HashSet<PathBuf>>, // Files tracked by git or read from stdin and their ancestor directories, as absolute normalized paths
+    pub glob_roots: Option<Vec<PathBuf>>, // Directories matched by a glob given as the root, as absolute normalized paths
+    pub subtree: Option<String>,
+    pub is_file_date: bool,
+    pub is_dir_date: bool,
+    pub since: Option<f64>,
+    pub until: Option<f64>,
+    pub accessed_before: Option<f64>, // Files last accessed on or after this time, in seconds since unix epoch, are left out
+    pub is_show_config: bool,
+    pub is_size_heat: bool,
+    pub is_json_matches_only: bool,
+    pub elide_middle: Option<usize>,
+    pub encoding: Option<&'static Encoding>,
+    pub is_count_matches_total: bool,
+    pub json_indent: usize,
+    pub is_no_report: bool,
+    pub is_prune: bool,
+    pub read_threads: Option<usize>,
+    pub is_show_root_abs: bool,
+    pub is_mount_summary: bool,
+    pub is_flatten_single_child: bool,
+    pub is_dereference_all: bool,
+    pub is_summary_by_ext: bool,
+    pub summary_sort: SummarySort,
+    pub color_scale: Option<ColorScale>,
+    pub is_utf8_lossy: bool,
+    pub is_progress: bool,
+    pub max_line_length: Option<usize>,
+    pub is_compact: bool,
+    pub diff: Option<Snapshot>,
+    pub max_total_size: Option<u64>,
+    pub is_matching_dirs: bool,
     pub is_enumerate: bool,
     pub is_follow_links: bool,
     pub is_gitignore: bool,
     pub radius: usize,
+    pub radius_before: usize, // Character radius of the window before the match, `radius` unless given with `--window-before`
+    pub radius_after: usize, // Character radius of the window after the match, `radius` unless given with `--window-after`
     pub colors: RippySchema,
 }
+impl Default for RippyArgs {
+    /// Settings for crawling the current directory as `rippy` yields when run without any options, ignoring any config file.
+    ///
+    /// Panics if the current directory no longer exists.
+    fn default() -> Self {
+        RippyArgs::new(".").expect("current directory should exist")
+    }
+}
+impl RippyArgs {
+    /// Creates the settings for crawling the directory provided as `rippy <DIRECTORY>` yields without any other options, ignoring any config file so results don't depend on the working directory.
+    /// The directory is always taken as the root rather than as a pattern to search the current directory for, returning an error if it is neither a directory nor a file.
+    pub fn new(directory: impl Into<String>) -> Result<Self, RippyError> {
+        let directory: String = directory.into();
+        if !Path::new(&directory).is_dir() && !Path::new(&directory).is_file() {
+            return Err(RippyError::new(ErrorKind::InvalidDirectory, concat_str!("The directory provided, '", directory, "', does not exist or is not a valid directory."), Some(directory)));
+        }
+        // Given after the separator so it can't be read as an option or left to be searched for as a pattern
+        Ok(parse_args_with_config(Some(vec![env!("CARGO_PKG_NAME").to_string(), "--".to_string(), directory]), false))
+    }
+}
+
+impl std::fmt::Display for RippyArgs {
+    /// Writes the settings laid out as the `Debug` dump, with the sort comparators named by their key and order, limits left at `usize::MAX` shown as unlimited and colors named as given to the color options.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let dump = format!("{self:#?}");
+        let escape_code = Regex::new(r"\\u\{1b\}\[[0-9;]*m").expect("escape code pattern is valid");
+        let mut skipped_indent = None; // Indent of the field being left out while its value spans lines
+        for line in dump.lines() {
+            let field = line.trim_start().split_once(": ").map_or("", |(field, _)| field);
+            let indent = &line[..line.len() - line.trim_start().len()];
+            if let Some(skipped) = skipped_indent {
+                if indent.len() == skipped {
+                    skipped_indent = None;
+                }
+                continue;
+            }
+            match field {
+                "sort_by" => writeln!(f, "{indent}sort_by: {},", self.sort_by_key)?,
+                "sort_leaves_by" => writeln!(f, "{indent}sort_leaves_by: {},", self.sort_leaves_by_key)?,
+                "sort_by_key" | "sort_leaves_by_key" => skipped_indent = (!line.ends_with(',')).then_some(indent.len()),
+                _ => {
+                    let line = line.replace(&format!(": {},", usize::MAX), ": unlimited,");
+                    let line = escape_code.replace_all(&line, |code: &regex::Captures| color_name(&code[0].replace("\\u{1b}", "\x1b")));
+                    writeln!(f, "{line}")?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Parses command line arguments and returns as struct to use as config container throughout rippy.
 pub fn parse_args(args: Option<Vec<String>>) -> RippyArgs {
+    parse_args_with_config(args, true)
+}
+
+/// Parses the command line arguments, inserting defaults from the config file ahead of them unless it should be ignored.
+fn parse_args_with_config(args: Option<Vec<String>>, is_config: bool) -> RippyArgs {
     let rippy_cmd = Command::new(env!("CARGO_PKG_NAME"))
         .version(RELEASE_INFO.unwrap_or("Unknown"))
         .author("Ante Tonkovic-Capin")
         .about(concat_str!(env!("CARGO_PKG_NAME"), " ", option_env!("RELEASE_INFO").unwrap_or("[unknown version]"), "\nCrawls directory specified according to arguments, optionally executing multithreaded searches for pattern provided, returning results in a pruned and pretty printed terminal tree."))
         .disable_version_flag(true)
         .disable_help_flag(true)
+        // Options repeated by the config file are overridden by those given explicitly afterwards
+        .args_override_self(true)
         .after_help("For example, run `rippy \"./\"` to display a tree of the current directory's contents.")
         /* Positional arguments */
         .arg(Arg::new("directory")
-             .help("Sets the root directory to search")
+             .help("Sets the root directory to search, a glob matching several directories, or a single file to display alone, defaults to the current directory which `-` is also shorthand for")
              .value_name("DIRECTORY")
-             .required(true)
              .index(1))
         .arg(Arg::new("pattern")
              .help("Sets the pattern to search file contents for")
@@ -111,11 +356,11 @@ pub fn parse_args(args: Option<Vec<String>>) -> RippyArgs {
              .default_value("name")
              .hide_default_value(true)
              .hide_possible_values(true)
-             .value_parser(["date","name","size","type"])
+             .value_parser(["date","name","size","type","score"])
              .ignore_case(true)
              .display_order(1)
              .action(ArgAction::Set)
-             .help("Sorting options: 'date', 'name' [d], 'size' or 'type'"))
+             .help("Sorting options: 'date', 'name' [d], 'size', 'type' or 'score'"))
         .arg(Arg::new("max-depth")
              .short('L')
              .long("max-depth")
@@ -123,7 +368,7 @@ pub fn parse_args(args: Option<Vec<String>>) -> RippyArgs {
              .action(ArgAction::Set)
              .display_order(2)
              .value_parser(value_parser!(usize))
-             .help("Maximum directory depth to search"))  
+             .help("Maximum directory depth to search, directories at the limit are listed without their contents"))  
         .arg(Arg::new("date-format")
              .short('Y')
              .short_alias('y')
@@ -152,7 +397,19 @@ pub fn parse_args(args: Option<Vec<String>>) -> RippyArgs {
              .value_delimiter(',')
              .display_order(5)
              .action(ArgAction::Append)
-             .help("Restrict search to specific filename patterns"))                  
+             .help("Restrict search to specific filename patterns"))
+        .arg(Arg::new("ignore-file-list")
+             .long("ignore-file-list")
+             .aliases(["ignore-from", "ignore-list"])
+             .value_name("PATH")
+             .action(ArgAction::Append)
+             .help("Ignore filename patterns read from the file, one per line, same as '--ignore'"))
+        .arg(Arg::new("include-file")
+             .long("include-file")
+             .aliases(["include-from", "include-list"])
+             .value_name("PATH")
+             .action(ArgAction::Append)
+             .help("Restrict search to filename patterns read from the file, one per line, same as '--include'"))                  
         .arg(Arg::new("window-radius")
              .short('R')
              .short_alias('r')
@@ -165,6 +422,22 @@ pub fn parse_args(args: Option<Vec<String>>) -> RippyArgs {
              .display_order(6)
              .action(ArgAction::Set)
              .help("Maximum character radius for result snippet window"))                        
+        .arg(Arg::new("window-before")
+             .long("window-before")
+             .aliases(["radius-before", "before-radius"])
+             .value_name("RADIUS")
+             .value_parser(value_parser!(usize))
+             .conflicts_with("word-radius")
+             .action(ArgAction::Set)
+             .help("Maximum character radius for result snippet window before the match, defaults to --window-radius"))
+        .arg(Arg::new("window-after")
+             .long("window-after")
+             .aliases(["radius-after", "after-radius"])
+             .value_name("RADIUS")
+             .value_parser(value_parser!(usize))
+             .conflicts_with("word-radius")
+             .action(ArgAction::Set)
+             .help("Maximum character radius for result snippet window after the match, defaults to --window-radius"))
         .arg(Arg::new("max-files")
              .short('M')
              .short_alias('m')
@@ -180,9 +453,9 @@ pub fn parse_args(args: Option<Vec<String>>) -> RippyArgs {
              .short_alias('o')
              .long("output")
              .value_name("FILENAME")
-             .action(ArgAction::Set)
+             .action(ArgAction::Append)
              .display_order(8)
-             .help("Export the results as JSON to specified file"))       
+             .help("Export the results to the file using the format of its extension"))       
         .arg(Arg::new("indent")
              .short('N')
              .short_alias('n')
@@ -222,7 +495,15 @@ pub fn parse_args(args: Option<Vec<String>>) -> RippyArgs {
              .long("reverse")
              .aliases(["reversed","rev"])
              .action(ArgAction::SetTrue)
-             .help("Reverses sort order from ascending to descending"))             
+             .help("Reverses sort order from ascending to descending"))
+        .arg(Arg::new("reverse-levels")
+             .long("reverse-levels")
+             .aliases(["reverse-level", "rev-levels"])
+             .value_name("LEVELS")
+             .value_parser(["files", "dirs"])
+             .ignore_case(true)
+             .action(ArgAction::Set)
+             .help("Reverse the ordering only within directories holding just files ('files'), or everywhere but within them ('dirs')"))             
         .arg(Arg::new("full-path")
              .short('K')
              .short_alias('k')
@@ -270,6 +551,11 @@ pub fn parse_args(args: Option<Vec<String>>) -> RippyArgs {
              .aliases(["grayscale","bw","black-and-white","no-color","colorless"])
              .action(ArgAction::SetTrue)
              .help("Display the results in grayscale without styling")) 
+        .arg(Arg::new("no-pipe")
+             .long("no-pipe")
+             .aliases(["keep-colors", "force-color"])
+             .action(ArgAction::SetTrue)
+             .help("Keep colors when output is piped or redirected rather than a terminal, still overridden by --gray"))
         .arg(Arg::new("quote")
              .short('Q')
              .short_alias('q')
@@ -282,7 +568,17 @@ pub fn parse_args(args: Option<Vec<String>>) -> RippyArgs {
              .long("flat")
              .aliases(["flattened", "flatten"])
              .action(ArgAction::SetTrue)
-             .help("Display the results as flat list without indentation"))                   
+             .help("Display the results as flat list without indentation"))
+        .arg(Arg::new("flat-paths")
+             .long("flat-paths")
+             .aliases(["flat-path", "flat-relative"])
+             .action(ArgAction::SetTrue)
+             .help("Display the results as flat list of relative paths from root"))
+        .arg(Arg::new("table")
+             .long("table")
+             .aliases(["tabular", "columns"])
+             .action(ArgAction::SetTrue)
+             .help("Display the results as aligned columns instead of a tree"))                   
         .arg(Arg::new("dir-detail")
              .short('U')
              .short_alias('u')
@@ -303,7 +599,532 @@ pub fn parse_args(args: Option<Vec<String>>) -> RippyArgs {
             .long("just-counts")
             .aliases(["counts", "count", "counts-only"])
             .action(ArgAction::SetTrue)
-            .help("Display just entry counts without rendering a tree"))     
+            .help("Display just entry counts without rendering a tree"))
+        .arg(Arg::new("count-root")
+            .long("count-root")
+            .aliases(["include-root", "inclusive-root"])
+            .action(ArgAction::SetTrue)
+            .help("Include the root directory itself in the directory count"))
+        .arg(Arg::new("legend")
+            .long("legend")
+            .aliases(["key"])
+            .action(ArgAction::SetTrue)
+            .help("Display a legend of the colors used after the results"))
+        .arg(Arg::new("fuzzy")
+            .long("fuzzy")
+            .aliases(["fuzzy-name"])
+            .value_name("QUERY")
+            .action(ArgAction::Set)
+            .help("Restrict results to filenames that fuzzy match the query, ranked with the closest matches first unless sorted otherwise"))
+        .arg(Arg::new("exec-ext")
+            .long("exec-ext")
+            .aliases(["exec-extensions", "executable-ext"])
+            .value_name("EXT1, ..., EXTN")
+            .value_delimiter(',')
+            .action(ArgAction::Append)
+            .help("Treat files with these extensions as executables when styling"))
+        .arg(Arg::new("opaque-dir")
+            .long("opaque-dir")
+            .aliases(["opaque", "opaque-dirs"])
+            .value_name("DIR1, ..., DIRN")
+            .value_delimiter(',')
+            .action(ArgAction::Append)
+            .help("Display these directories as leaves without their contents"))
+        .arg(Arg::new("ellipsis")
+            .long("ellipsis")
+            .value_name("STR")
+            .default_value("...")
+            .hide_default_value(true)
+            .action(ArgAction::Set)
+            .help("String used to mark content elided from snippet windows"))
+        .arg(Arg::new("mark")
+            .long("mark")
+            .aliases(["marker", "markers"])
+            .value_names(["LEFT", "RIGHT"])
+            .num_args(2)
+            .action(ArgAction::Set)
+            .help("Wrap matches in snippet windows with the left and right markers"))
+        .arg(Arg::new("word-radius")
+            .long("word-radius")
+            .aliases(["window-words", "words"])
+            .value_name("WORDS")
+            .value_parser(value_parser!(usize))
+            .conflicts_with("window-radius")
+            .action(ArgAction::Set)
+            .help("Maximum word radius for result snippet window instead of characters"))
+        .arg(Arg::new("multiline")
+            .long("multiline")
+            .aliases(["multi-line", "dotall"])
+            .action(ArgAction::SetTrue)
+            .help("Allow pattern matches to span multiple lines"))
+        .arg(Arg::new("collapse")
+            .long("collapse")
+            .aliases(["compact-folders", "collapse-dirs"])
+            .action(ArgAction::SetTrue)
+            .help("Display chains of single child directories on one line"))
+        .arg(Arg::new("max-entries")
+            .long("max-entries")
+            .aliases(["entry-budget", "max-visited"])
+            .value_name("ENTRIES")
+            .value_parser(value_parser!(usize))
+            .action(ArgAction::Set)
+            .help("Stop crawling after visiting this many filesystem entries"))
+        .arg(Arg::new("shallow-size")
+            .long("shallow-size")
+            .aliases(["own-size", "inode-size"])
+            .action(ArgAction::SetTrue)
+            .help("Display the size of directory entries themselves instead of their contents"))
+        .arg(Arg::new("no-ext-colors")
+            .long("no-ext-colors")
+            .aliases(["no-extension-colors"])
+            .action(ArgAction::SetTrue)
+            .help("Display all files using the same color regardless of extension"))
+        .arg(Arg::new("ext-color")
+            .long("ext-color")
+            .aliases(["ext-colors", "extension-color"])
+            .value_name("EXT=COLOR, ...")
+            .value_delimiter(',')
+            .value_parser(parse_ext_color)
+            .action(ArgAction::Append)
+            .help("Override the 8 bit color code used for files with the extension"))
+        .arg(Arg::new("ascii-space")
+            .long("ascii-space")
+            .aliases(["plain-space", "no-nbsp"])
+            .action(ArgAction::SetTrue)
+            .help("Use regular spaces instead of non-breaking spaces for indentation"))
+        .arg(Arg::new("select")
+            .long("select")
+            .aliases(["interactive", "pick"])
+            .action(ArgAction::SetTrue)
+            .help("Interactively select results and print their paths on exit"))
+        .arg(Arg::new("no-rippyignore")
+            .long("no-rippyignore")
+            .aliases(["no-rippy-ignore"])
+            .action(ArgAction::SetTrue)
+            .help("Do not use .rippyignore files when found for filtering"))     
+        .arg(Arg::new("show-unreadable")
+            .long("show-unreadable")
+            .aliases(["show-unread", "unreadable"])
+            .action(ArgAction::SetTrue)
+            .help("Display files that could not be read while searching with an [unreadable] marker"))
+        .arg(Arg::new("relative-to")
+            .long("relative-to")
+            .aliases(["rel-to", "relative-base"])
+            .value_name("DIR")
+            .help("Display paths relative to this directory instead of the root, falling back to absolute paths outside of it"))
+        .arg(Arg::new("json-paths")
+            .long("json-paths")
+            .aliases(["json-paths-relative-to", "json-path"])
+            .value_name("FORM")
+            .value_parser(parse_json_paths)
+            .help("Include the path of each entry in JSON exports, relative to the root with 'relative', to a directory with 'base=<DIR>' or 'absolute'"))
+        .arg(Arg::new("depth-summary")
+            .long("depth-summary")
+            .aliases(["depth-counts", "levels"])
+            .action(ArgAction::SetTrue)
+            .help("Display the number of entries found at each depth after the results"))
+        .arg(Arg::new("match-color")
+            .long("match-color")
+            .aliases(["highlight-color", "match-colour"])
+            .value_name("ANSI|HEX")
+            .value_parser(parse_match_color)
+            .help("Override the color used to highlight matches with an 8 bit color code or hex color"))
+        .arg(Arg::new("searched-color")
+            .long("searched-color")
+            .aliases(["search-count-color", "searched-colour"])
+            .value_name("ANSI|HEX")
+            .value_parser(parse_match_color)
+            .help("Override the color used for the searched count in the summary with an 8 bit color code or hex color"))
+        .arg(Arg::new("deterministic")
+            .long("deterministic")
+            .aliases(["reproducible", "stable"])
+            .action(ArgAction::SetTrue)
+            .help("Produce stable output for snapshots by fixing timestamps, omitting elapsed time and avoiding absolute paths"))
+        .arg(Arg::new("deep-size")
+            .long("deep-size")
+            .aliases(["full-size", "du-size"])
+            .action(ArgAction::SetTrue)
+            .conflicts_with("shallow-size")
+            .help("Include content beyond the maximum depth in the size of directories at the depth limit"))
+        .arg(Arg::new("one-file-system")
+            .long("one-file-system")
+            .aliases(["xdev", "same-fs"])
+            .action(ArgAction::SetTrue)
+            .help("Do not descend into directories on a different file system than the root"))
+        .arg(Arg::new("min-children")
+            .long("min-children")
+            .aliases(["min-entries", "min-dir-size"])
+            .value_name("N")
+            .value_parser(value_parser!(usize))
+            .action(ArgAction::Set)
+            .help("Hide directories containing fewer than this many entries unless they lead to one that is shown"))
+        .arg(Arg::new("bare-json")
+            .long("bare-json")
+            .aliases(["raw-json", "no-json-header"])
+            .action(ArgAction::SetTrue)
+            .help("Write JSON output as the bare root entry without the schema and metadata header"))
+        .arg(Arg::new("pattern-file")
+            .long("pattern-file")
+            .aliases(["patterns-from", "file-patterns"])
+            .value_name("PATH")
+            .help("Read patterns to search for from the file, one per line, matching any of them"))
+        .arg(Arg::new("fixed-strings")
+            .long("fixed-strings")
+            .aliases(["fixed", "literal"])
+            .action(ArgAction::SetTrue)
+            .help("Treat search patterns as literal strings instead of regular expressions"))
+        .arg(Arg::new("plain")
+            .long("plain")
+            .aliases(["plain-text", "plaintext"])
+            .action(ArgAction::SetTrue)
+            .help("Display plain output without colors or non-ASCII characters, equivalent to --gray --ascii --ascii-space"))
+        .arg(Arg::new("ext-count")
+            .long("ext-count")
+            .aliases(["extension-count", "count-ext"])
+            .action(ArgAction::SetTrue)
+            .help("Append the number of distinct file extensions found to the summary"))
+        .arg(Arg::new("flag-empty")
+            .long("flag-empty")
+            .aliases(["mark-empty", "flag-empty-files"])
+            .action(ArgAction::SetTrue)
+            .help("Mark zero-byte files as empty using muted styling"))
+        .arg(Arg::new("no-empty-files")
+            .long("no-empty-files")
+            .aliases(["hide-empty-files", "skip-empty-files"])
+            .action(ArgAction::SetTrue)
+            .help("Hide zero-byte files from the results"))
+        .arg(Arg::new("line-numbers")
+            .long("line-numbers")
+            .aliases(["line-number", "lines"])
+            .action(ArgAction::SetTrue)
+            .help("Display the line number of the first match before its window"))
+        .arg(Arg::new("no-bold")
+            .long("no-bold")
+            .aliases(["nobold", "unbold"])
+            .action(ArgAction::SetTrue)
+            .help("Display the results without bold styling while keeping colors"))
+        .arg(Arg::new("guide-tee")
+            .long("guide-tee")
+            .value_name("CHARS")
+            .value_parser(parse_guide)
+            .help("Override the connector drawn before entries that have siblings after them"))
+        .arg(Arg::new("guide-elbow")
+            .long("guide-elbow")
+            .value_name("CHARS")
+            .value_parser(parse_guide)
+            .help("Override the connector drawn before the last entry of a directory"))
+        .arg(Arg::new("guide-pipe")
+            .long("guide-pipe")
+            .value_name("CHARS")
+            .value_parser(parse_guide)
+            .help("Override the guide drawn alongside entries of a directory above"))
+        .arg(Arg::new("guide-dash")
+            .long("guide-dash")
+            .value_name("CHARS")
+            .value_parser(parse_guide)
+            .help("Override the character repeated to extend each connector"))
+        .arg(Arg::new("time-phases")
+            .long("time-phases")
+            .aliases(["phase-times", "profile"])
+            .action(ArgAction::SetTrue)
+            .help("Display the time spent crawling, building and rendering the tree"))
+        .arg(Arg::new("breadth-first")
+            .long("breadth-first")
+            .aliases(["level-order", "bfs"])
+            .action(ArgAction::SetTrue)
+            .help("Display the results as flat list in level order, listing all entries at each depth before the next"))
+        .arg(Arg::new("summary-top")
+            .long("summary-top")
+            .aliases(["summary-first", "top-summary"])
+            .action(ArgAction::SetTrue)
+            .help("Display the summary before the results instead of after"))
+        .arg(Arg::new("dereference")
+            .long("dereference")
+            .aliases(["deref", "target-size"])
+            .action(ArgAction::SetTrue)
+            .help("Display the size of the target of symbolic links instead of the link itself"))
+        .arg(Arg::new("no-summary-on-empty")
+            .long("no-summary-on-empty")
+            .aliases(["quiet-empty", "no-empty-summary"])
+            .action(ArgAction::SetTrue)
+            .help("Omit the summary line when nothing was found"))
+        .arg(Arg::new("bom")
+            .long("bom")
+            .aliases(["utf8-bom", "with-bom"])
+            .action(ArgAction::SetTrue)
+            .help("Prepend a UTF-8 byte order mark to exported files"))
+        .arg(Arg::new("glob")
+            .long("glob")
+            .aliases(["path-glob", "glob-mode"])
+            .action(ArgAction::SetTrue)
+            .help("Treat the pattern as a glob matched against paths relative to the root instead of searching contents"))
+        .arg(Arg::new("config")
+            .long("config")
+            .value_name("PATH")
+            .help("Read default options from the TOML file instead of 'rippy.toml' in the current directory"))
+        .arg(Arg::new("native-separators")
+            .long("native-separators")
+            .action(ArgAction::SetTrue)
+            .help("Display and export paths using the separator native to the platform rather than forward slashes"))
+        .arg(Arg::new("window-below")
+            .long("window-below")
+            .action(ArgAction::SetTrue)
+            .help("Place the search window on its own line beneath the file name instead of aligned beside it"))
+        .arg(Arg::new("verbose")
+            .long("verbose")
+            .action(ArgAction::SetTrue)
+            .help("Append the number of hidden and ignored entries skipped while crawling to the summary and warn if colors had to be disabled"))
+        .arg(Arg::new("du")
+            .long("du")
+            .action(ArgAction::SetTrue)
+            .help("Print only the total size of the root directory without rendering the tree, similar to du -sh"))
+        .arg(Arg::new("number-format")
+            .long("number-format")
+            .value_name("SPEC")
+            .value_parser(parse_number_format)
+            .help("Separators used for sizes, a locale such as en, de, fr or ch or the thousands then decimal separator, e.g. \".,\""))
+        .arg(Arg::new("exclude-dir")
+            .long("exclude-dir")
+            .aliases(["exclude-dirs"])
+            .value_name("DIR1, ..., DIRN")
+            .value_delimiter(',')
+            .action(ArgAction::Append)
+            .help("Prune directories matching these names along with their contents, leaving files of the same name"))
+        .arg(Arg::new("top-dirs")
+            .long("top-dirs")
+            .aliases(["largest-dirs"])
+            .value_name("N")
+            .value_parser(value_parser!(usize))
+            .help("List only the N largest directories by total size in place of the tree"))
+        .arg(Arg::new("error-format")
+            .long("error-format")
+            .value_name("FORMAT")
+            .value_parser(["text", "json"])
+            .help("Report errors as styled text or as JSON objects for tooling"))
+        .arg(Arg::new("filename-regex")
+            .long("filename-regex")
+            .aliases(["name-regex"])
+            .value_name("RE")
+            .help("Keep only files whose names match the regex, combined with any search pattern"))
+        .arg(Arg::new("name-style")
+            .long("name-style")
+            .aliases(["naming", "case-style"])
+            .value_name("STYLE")
+            .value_parser(["snake", "kebab", "camel", "pascal"])
+            .ignore_case(true)
+            .help("Keep only files whose names follow the naming convention, such as 'snake' for snake_case.rs"))
+        .arg(Arg::new("invert")
+            .long("invert")
+            .aliases(["invert-name-style", "violators"])
+            .action(ArgAction::SetTrue)
+            .requires("name-style")
+            .help("Keep only the files whose names break the --name-style convention instead"))
+        .arg(Arg::new("max-name-length")
+            .long("max-name-length")
+            .aliases(["max-name-len", "truncate-names"])
+            .value_name("N")
+            .value_parser(value_parser!(usize))
+            .help("Truncate displayed names longer than N characters with an ellipsis"))
+        .arg(Arg::new("notify")
+            .long("notify")
+            .aliases(["bell"])
+            .action(ArgAction::SetTrue)
+            .help("Ring the terminal bell and send an OSC 9 notification when finished"))
+        .arg(Arg::new("group-similar")
+            .long("group-similar")
+            .aliases(["group-sequences"])
+            .action(ArgAction::SetTrue)
+            .help("Collapse numbered sibling files such as frame0001.png into a single summary entry"))
+        .arg(Arg::new("ascii")
+            .long("ascii")
+            .aliases(["ascii-only", "no-unicode"])
+            .action(ArgAction::SetTrue)
+            .help("Render with ASCII characters only, replacing box-drawing connectors, non-breaking spaces and ellipses"))
+        .arg(Arg::new("no-root-bold")
+            .long("no-root-bold")
+            .aliases(["no-bold-root", "unbold-root"])
+            .action(ArgAction::SetTrue)
+            .help("Display the root directory without bold styling, leaving other entries bold"))
+        .arg(Arg::new("tracked")
+            .long("tracked")
+            .aliases(["git-tracked", "tracked-only"])
+            .action(ArgAction::SetTrue)
+            .help("List only files tracked by git along with the directories containing them"))
+        .arg(Arg::new("paths-from-null")
+            .long("paths-from-null")
+            .aliases(["null-paths", "stdin0", "read0"])
+            .conflicts_with("tracked")
+            .action(ArgAction::SetTrue)
+            .help("List only the paths read from stdin separated by NUL bytes, as written by find -print0, along with the directories containing them"))
+        .arg(Arg::new("subtree")
+            .long("subtree")
+            .aliases(["focus", "only-subtree"])
+            .value_name("PATH")
+            .help("Render only the subtree rooted at the path relative to the root"))
+        .arg(Arg::new("file-date")
+            .long("file-date")
+            .aliases(["file-dates"])
+            .action(ArgAction::SetTrue)
+            .help("Display the last modified datetime of files only"))
+        .arg(Arg::new("dir-date")
+            .long("dir-date")
+            .aliases(["dir-dates"])
+            .action(ArgAction::SetTrue)
+            .help("Display the last modified datetime of directories only"))
+        .arg(Arg::new("since")
+            .long("since")
+            .aliases(["newer-than", "modified-since"])
+            .value_name("DATE")
+            .value_parser(parse_date_bound)
+            .help("Only list and search files last modified on or after the date, as YYYY-MM-DD or YYYY-MM-DD HH:MM:SS in UTC"))
+        .arg(Arg::new("until")
+            .long("until")
+            .aliases(["older-than", "modified-before"])
+            .value_name("DATE")
+            .value_parser(parse_date_bound)
+            .help("Only list and search files last modified before the date, as YYYY-MM-DD or YYYY-MM-DD HH:MM:SS in UTC"))
+        .arg(Arg::new("atime-older")
+            .long("atime-older")
+            .aliases(["older-than-access", "not-accessed-within", "unused-for"])
+            .value_name("AGE")
+            .value_parser(parse_age)
+            .help("Only list and search files not accessed within the age, as a number of days or with a unit like 90d, 12h, 30m or 2w"))
+        .arg(Arg::new("show-config")
+            .long("show-config")
+            .aliases(["print-config", "dry-run"])
+            .action(ArgAction::SetTrue)
+            .help("Print the settings resolved from the config file and arguments instead of crawling"))
+        .arg(Arg::new("size-heat")
+            .long("size-heat")
+            .aliases(["heat", "size-colors"])
+            .action(ArgAction::SetTrue)
+            .help("Color sizes on a gradient from green for small to red for large, disabled for grayscale output"))
+        .arg(Arg::new("json-matches-only")
+            .long("json-matches-only")
+            .aliases(["json-only-matches", "export-matches-only"])
+            .action(ArgAction::SetTrue)
+            .help("Export only the matched files and the directories leading to them to JSON"))
+        .arg(Arg::new("elide-middle")
+            .long("elide-middle")
+            .aliases(["elide", "head-tail"])
+            .value_name("N")
+            .value_parser(value_parser!(usize))
+            .help("Display only the first and last N children of each directory with a marker for those in between"))
+        .arg(Arg::new("encoding")
+            .long("encoding")
+            .aliases(["charset"])
+            .value_name("LABEL")
+            .value_parser(parse_encoding)
+            .help("Decode file contents from the encoding before searching, such as latin1 or utf16le, instead of UTF-8"))
+        .arg(Arg::new("count-matches-total")
+            .long("count-matches-total")
+            .aliases(["total", "match-total"])
+            .action(ArgAction::SetTrue)
+            .help("Print only the number of matching files, or of matches with --count, for use in scripts"))
+        .arg(Arg::new("json-indent")
+            .long("json-indent")
+            .aliases(["indent-json"])
+            .value_name("N")
+            .value_parser(value_parser!(usize))
+            .help("Indent each level of exported JSON by N spaces, writing compact JSON for 0 [default: 2]"))
+        .arg(Arg::new("no-report")
+            .long("no-report")
+            .aliases(["noreport", "no-summary"])
+            .action(ArgAction::SetTrue)
+            .help("Omit the report of directory and file counts after the tree, matching tree --noreport"))
+        .arg(Arg::new("prune")
+            .long("prune")
+            .aliases(["prune-empty"])
+            .action(ArgAction::SetTrue)
+            .help("Remove directories left without any files, or without matches when searching"))
+        .arg(Arg::new("read-threads")
+            .long("read-threads")
+            .value_name("N")
+            .value_parser(value_parser!(usize))
+            .action(ArgAction::Set)
+            .help("Number of threads reading files while searching, separate from those walking the directory"))
+        .arg(Arg::new("show-root-abs")
+            .long("show-root-abs")
+            .aliases(["absolute-root"])
+            .action(ArgAction::SetTrue)
+            .help("Display the absolute path of the root while entries keep their own display"))
+        .arg(Arg::new("mount-summary")
+            .long("mount-summary")
+            .aliases(["mounts"])
+            .action(ArgAction::SetTrue)
+            .help("Print the entries and size found on each file system after the tree"))
+        .arg(Arg::new("flatten-single-child")
+            .long("flatten-single-child")
+            .aliases(["flatten-exports"])
+            .action(ArgAction::SetTrue)
+            .help("Merge chains of single child directories into one entry named by their joined path in exported files"))
+        .arg(Arg::new("dereference-all")
+            .long("dereference-all")
+            .aliases(["deref-all"])
+            .action(ArgAction::SetTrue)
+            .help("Treat symbolic links as their targets, following linked directories and displaying linked files without the link notation"))
+        .arg(Arg::new("summary-by-ext")
+            .long("summary-by-ext")
+            .aliases(["ext-summary", "by-ext"])
+            .action(ArgAction::SetTrue)
+            .help("Print the number of files and total size for each file extension after the summary"))
+        .arg(Arg::new("summary-sort")
+            .long("summary-sort")
+            .value_name("KEY")
+            .value_parser(["size", "count", "name"])
+            .ignore_case(true)
+            .hide_possible_values(true)
+            .action(ArgAction::Set)
+            .help("Order of the extension breakdown: 'size' [d], 'count' or 'name'"))
+        .arg(Arg::new("color-scale")
+            .long("color-scale")
+            .aliases(["colour-scale", "age-colors"])
+            .value_name("SCALE")
+            .value_parser(["date"])
+            .ignore_case(true)
+            .action(ArgAction::Set)
+            .help("Color files on a gradient from bright for recently modified to dim for old, implying the date is shown and disabled for grayscale output"))
+        .arg(Arg::new("utf8-lossy")
+            .long("utf8-lossy")
+            .aliases(["lossy", "lossy-utf8"])
+            .action(ArgAction::SetTrue)
+            .help("Search files with invalid UTF-8 by replacing the invalid bytes rather than skipping them as unreadable"))
+        .arg(Arg::new("progress")
+            .long("progress")
+            .aliases(["show-progress"])
+            .action(ArgAction::SetTrue)
+            .help("Show the percentage of entries processed on stderr while calculating directory sizes"))
+        .arg(Arg::new("max-line-length")
+            .long("max-line-length")
+            .aliases(["max-line-len", "long-lines"])
+            .value_name("N")
+            .value_parser(value_parser!(usize))
+            .action(ArgAction::Set)
+            .help("Cap the window to a small neighborhood of the match on lines longer than N characters, whatever the window radius"))
+        .arg(Arg::new("compact")
+            .long("compact")
+            .aliases(["flush-left", "no-margin"])
+            .action(ArgAction::SetTrue)
+            .help("Remove the left margin and trailing blank line from the rendered tree"))
+        .arg(Arg::new("diff")
+            .long("diff")
+            .aliases(["diff-against", "compare-to"])
+            .value_name("SNAPSHOT")
+            .conflicts_with_all(["table", "breadth-first"])
+            .action(ArgAction::Set)
+            .help("Mark entries added, removed or changed since a snapshot exported as JSON with --output"))
+        .arg(Arg::new("max-total-size")
+            .long("max-total-size")
+            .aliases(["size-budget", "max-scanned-size"])
+            .value_name("SIZE")
+            .value_parser(parse_size_budget)
+            .action(ArgAction::Set)
+            .help("Stop crawling once the files visited add up to more than this size, in bytes or with a unit like 500K, 10M or 2G"))
+        .arg(Arg::new("matching-dirs")
+            .long("matching-dirs")
+            .aliases(["dirs-with-matches", "containing-dirs"])
+            .action(ArgAction::SetTrue)
+            .help("List only the directories holding files kept after filtering, once each, in place of the tree"))
         .arg(Arg::new("version")
             .short('v')
             .short_alias('V')
@@ -319,37 +1140,171 @@ pub fn parse_args(args: Option<Vec<String>>) -> RippyArgs {
             .display_order(1000)
             .action(clap::ArgAction::Help));
      
-    let matches = if args.is_none() { rippy_cmd.get_matches() } else { rippy_cmd.get_matches_from(args.unwrap()) };
+    // Defaults from the config file are inserted ahead of the arguments provided, leaving out any option given explicitly so it replaces the config value rather than adding to it
+    let raw_args: Vec<String> = args.unwrap_or_else(|| std::env::args_os().map(|arg| arg.to_string_lossy().into_owned()).collect());
+    // Flags can be given an explicit value like `--size=false` to turn off one enabled by the config file
+    let (raw_args, flags_off) = resolve_flag_values(&rippy_cmd, raw_args);
+    let config_args = match find_config_path(&raw_args).filter(|_| is_config) {
+        Some(config_path) => match read_config(&config_path).and_then(|entries| convert_config_to_args(&rippy_cmd, entries, rippy_cmd.clone().try_get_matches_from(&raw_args).ok().as_ref(), &flags_off)) {
+            Ok(config_args) => config_args,
+            Err(e) => {
+                // Arguments haven't been parsed yet so the error format is taken from those provided directly
+                let is_json_errors = raw_args.iter().any(|arg| arg == "--error-format=json") || raw_args.windows(2).any(|pair| pair[0] == "--error-format" && pair[1] == "json");
+                let config_path = config_path.to_string_lossy().to_string();
+                RippyError::new(ErrorKind::Config, concat_str!("Unable to load config from '", config_path, "': ", e), Some(config_path)).report(is_json_errors, true);
+                std::process::exit(1);
+            }
+        },
+        None => Vec::new(),
+    };
+    let matches = rippy_cmd.get_matches_from(raw_args.iter().take(1).chain(config_args.iter()).chain(raw_args.iter().skip(1)));
+
+    // Report errors as JSON objects for tooling rather than styled text
+    let is_json_errors = matches.get_one::<String>("error-format").is_some_and(|format| format == "json");
+
+    // A root holding glob characters that doesn't exist as written, e.g. `rippy 'projects/*/src'`, is expanded to the directories it matches and crawled from the literal directory the glob starts in
+    // Without a separator or pattern alongside, a lone glob like `rippy "TODO*"` is left to be searched for as a pattern
+    let glob_root = matches.get_one::<String>("directory")
+        .filter(|dir| is_glob_root(dir) && (dir.contains(['/', '\\']) || matches.contains_id("pattern")))
+        .map(|glob| match expand_glob_root(glob) {
+            Ok(expanded) if !expanded.1.is_empty() => expanded,
+            Ok(_) => {
+                RippyError::new(ErrorKind::InvalidGlob, concat_str!("The glob provided as the root, '", glob, "', does not match any directories."), Some(glob.to_owned())).report(is_json_errors, true);
+                std::process::exit(1);
+            },
+            Err(e) => {
+                RippyError::new(ErrorKind::InvalidGlob, concat_str!("The glob provided as the root, '", glob, "', is invalid: ", e), Some(glob.to_owned())).report(is_json_errors, true);
+                std::process::exit(1);
+            },
+        });
 
     // Initial start directory to crawl
-    let directory_arg = matches.get_one::<String>("directory").map_or_else(|| ".".to_string(), |p| p.replace("\\", "/"));
+    // A lone positional that doesn't exist is treated as the pattern to search the current directory for, e.g. `rippy "TODO"`, unless it looks like a path so a mistyped directory still fails
+    let (directory_arg, pattern_arg) = match (matches.get_one::<String>("directory"), matches.get_one::<String>("pattern")) {
+        (Some(_), pat) if glob_root.is_some() => (glob_root.as_ref().map(|(base, _)| trim_trailing_separators(normalize_path(base))).unwrap_or_default(), pat.cloned()),
+        (Some(dir), None) if !std::path::Path::new(dir).exists() && !is_path_like(dir) => (".".to_string(), Some(dir.to_owned())),
+        (Some(dir), pat) if dir == "-" => (".".to_string(), pat.cloned()),
+        (dir, pat) => (dir.map_or_else(|| ".".to_string(), |p| trim_trailing_separators(normalize_path(std::path::Path::new(p)))), pat.cloned()),
+    };
     let directory = PathBuf::from(&directory_arg);
 
-    // Exit if only required argument, <directory>, does not exist or is not a valid directory to traverse
-    if !directory.exists() || !directory.is_dir() {
-        let error_fmt = ansi_color!(ERROR_COLOR, bold=true, "error:"); // (241, 76, 76)
-        let directory_fmt = ansi_color!(WARN_COLOR, bold=false, directory_arg); // (229, 229, 16)
-        eprintln!("{} The directory provided, '{}', does not exist or is not a valid directory.", error_fmt, directory_fmt);
+    // Exit if only required argument, <directory>, does not exist or is not a valid directory to traverse, a single file being rendered alone like `ls file`
+    let is_file_root = directory.is_file();
+    if !directory.is_dir() && !is_file_root {
+        RippyError::new(ErrorKind::InvalidDirectory, concat_str!("The directory provided, '", directory_arg, "', does not exist or is not a valid directory."), Some(directory_arg)).report(is_json_errors, true);
         std::process::exit(1);
     }
-     // Show full path
-     let show_full_path = matches.get_flag("full-path");
-     // Show full relative paths
-     let show_relative_path = matches.get_flag("relative-path");
+     // Stable output for snapshots that doesn't vary across machines or runs
+     let is_deterministic = matches.get_flag("deterministic");
+     // Show full path, displayed relative to the working directory instead when output should be deterministic
+     let show_full_path = matches.get_flag("full-path") && !is_deterministic;
+     // Base directory to display paths relative to in place of the root, resolved to an absolute path so any base can be compared against
+     let relative_to = matches.get_one::<String>("relative-to").map(|base| normalize_path_lexically(&std::path::absolute(base).unwrap_or_else(|_| PathBuf::from(base))))
+          .or_else(|| std::env::current_dir().ok().filter(|_| is_deterministic && matches.get_flag("full-path")).map(|cwd| normalize_path_lexically(&cwd)));
+     // Exit if the base to display paths relative to is not a valid directory
+     if let Some(base) = matches.get_one::<String>("relative-to").filter(|base| !std::path::Path::new(base).is_dir()) {
+          RippyError::new(ErrorKind::InvalidDirectory, concat_str!("The directory provided to --relative-to, '", base, "', does not exist or is not a valid directory."), Some(base.to_owned())).report(is_json_errors, true);
+          std::process::exit(1);
+     }
+     // Form of the paths added to JSON exports, resolved against the working directory like the base for displayed paths
+     let json_paths = matches.get_one::<JsonPaths>("json-paths").cloned();
+     // Show full relative paths, implied when displaying relative to another base
+     let show_relative_path = matches.get_flag("relative-path") || matches.get_flag("flat-paths") || relative_to.is_some();
 
      // Allows avoiding calling on dir entries since dir entry paths are derived from root path using 'rootpath + filename' approach
      let directory = if show_full_path {
-          PathBuf::from(std::path::absolute(std::path::Path::new(&directory)).map_or(directory_arg.to_owned(), |path| path.to_string_lossy().replace("\\","/")))
+          PathBuf::from(std::path::absolute(std::path::Path::new(&directory)).map_or(directory_arg.to_owned(), |path| normalize_path(&path)))
      } else {
           directory
      };
 
     // Pattern to search for in file contents
     let is_ignore_case = matches.get_flag("case-insensitive");
-    let pattern = matches.get_one::<String>("pattern").map_or_else(|| None, |pat| {if is_ignore_case {Some(Regex::new(&concat_str!("(?i)", &pat)).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e)).unwrap())} else {Some(Regex::new(&pat).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e)).unwrap())}});
+    // Allow matches to span lines by letting '.' match newlines
+    let is_multiline = matches.get_flag("multiline");
+    let pattern_flags = match (is_ignore_case, is_multiline) {
+        (true, true) => "(?is)",
+        (true, false) => "(?i)",
+        (false, true) => "(?s)",
+        (false, false) => "",
+    };
+    // The positional pattern filters paths instead of contents in glob mode
+    let (glob_arg, pattern_arg) = if matches.get_flag("glob") { (pattern_arg, None) } else { (None, pattern_arg) };
+    let path_glob = glob_arg.map(|glob| match GlobBuilder::new(&glob).literal_separator(true).case_insensitive(is_ignore_case).build() {
+        Ok(glob) => glob.compile_matcher(),
+        Err(e) => {
+            RippyError::new(ErrorKind::InvalidGlob, concat_str!("The glob provided, '", glob, "', is invalid: ", e.kind().to_string()), None).report(is_json_errors, true);
+            std::process::exit(1);
+        }
+    });
+    // Patterns read from a file are combined with any positional pattern as alternatives
+    let mut patterns: Vec<String> = pattern_arg.into_iter().collect();
+    // Line of each pattern read from the pattern file, to point at the one that fails to compile
+    let mut pattern_lines: Vec<Option<usize>> = vec![None; patterns.len()];
+    let pattern_file = matches.get_one::<String>("pattern-file");
+    if let Some(pattern_file) = pattern_file {
+        match read_pattern_file(pattern_file) {
+            Ok(file_patterns) => file_patterns.into_iter().for_each(|(line, pattern)| {
+                patterns.push(pattern);
+                pattern_lines.push(Some(line));
+            }),
+            Err(e) => {
+                RippyError::new(ErrorKind::PatternFile, concat_str!("Unable to read patterns from '", pattern_file, "': ", e.to_string()), Some(pattern_file.to_owned())).report(is_json_errors, true);
+                std::process::exit(1);
+            }
+        }
+    }
+    // Match patterns literally if requested by escaping any regex syntax
+    let is_fixed_strings = matches.get_flag("fixed-strings");
+    let pattern_text = if patterns.is_empty() { None } else { Some(patterns.join("|")) };
+    let pattern_source = match patterns.len() {
+        0 => None,
+        1 if !is_fixed_strings => Some(patterns[0].to_owned()),
+        _ => Some(patterns.iter().map(|pat| concat_str!("(?:", if is_fixed_strings { regex::escape(pat) } else { pat.to_owned() }, ")")).collect::<Vec<String>>().join("|")),
+    };
+    // Each pattern is compiled alone first so an invalid one is reported by itself rather than as part of the combined alternation
+    if !is_fixed_strings {
+        for (pat, line) in patterns.iter().zip(pattern_lines.iter()) {
+            if let Err(e) = Regex::new(&concat_str!(pattern_flags, pat)) {
+                let error = match (line, pattern_file) {
+                    (Some(line), Some(file)) => RippyError::new(ErrorKind::InvalidRegex, format!("The pattern '{pat}' on line {line} of '{file}' is invalid: {e}"), Some(file.to_owned())),
+                    _ => RippyError::new(ErrorKind::InvalidRegex, format!("The pattern '{pat}' is invalid: {e}"), None),
+                };
+                error.report(is_json_errors, true);
+                std::process::exit(1);
+            }
+        }
+    }
+    let pattern = match pattern_source.map(|pat| Regex::new(&concat_str!(pattern_flags, &pat))) {
+        Some(Ok(re)) => Some(re),
+        Some(Err(e)) => {
+            RippyError::new(ErrorKind::InvalidRegex, concat_str!("The patterns provided are invalid: ", e.to_string()), None).report(is_json_errors, true);
+            std::process::exit(1);
+        },
+        None => None,
+    };
     let is_search = pattern.is_some();
-    let ignore_patterns: Option<RegexSet> = matches.get_many::<String>("ignore").map_or_else(|| None, |v| Some(parse_and_convert_patterns(v.collect::<Vec<_>>(), is_ignore_case)));
-    let include_patterns: Option<RegexSet> = matches.get_many::<String>("include").map_or_else(|| None, |v| Some(parse_and_convert_patterns(v.collect::<Vec<_>>(), is_ignore_case)));
+    // Filename patterns provided directly are merged with any read from files, using the same conversion for both
+    let collect_globs = |id: &str, file_id: &str| -> Vec<String> {
+        let mut globs: Vec<String> = matches.get_many::<String>(id).map_or_else(Vec::new, |v| v.cloned().collect());
+        for path in matches.get_many::<String>(file_id).into_iter().flatten() {
+            match read_pattern_file(path) {
+                Ok(file_globs) => globs.extend(file_globs.iter().map(|(_, glob)| glob.trim().to_string())),
+                Err(e) => {
+                    RippyError::new(ErrorKind::PatternFile, concat_str!("Unable to read patterns from '", path, "': ", e.to_string()), Some(path.to_owned())).report(is_json_errors, true);
+                    std::process::exit(1);
+                }
+            }
+        }
+        globs
+    };
+    let ignore_globs = collect_globs("ignore", "ignore-file-list");
+    let include_globs = collect_globs("include", "include-file");
+    let ignore_patterns: Option<RegexSet> = if ignore_globs.is_empty() { None } else { Some(parse_and_convert_patterns(ignore_globs.iter().collect(), is_ignore_case)) };
+    let include_patterns: Option<RegexSet> = if include_globs.is_empty() { None } else { Some(parse_and_convert_patterns(include_globs.iter().collect(), is_ignore_case)) };
+    // Include patterns explicitly naming dotfiles match them whether or not hidden entries are shown
+    let dotfile_globs: Vec<&String> = include_globs.iter().filter(|glob| glob.starts_with('.')).collect();
+    let dotfile_include_patterns: Option<RegexSet> = if dotfile_globs.is_empty() { None } else { Some(parse_and_convert_patterns(dotfile_globs, is_ignore_case)) };
     
     // Include hidden and other directories set to be ignored by default
     let include_all = matches.get_flag("all");
@@ -361,7 +1316,15 @@ pub fn parse_args(args: Option<Vec<String>>) -> RippyArgs {
     let max_files = *matches.get_one::<usize>("max-files").unwrap_or(&usize::MAX);
 
     // Output tree as JSON to specified file
-    let output = matches.get_one::<String>("output").map_or_else(|| "".to_string(), |s| s.to_string());
+    let output: Vec<String> = matches.get_many::<String>("output").map(|vals| vals.filter(|s| !s.is_empty()).cloned().collect()).unwrap_or_default();
+
+    // Output files written inside the root are excluded so a previous export isn't listed in the next
+    let absolute_normalized = |path: &std::path::Path| normalize_path_lexically(&std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf()));
+    let root_path = absolute_normalized(&directory);
+    let excluded_paths: Vec<PathBuf> = output.iter()
+        .map(|output| absolute_normalized(std::path::Path::new(output)))
+        .filter(|output| output.starts_with(&root_path))
+        .collect();
 
     // Indentation width to use for new level when displaying tree
     let indent = *matches.get_one::<usize>("indent").unwrap_or(&2_usize);
@@ -369,93 +1332,811 @@ pub fn parse_args(args: Option<Vec<String>>) -> RippyArgs {
     // Use reversed sort ordering
     let reverse = matches.get_flag("reverse");
 
+    // Reverse only the leaf level of directories holding just files, or every level but that one
+    let reverse_levels = match matches.get_one::<String>("reverse-levels").map(|levels| levels.to_lowercase()).as_deref() {
+        Some("files") => Some(ReverseLevels::Files),
+        Some("dirs") => Some(ReverseLevels::Dirs),
+        _ => None,
+    };
+
     // Sort tree by ordering
-    let sort_by =  match matches.get_one::<String>("sort-by").unwrap_or(&"name".to_string()).to_lowercase().as_ref() {
-          "date" => SortKey::Date(!reverse).compare(),
-          "name" => SortKey::Name(!reverse).compare(),
-          "size" => SortKey::Size(!reverse).compare(),
-          "type" => SortKey::Type(!reverse).compare(),
-               _ => SortKey::Name(!reverse).compare(),
+    // Fuzzy matches are ranked by score unless another ordering is requested
+    let is_fuzzy = matches.get_one::<String>("fuzzy").is_some_and(|query| !query.is_empty());
+    let sort_key = match matches.value_source("sort-by") {
+        Some(ValueSource::DefaultValue) | None if is_fuzzy => "score".to_string(),
+        _ => matches.get_one::<String>("sort-by").map_or_else(|| "name".to_string(), |key| key.to_lowercase()),
+    };
+    let sort_for = |is_ascending: bool| match sort_key.as_ref() {
+          "date" => SortKey::Date(is_ascending),
+          "name" => SortKey::Name(is_ascending),
+          "size" => SortKey::Size(is_ascending),
+          "type" => SortKey::Type(is_ascending),
+         "score" => SortKey::Score(is_ascending),
+               _ => SortKey::Name(is_ascending),
      };
+    let (sort_by_key, sort_leaves_by_key) = match reverse_levels {
+        Some(ReverseLevels::Files) => (sort_for(true), sort_for(false)),
+        Some(ReverseLevels::Dirs) => (sort_for(false), sort_for(true)),
+        None => (sort_for(!reverse), sort_for(!reverse)),
+    };
+    let (sort_by, sort_leaves_by) = (sort_by_key.compare(), sort_leaves_by_key.compare());
+
+    // Display the size of the directory entry itself like `ls -ld` rather than the recursive total
+    let is_shallow_size = matches.get_flag("shallow-size");
+
+    // Measure the content beyond the maximum depth that is otherwise not crawled for directories at the depth limit
+    let is_deep_size = matches.get_flag("deep-size");
+
+    // Print only the total size of the root similar to `du -sh`
+    let is_du = matches.get_flag("du");
+
+    // Number of largest directories to list in place of the tree
+    let top_dirs = matches.get_one::<usize>("top-dirs").copied();
 
     // Display dir-detail details for both file and directory types
-    let is_dir_detail = matches.get_flag("dir-detail");
+    let is_dir_detail = matches.get_flag("dir-detail") || is_shallow_size || is_deep_size;
+
+    // Display results as aligned columns, which always includes the size and date columns similar to `ls -l`
+    let is_table = matches.get_flag("table");
 
     // Determine if size should be displayed
-    let show_size = matches.get_flag("size");
+    let show_size = matches.get_flag("size") || is_table || is_shallow_size || is_deep_size || is_du || top_dirs.is_some();
+
+    // Display the last modified date of files only, leaving directories without one
+    let is_file_date = matches.get_flag("file-date");
+
+    // Display the last modified date of directories only, leaving files without one
+    let is_dir_date = matches.get_flag("dir-date");
 
     // Show last modified date only in short format
     let date_format = matches.get_one::<String>("date-format").map_or_else(|| "%Y-%m-%d %H:%M:%S".to_string(), |fmt| fmt.to_string());
-    let show_date = matches.get_flag("date") || matches!(matches.value_source("date-format"), Some(ValueSource::CommandLine));
+    // Gradient to color entries on, with aging by date needing the modified dates shown
+    let color_scale = matches.get_one::<String>("color-scale").map(|_| ColorScale::Date);
+    let show_date = matches.get_flag("date") || matches!(matches.value_source("date-format"), Some(ValueSource::CommandLine)) || is_table || is_file_date || is_dir_date || color_scale.is_some();
+    // Sorting by date or size needs them read whether or not they are displayed
+    let is_stat_date = show_date || sort_key == "date";
 
     // Elapsed search time
-    let show_elapsed = matches.get_flag("time");
+    let show_elapsed = matches.get_flag("time") && !is_deterministic;
 
     // Select color schema based on arguments and ansi support and if search pattern is present
-    let is_grayscale = matches.get_flag("gray") || !std::io::stdout().is_terminal() || !enable_ansi_support();
-    let colors: RippySchema = RippySchema::get_color_schema(is_grayscale);
+    // Plain output for maximum compatibility combines grayscale, ASCII connectors and regular spaces
+    let is_plain = matches.get_flag("plain");
+    // Render ASCII only by replacing connectors, non-breaking spaces and ellipses, implied by plain output
+    let is_ascii = matches.get_flag("ascii") || is_plain;
+    // Colors are dropped when output isn't a terminal unless the consumer is known to render them
+    let is_color_wanted = !matches.get_flag("gray") && !is_plain && (matches.get_flag("no-pipe") || std::io::stdout().is_terminal());
+    let ansi_error = if is_color_wanted { try_enable_ansi_support().err() } else { None };
+    let is_grayscale = !is_color_wanted || ansi_error.is_some();
+    // Explain why output is plain when colors were wanted but the console refused them
+    if let Some(e) = ansi_error.filter(|_| matches.get_flag("verbose")) {
+        eprintln!("{}", format_ansi_warning(&e));
+    }
+    let mut colors: RippySchema = RippySchema::get_color_schema(is_grayscale);
+    // Override only the match highlight color if provided and output is styled
+    if let Some(match_color) = matches.get_one::<String>("match-color").filter(|_| !is_grayscale) {
+        colors.window = Some(match_color.to_owned());
+    }
+    // Override the searched count color the same way, leaked once to share the lifetime of the schema colors
+    if let Some(searched_color) = matches.get_one::<String>("searched-color").filter(|_| !is_grayscale) {
+        colors.search = Some(Box::leak(searched_color.to_owned().into_boxed_str()));
+    }
 
     // Use double-quotes when displaying paths
     let is_quote = matches.get_flag("quote");
     
     // Display tree as flattened list
-    let is_flat = matches.get_flag("flat");
+    let is_flat = matches.get_flag("flat") || matches.get_flag("flat-paths") || matches.get_flag("breadth-first");
 
     // Development addition to display just summary counts without rendering tree
     let is_just_counts = matches.get_flag("just-counts");
 
+    // Include the scan root itself when counting directories
+    let is_count_root = matches.get_flag("count-root");
+
+    // Legend is only meaningful when colors are actually being rendered
+    let is_legend = matches.get_flag("legend");
+
+    // Fuzzy filename query applied as a filter once the tree has been built
+    let fuzzy = matches.get_one::<String>("fuzzy").filter(|q| !q.is_empty()).cloned();
+
+    // Extensions considered executable for styling, normalized without leading dot for comparison
+    let exec_extensions: Vec<String> = matches.get_many::<String>("exec-ext")
+        .map(|vals| vals.map(|ext| ext.trim().trim_start_matches('.').to_lowercase()).filter(|ext| !ext.is_empty()).collect())
+        .unwrap_or_default();
+
+    // Elision string and optional literal markers used when constructing snippets
+    let ellipsis = matches.get_one::<String>("ellipsis").map_or_else(|| "...".to_string(), |s| s.to_string());
+    let mark = matches.get_many::<String>("mark").map(|mut vals| (vals.next().cloned().unwrap_or_default(), vals.next().cloned().unwrap_or_default()));
+
+    // Radius in whitespace delimited words used for snippets instead of characters if provided
+    let word_radius = matches.get_one::<usize>("word-radius").copied();
+
+    // Merge chains of directories containing a single directory into one entry
+    let is_collapse = matches.get_flag("collapse");
+
+    // Budget of filesystem entries to visit before the crawl is stopped early
+    let max_entries = *matches.get_one::<usize>("max-entries").unwrap_or(&usize::MAX);
+
+    // Per-extension file colors, with overrides leaked since the args live for the duration of the program
+    let is_ext_colors = !matches.get_flag("no-ext-colors");
+    let ext_colors: Vec<(String, &'static str)> = matches.get_many::<(String, u8)>("ext-color")
+        .map(|vals| vals.map(|(ext, code)| (ext.to_owned(), &*Box::leak(format!("\x1b[38;5;{code}m").into_boxed_str()))).collect())
+        .unwrap_or_default();
+
+    // Indent using regular spaces for tools that don't treat non-breaking spaces as whitespace
+    let is_ascii_space = matches.get_flag("ascii-space") || is_ascii;
+
+    // Interactive selection of results in place of rendering the tree
+    let is_select = matches.get_flag("select");
+
+    // Whether or not rippy specific ignore files should be used, independent of the gitignore option
+    let is_rippyignore = !matches.get_flag("no-rippyignore");
+
+    // Keep files that failed to be read while searching in the results, marked as unreadable
+    let is_show_unreadable = matches.get_flag("show-unreadable");
+
+    // Breakdown of the number of entries at each depth after the summary
+    let is_depth_summary = matches.get_flag("depth-summary");
+
+    // Stay on the file system of the root directory when descending
+    let is_one_file_system = matches.get_flag("one-file-system");
+
+    // Minimum number of entries a directory must contain to be displayed, zero if not provided
+    let min_children = *matches.get_one::<usize>("min-children").unwrap_or(&0);
+
+    // Write JSON output in the original shape of only the root entry without the metadata header
+    let is_bare_json = matches.get_flag("bare-json");
+
+    // Characters used to draw the tree connectors, plain ASCII for plain output
+    let mut glyphs = if is_ascii { ASCII_GLYPHS } else { UNICODE_GLYPHS };
+    // Individual guide overrides take precedence over the preset, leaked once to share the lifetime of the presets
+    let guide = |id: &str| matches.get_one::<String>(id).map(|chars| &*Box::leak(chars.to_owned().into_boxed_str()));
+    glyphs.tee = guide("guide-tee").unwrap_or(glyphs.tee);
+    glyphs.elbow = guide("guide-elbow").unwrap_or(glyphs.elbow);
+    glyphs.pipe = guide("guide-pipe").unwrap_or(glyphs.pipe);
+    glyphs.dash = guide("guide-dash").unwrap_or(glyphs.dash);
+
+    // Append the number of distinct file extensions to the summary
+    let is_ext_count = matches.get_flag("ext-count");
+
+    // Mark zero-byte files so placeholders stand out
+    let is_flag_empty = matches.get_flag("flag-empty");
+
+    // Remove zero-byte files from the tree
+    let is_no_empty_files = matches.get_flag("no-empty-files");
+
+    // Prefix the window with the line number of the match
+    let is_line_numbers = matches.get_flag("line-numbers");
+
+    // Drop bold styling while keeping colors
+    let is_no_bold = matches.get_flag("no-bold");
+
+    // Display the root without bold styling, implied by no bold at all
+    let is_no_root_bold = matches.get_flag("no-root-bold") || is_no_bold;
+
+    // Break the elapsed time down by phase, omitted from deterministic output like the total
+    let is_time_phases = matches.get_flag("time-phases") && !is_deterministic;
+
+    // List the entries in level order instead of rendering the tree
+    let is_breadth_first = matches.get_flag("breadth-first");
+
+    // Print the summary ahead of the tree, buffering the tree since counts are gathered while rendering
+    let is_summary_top = matches.get_flag("summary-top");
+
+    // Treat symbolic links entirely as their targets, implying --follow-links and --dereference
+    let is_dereference_all = matches.get_flag("dereference-all");
+
+    // Measure symbolic links by their target instead of the link itself
+    let is_dereference = matches.get_flag("dereference") || is_dereference_all;
+
+    // Leave out the summary when there are no results
+    let is_no_summary_on_empty = matches.get_flag("no-summary-on-empty");
+
+    // Start exported files with a UTF-8 byte order mark for tools expecting one
+    let is_bom = matches.get_flag("bom");
+
+    // Use the platform separator for displayed paths
+    let is_native_separators = matches.get_flag("native-separators");
+
+    // Place windows on the line below the entry rather than padded beside it
+    let is_window_below = matches.get_flag("window-below");
+
+    // Append the counts of entries skipped while crawling to the summary
+    let is_verbose = matches.get_flag("verbose");
+
+    // Separators used when rendering numbers such as sizes
+    let number_format = matches.get_one::<NumberFormat>("number-format").copied().unwrap_or(US_NUMBER_FORMAT);
+
+    // Directory names to prune along with their contents, leaving files of the same name
+    let exclude_dir_patterns = matches.get_many::<String>("exclude-dir")
+        .map(|vals| vals.map(|dir| dir.trim().trim_end_matches(['/', '\\']).to_string()).filter(|dir| !dir.is_empty()).collect::<Vec<String>>())
+        .filter(|dirs| !dirs.is_empty())
+        .map(|dirs| parse_and_convert_patterns(dirs.iter().collect(), is_ignore_case));
+
+    // Regex file names must match to be kept, checked before any contents are read
+    let filename_regex = matches.get_one::<String>("filename-regex").map(|re| match regex::RegexBuilder::new(re).case_insensitive(is_ignore_case).build() {
+        Ok(re) => re,
+        Err(e) => {
+            RippyError::new(ErrorKind::InvalidRegex, concat_str!("The regex provided to --filename-regex is invalid: ", e.to_string()), None).report(is_json_errors, true);
+            std::process::exit(1);
+        }
+    });
+
+    // Naming convention file names must follow, or break when inverted to audit the violators
+    let name_style = matches.get_one::<String>("name-style").and_then(|style| NameStyle::new(style, matches.get_flag("invert")));
+
+    // Maximum characters of names to display before truncating with an ellipsis
+    let max_name_length = matches.get_one::<usize>("max-name-length").copied();
+
+    // Notify with the terminal bell and an OSC 9 desktop notification once finished, emitted only when stdout is a terminal
+    let is_notify = matches.get_flag("notify");
+
+    // Collapse runs of numbered sibling files into a single summary entry
+    let is_group_similar = matches.get_flag("group-similar");
+
+    // Directories matched by a glob given as the root, as absolute normalized paths, listed along with the directories leading to them
+    let glob_roots = glob_root.map(|(_, roots)| roots.iter().map(|root| absolute_normalized(root)).collect::<Vec<PathBuf>>());
+
+    // Files tracked by git and the directories containing them, as absolute normalized paths, listed in place of whatever is on disk
+    let tracked_paths = matches.get_flag("tracked").then(|| match read_tracked_paths(&directory) {
+        Ok(tracked) => tracked.iter().map(|path| absolute_normalized(&directory.join(path))).chain([root_path.clone()]).collect::<HashSet<PathBuf>>(),
+        Err(e) => {
+            RippyError::new(ErrorKind::Git, concat_str!("Unable to list the files tracked by git in '", directory_arg, "': ", e), Some(directory_arg.to_owned())).report(is_json_errors, true);
+            std::process::exit(1);
+        }
+    });
+
+    // Paths read from stdin separated by NUL bytes like `find -print0` writes them, relative to the working directory, listed with the directories leading to them from the root in the same way
+    let stdin_paths = matches.get_flag("paths-from-null").then(|| match read_null_paths(std::io::stdin().lock()) {
+        Ok(paths) => {
+            // Paths outside the root can't be listed in its tree, so they are reported rather than silently dropped
+            let (within_root, outside_root): (Vec<&PathBuf>, Vec<&PathBuf>) = paths.iter().partition(|path| absolute_normalized(path).starts_with(&root_path));
+            if within_root.is_empty() && !outside_root.is_empty() {
+                RippyError::new(ErrorKind::InvalidDirectory, concat_str!("None of the paths read from stdin are within the directory provided, '", directory_arg, "'."), Some(directory_arg.to_owned())).report(is_json_errors, true);
+                std::process::exit(1);
+            }
+            if let Some(first_outside) = outside_root.first() {
+                eprintln!("{} {} {} read from stdin {} outside of '{}' and skipped, such as '{}'", ansi_color!(WARN_COLOR, bold=!is_no_bold, "Warning:"), outside_root.len(), if outside_root.len() == 1 { "path" } else { "paths" }, if outside_root.len() == 1 { "was" } else { "were" }, directory_arg, normalize_path(first_outside));
+            }
+            let mut listed: HashSet<PathBuf> = HashSet::from([root_path.clone()]);
+            for path in within_root.into_iter().map(|path| absolute_normalized(path)) {
+                let mut ancestor = path.parent();
+                while let Some(dir) = ancestor.filter(|dir| dir.starts_with(&root_path) && !listed.contains(*dir)) {
+                    listed.insert(dir.to_path_buf());
+                    ancestor = dir.parent();
+                }
+                listed.insert(path);
+            }
+            listed
+        },
+        Err(e) => {
+            RippyError::new(ErrorKind::Read, concat_str!("Unable to read the paths from stdin: ", e.to_string()), None).report(is_json_errors, true);
+            std::process::exit(1);
+        }
+    });
+    let listed_paths = tracked_paths.or(stdin_paths);
+
+    // Path relative to the root of the only subtree to render
+    let subtree = matches.get_one::<String>("subtree").map(|path| normalize_path(Path::new(path)).trim_matches('/').to_string()).filter(|path| !path.is_empty() && path != ".");
+
+    // Only files last modified on or after this date are kept and searched
+    let since = matches.get_one::<f64>("since").copied();
+
+    // Only files last modified before this date are kept and searched
+    let until = matches.get_one::<f64>("until").copied();
+
+    // Only files not accessed within this many seconds of now are kept and searched
+    let accessed_before = matches.get_one::<f64>("atime-older").map(|age| chrono::Utc::now().timestamp() as f64 - age);
+
+    // Print the resolved settings in place of crawling to check how the config file and arguments combine
+    let is_show_config = matches.get_flag("show-config");
+
+    // Color sizes by magnitude in place of the uniform detail color, disabled along with colors
+    let is_size_heat = matches.get_flag("size-heat") && !is_grayscale;
+    let color_scale = color_scale.filter(|_| !is_grayscale);
+
+    // Export only matches and their ancestors to JSON, dropping directories without any
+    let is_json_matches_only = matches.get_flag("json-matches-only");
+
+    // Number of children to keep at each end of a directory, eliding those in between
+    let elide_middle = matches.get_one::<usize>("elide-middle").copied().filter(|keep| *keep > 0);
+
+    // Encoding to decode file contents from before searching, UTF-8 if not provided
+    let encoding = matches.get_one::<&Encoding>("encoding").copied();
+
+    // Print only the total number of matching files, or matches with just counts, skipping the tree and summary
+    let is_count_matches_total = matches.get_flag("count-matches-total");
+
+    // Spaces to indent each level of exported JSON by, writing compact JSON for zero
+    let json_indent = matches.get_one::<usize>("json-indent").copied().unwrap_or(2);
+
+    // Omit the summary report entirely like `tree --noreport`
+    let is_no_report = matches.get_flag("no-report");
+
+    // Remove directories without any files beneath them once filtering is done, or without matches when searching
+    let is_prune = matches.get_flag("prune");
+
+    // Maximum number of threads used to read files while searching, separate from those walking the directory
+    let read_threads = matches.get_one::<usize>("read-threads").copied().filter(|&threads| threads > 0);
+
+    // Show the absolute path of the root even when entries are displayed by name or relative path, unless output should be deterministic
+    let is_show_root_abs = matches.get_flag("show-root-abs") && !is_deterministic;
+
+    // Summarize the entries and size found on each file system after the tree
+    let is_mount_summary = matches.get_flag("mount-summary");
+
+    // Merge chains of single child directories into compound paths within exported files, leaving the rendered tree as is
+    let is_flatten_single_child = matches.get_flag("flatten-single-child");
+
+    // Break down the files by extension after the summary, with their count and total size
+    let is_summary_by_ext = matches.get_flag("summary-by-ext");
+
+    // Sizes are read whether or not they are displayed when needed to recognize empty files or total extensions
+    let is_stat_size = show_size || is_flag_empty || is_no_empty_files || is_summary_by_ext;
+    // Sorting by size only reads the sizes of files, leaving directories unsized unless they are displayed
+    let is_size_sort = sort_key == "size";
+
+    // Order of the extension breakdown, largest total size first by default
+    let summary_sort = match matches.get_one::<String>("summary-sort").map(|key| key.to_lowercase()).as_deref() {
+        Some("name") => SummarySort::Name,
+        Some("count") => SummarySort::Count,
+        _ => SummarySort::Size,
+    };
+
+    // Decode invalid UTF-8 with replacement characters so mostly valid files are still searched
+    let is_utf8_lossy = matches.get_flag("utf8-lossy");
+
+    // Report the progress of the directory size pass on stderr when it is a terminal
+    let is_progress = matches.get_flag("progress");
+
+    // Lines longer than this are capped to a small window around the match, such as in minified files
+    let max_line_length = matches.get_one::<usize>("max-line-length").copied().filter(|length| *length > 0);
+
+    // Drop the left margin and trailing blank line for flush-left output
+    let is_compact = matches.get_flag("compact");
+
+    // Snapshot exported earlier with --output to mark what has been added, removed or changed since when rendering the tree
+    let diff = matches.get_one::<String>("diff").map(|path| match Snapshot::read(path) {
+        Ok(snapshot) => snapshot,
+        Err(e) => {
+            RippyError::new(ErrorKind::Read, concat_str!("Unable to read the snapshot '", path, "' to compare against: ", e), Some(path.to_owned())).report(is_json_errors, true);
+            std::process::exit(1);
+        }
+    });
+
+    // Bytes of files to scan before the crawl is stopped early with partial results
+    let max_total_size = matches.get_one::<u64>("max-total-size").copied();
+
+    // List only the directories holding the files kept in place of the tree
+    let is_matching_dirs = matches.get_flag("matching-dirs");
+
+    // Directory names to display without descending into
+    let opaque_dirs: Vec<String> = matches.get_many::<String>("opaque-dir")
+        .map(|vals| vals.map(|dir| dir.trim().trim_end_matches(['/', '\\']).to_string()).filter(|dir| !dir.is_empty()).collect())
+        .unwrap_or_default();
+
     // Follow symbolic links when found if target points to directory
-    let is_follow_links = matches.get_flag("follow-links");
+    let is_follow_links = matches.get_flag("follow-links") || is_dereference_all;
 
     // Display enumerated position of entry within parent directory
     let is_enumerate = matches.get_flag("enumerate");
 
     // Whether or not gitignore files should be used to filter results using specified globs and patterns
-    let is_gitignore = !matches.get_flag("no-gitignore"); // More like asking "is no gitignore flag present? If not, then yes is gitignore, false otherwise"
+    // Tracked files were already chosen by git, and paths read from stdin by whatever listed them, so ignore files aren't applied again
+    let is_gitignore = !matches.get_flag("no-gitignore") && listed_paths.is_none(); // More like asking "is no gitignore flag present? If not, then yes is gitignore, false otherwise"
 
     // Display context window with search results and character radius window if present, assuming a window was requested if radius is specified without explicit window flag
     let is_window = !matches.get_flag("windowless");
     let radius = *matches.get_one::<usize>("window-radius").unwrap_or(&20_usize);
+    // Radius on either side of the match, each overriding the radius independently
+    let radius_before = matches.get_one::<usize>("window-before").copied().unwrap_or(radius);
+    let radius_after = matches.get_one::<usize>("window-after").copied().unwrap_or(radius);
 
     RippyArgs {
         directory,
+        is_file_root,
         pattern,
+        pattern_text,
         is_search,
         ignore_patterns,
         include_all,
         include_patterns,
+        dotfile_include_patterns,
+        path_glob,
         max_depth,
         max_files,
         output,
+        excluded_paths,
         indent,
         sort_by,
+        sort_leaves_by,
+        sort_by_key,
+        sort_leaves_by_key,
         is_dir_detail,
         show_full_path,
         show_relative_path,
         show_size,
         show_date,
+        is_stat_date,
+        is_stat_size,
+        is_size_sort,
         date_format,
         show_elapsed,
         is_grayscale,
         is_quote,
         is_flat,
+        is_table,
         is_window,
         is_just_counts,
+        is_count_root,
+        is_legend,
+        fuzzy,
+        exec_extensions,
+        opaque_dirs,
+        ellipsis,
+        mark,
+        word_radius,
+        is_multiline,
+        is_collapse,
+        max_entries,
+        is_shallow_size,
+        is_ext_colors,
+        ext_colors,
+        is_ascii_space,
+        is_select,
+        is_rippyignore,
+        is_show_unreadable,
+        relative_to,
+        json_paths,
+        is_depth_summary,
+        is_deterministic,
+        is_deep_size,
+        is_one_file_system,
+        min_children,
+        is_bare_json,
+        glyphs,
+        is_ext_count,
+        is_flag_empty,
+        is_no_empty_files,
+        is_line_numbers,
+        is_no_bold,
+        is_time_phases,
+        is_breadth_first,
+        is_summary_top,
+        is_dereference,
+        is_no_summary_on_empty,
+        is_bom,
+        is_native_separators,
+        is_window_below,
+        is_verbose,
+        is_du,
+        number_format,
+        exclude_dir_patterns,
+        top_dirs,
+        is_json_errors,
+        filename_regex,
+        name_style,
+        max_name_length,
+        is_notify,
+        is_group_similar,
+        is_ascii,
+        is_no_root_bold,
+        listed_paths,
+        glob_roots,
+        subtree,
+        is_file_date,
+        is_dir_date,
+        since,
+        until,
+        accessed_before,
+        is_show_config,
+        is_size_heat,
+        is_json_matches_only,
+        elide_middle,
+        encoding,
+        is_count_matches_total,
+        json_indent,
+        is_no_report,
+        is_prune,
+        read_threads,
+        is_show_root_abs,
+        is_mount_summary,
+        is_flatten_single_child,
+        is_dereference_all,
+        is_summary_by_ext,
+        summary_sort,
+        color_scale,
+        is_utf8_lossy,
+        is_progress,
+        max_line_length,
+        is_compact,
+        diff,
+        max_total_size,
+        is_matching_dirs,
         is_enumerate,
         is_follow_links,
         is_gitignore,
         radius,
+        radius_before,
+        radius_after,
         colors
     }
 }
 
-/// Parses and converts the Vec<String> of arguments collected from "ignore" or "pattern" into regex sets based on wildcards present
+/// Parses an extension color override in the format of `EXT=COLOR` where `COLOR` is an 8 bit ANSI color code from 0 to 255.
+fn parse_ext_color(value: &str) -> Result<(String, u8), String> {
+    let (ext, code) = value.split_once('=').ok_or_else(|| format!("expected 'EXT=COLOR' but found '{value}'"))?;
+    let ext = ext.trim().trim_start_matches('.').to_lowercase();
+    if ext.is_empty() {
+        return Err(format!("missing extension in '{value}'"));
+    }
+    let code = code.trim().parse::<u8>().map_err(|_| format!("color code in '{value}' must be a number from 0 to 255"))?;
+    Ok((ext, code))
+}
+
+/// Parses the color provided to `--match-color` as either an 8 bit color code, e.g. `42`, or a hex color, e.g. `#ff8800`, into the escape sequence used to apply it.
+fn parse_match_color(value: &str) -> Result<String, String> {
+    let value = value.trim();
+    if let Ok(code) = value.parse::<u8>() {
+        return Ok(format!("\x1b[38;5;{code}m"));
+    }
+    let hex = value.strip_prefix('#').unwrap_or(value);
+    if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!("expected a color code from 0 to 255 or a hex color like '#ff8800' but found '{value}'"));
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).unwrap_or_default();
+    Ok(format!("\x1b[38;2;{};{};{}m", channel(0), channel(2), channel(4)))
+}
+
+/// Converts the entries read from the config file into the equivalent command line options, matching each key to the long name or an alias of an option. Entries for options given on the command line, or for options conflicting with them, are left out so the command line takes precedence.
+fn convert_config_to_args(rippy_cmd: &Command, entries: Vec<(String, Vec<ConfigValue>)>, cli_matches: Option<&clap::ArgMatches>, flags_off: &[String]) -> Result<Vec<String>, String> {
+    let is_given = |arg: &Arg| flags_off.iter().any(|id| id == arg.get_id().as_str()) || cli_matches.is_some_and(|matches| matches.value_source(arg.get_id().as_str()) == Some(ValueSource::CommandLine));
+    let mut config_args = Vec::new();
+    for (key, values) in entries {
+        let option = rippy_cmd.get_arguments()
+            .filter(|arg| !matches!(arg.get_id().as_str(), "config" | "help" | "version"))
+            .find(|arg| arg.get_long() == Some(key.as_str()) || arg.get_all_aliases().is_some_and(|aliases| aliases.contains(&key.as_str())))
+            .ok_or_else(|| format!("unknown option '{key}'"))?;
+        if is_given(option) || rippy_cmd.get_arg_conflicts_with(option).into_iter().any(is_given) {
+            continue;
+        }
+        let long = option.get_long().unwrap_or_default();
+        let takes_values = option.get_action().takes_values();
+        for value in values {
+            match value {
+                ConfigValue::Bool(true) if !takes_values => config_args.push(concat_str!("--", long)),
+                ConfigValue::Bool(false) if !takes_values => {},
+                ConfigValue::Text(text) if takes_values => config_args.push(concat_str!("--", long, "=", text)),
+                _ if takes_values => return Err(format!("option '{key}' expects a value")),
+                _ => return Err(format!("option '{key}' expects true or false")),
+            }
+        }
+    }
+    Ok(config_args)
+}
+
+/// Resolves flags given an explicit value on the command line, keeping `--flag=true` as `--flag` and dropping `--flag=false` while returning the ids of the flags turned off so the config file can't enable them.
+fn resolve_flag_values(rippy_cmd: &Command, raw_args: Vec<String>) -> (Vec<String>, Vec<String>) {
+    let mut flags_off = Vec::new();
+    let mut is_escaped = false;
+    let mut resolved = Vec::with_capacity(raw_args.len());
+    for arg in raw_args {
+        is_escaped |= arg == "--";
+        let flag = arg.strip_prefix("--").and_then(|arg| arg.split_once('=')).filter(|_| !is_escaped).and_then(|(long, value)| {
+            rippy_cmd.get_arguments()
+                .filter(|option| matches!(option.get_action(), ArgAction::SetTrue))
+                .find(|option| option.get_long() == Some(long) || option.get_all_aliases().is_some_and(|aliases| aliases.contains(&long)))
+                .zip(value.parse::<bool>().ok())
+        });
+        match flag {
+            Some((option, true)) => resolved.push(concat_str!("--", option.get_long().unwrap_or_default())),
+            Some((option, false)) => flags_off.push(option.get_id().to_string()),
+            None => resolved.push(arg),
+        }
+    }
+    (resolved, flags_off)
+}
+
+/// Parses a custom guide provided to one of the `--guide-*` options, rejecting empty values that would misalign the tree.
+fn parse_guide(value: &str) -> Result<String, String> {
+    if value.is_empty() {
+        return Err("guide characters cannot be empty".to_string());
+    }
+    Ok(value.to_string())
+}
+
+/// Parses the spec provided to `--number-format` as either a known locale or the thousands separator followed by the decimal separator.
+fn parse_number_format(value: &str) -> Result<NumberFormat, String> {
+    match value.to_ascii_lowercase().as_str() {
+        "en" | "us" | "uk" => Ok(US_NUMBER_FORMAT),
+        "de" | "eu" | "es" | "it" | "nl" | "pt" => Ok(NumberFormat { thousands: '.', decimal: ',' }),
+        "fr" => Ok(NumberFormat { thousands: ' ', decimal: ',' }),
+        "ch" => Ok(NumberFormat { thousands: '\'', decimal: '.' }),
+        _ => match value.chars().collect::<Vec<char>>()[..] {
+            [thousands, decimal] if thousands != decimal && !decimal.is_ascii_digit() && !thousands.is_ascii_digit() => Ok(NumberFormat { thousands, decimal }),
+            _ => Err("expected a locale such as en, de, fr or ch, or two distinct separator characters such as \".,\"".to_string()),
+        },
+    }
+}
+
+/// Parses the label provided to `--encoding` as any label known to the WHATWG Encoding Standard, also accepting UTF-16 labels without the dash such as `utf16le`.
+fn parse_encoding(value: &str) -> Result<&'static Encoding, String> {
+    let label = value.trim().to_ascii_lowercase();
+    Encoding::for_label(label.as_bytes())
+        .or_else(|| label.strip_prefix("utf16").and_then(|endianness| Encoding::for_label(concat_str!("utf-16", endianness).as_bytes())))
+        .ok_or_else(|| "expected an encoding label such as latin1, windows-1252, shift_jis or utf16le".to_string())
+}
+
+/// Parses the form of the paths added to JSON exports as `relative`, `absolute` or `base=<DIR>`, resolving the base to an absolute path that must be an existing directory.
+fn parse_json_paths(value: &str) -> Result<JsonPaths, String> {
+    let value = value.trim();
+    match value.to_ascii_lowercase().as_str() {
+        "relative" => Ok(JsonPaths::Relative),
+        "absolute" => Ok(JsonPaths::Absolute),
+        _ => {
+            let base = value.split_once('=').filter(|(key, _)| key.trim().eq_ignore_ascii_case("base")).map(|(_, base)| base.trim())
+                .ok_or_else(|| "expected 'relative', 'absolute' or 'base=<DIR>'".to_string())?;
+            if !std::path::Path::new(base).is_dir() {
+                return Err(format!("the base '{base}' does not exist or is not a valid directory"));
+            }
+            Ok(JsonPaths::Base(normalize_path_lexically(&std::path::absolute(base).unwrap_or_else(|_| PathBuf::from(base)))))
+        },
+    }
+}
+
+/// Parses a date given as `YYYY-MM-DD`, `YYYY-MM-DD HH:MM:SS` or `YYYY-MM-DDTHH:MM:SS` in UTC into seconds since unix epoch.
+fn parse_date_bound(value: &str) -> Result<f64, String> {
+    let value = value.trim();
+    let datetime = chrono::NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S")
+        .or_else(|_| chrono::NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S"))
+        .or_else(|_| chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d").map(|date| date.and_time(chrono::NaiveTime::MIN)))
+        .map_err(|_| "expected a date as YYYY-MM-DD or YYYY-MM-DD HH:MM:SS".to_string())?;
+    Ok(datetime.and_utc().timestamp() as f64)
+}
+
+/// Parses an age given as a number of days or with a unit of `s`, `m`, `h`, `d` or `w` like `90d` into seconds.
+fn parse_age(value: &str) -> Result<f64, String> {
+    let value = value.trim();
+    let (amount, unit_secs) = match value.char_indices().last() {
+        Some((i, 's')) => (&value[..i], 1.0),
+        Some((i, 'm')) => (&value[..i], 60.0),
+        Some((i, 'h')) => (&value[..i], 3_600.0),
+        Some((i, 'd')) => (&value[..i], 86_400.0),
+        Some((i, 'w')) => (&value[..i], 604_800.0),
+        _ => (value, 86_400.0),
+    };
+    match amount.trim().parse::<f64>() {
+        Ok(amount) if amount.is_finite() && amount >= 0.0 => Ok(amount * unit_secs),
+        _ => Err("expected an age as a number of days or with a unit like 90d, 12h, 30m or 2w".to_string()),
+    }
+}
+
+/// Parses a size given in bytes or with a unit of `K`, `M`, `G` or `T` like `10M` into bytes, with units in powers of 1000 like displayed sizes and an optional trailing `B`.
+fn parse_size_budget(value: &str) -> Result<u64, String> {
+    let value = value.trim().to_ascii_uppercase();
+    let value = value.strip_suffix('B').unwrap_or(&value);
+    let (amount, unit) = match value.char_indices().last() {
+        Some((i, 'K')) => (&value[..i], 1e3),
+        Some((i, 'M')) => (&value[..i], 1e6),
+        Some((i, 'G')) => (&value[..i], 1e9),
+        Some((i, 'T')) => (&value[..i], 1e12),
+        _ => (value, 1.0),
+    };
+    match amount.trim().parse::<f64>() {
+        Ok(amount) if amount.is_finite() && amount >= 0.0 => Ok((amount * unit) as u64),
+        _ => Err("expected a size in bytes or with a unit like 500K, 10M or 2G".to_string()),
+    }
+}
+
+/// Reads the patterns from the file provided to `--pattern-file`, `--include-file` or `--ignore-file-list`, one per line along with its line number, skipping empty lines and `#` comments.
+fn read_pattern_file(path: &str) -> std::io::Result<Vec<(usize, String)>> {
+    let contents = std::fs::read_to_string(path)?;
+    Ok(contents.lines()
+        .map(|line| line.trim_end_matches('\r'))
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+        .map(|(i, line)| (i + 1, line.to_string()))
+        .collect())
+}
+
+/// Characters marking a root argument as a glob to expand.
+const GLOB_CHARS: [char; 4] = ['*', '?', '[', '{'];
+
+/// Checks whether a root argument should be expanded as a glob, holding glob characters while not existing as written.
+fn is_glob_root(directory: &str) -> bool {
+    directory.contains(GLOB_CHARS) && !Path::new(directory).exists()
+}
+
+/// Checks whether a positional argument is written as a path, holding a separator or being `.` or `..`, rather than something that could be a pattern.
+fn is_path_like(arg: &str) -> bool {
+    arg.contains(['/', '\\']) || arg == "." || arg == ".."
+}
+
+/// Expands a glob given as the root into the literal directory it starts in and every directory below that it matches, matching one path component at a time like a shell so `*` never crosses a separator or matches hidden names unless the component starts with a dot.
+fn expand_glob_root(glob: &str) -> Result<(PathBuf, Vec<PathBuf>), String> {
+    let glob = normalize_path(Path::new(glob));
+    let components: Vec<&str> = glob.split('/').collect();
+    let literal_len = components.iter().take_while(|component| !component.contains(GLOB_CHARS)).count();
+    let base = match components[..literal_len].join("/") {
+        base if base.is_empty() && glob.starts_with('/') => PathBuf::from("/"),
+        base if base.is_empty() => PathBuf::from("."),
+        base => PathBuf::from(base),
+    };
+    let mut roots = vec![base.clone()];
+    for component in components[literal_len..].iter().filter(|component| !component.is_empty()) {
+        let matcher = GlobBuilder::new(component).literal_separator(true).build().map_err(|e| e.kind().to_string())?.compile_matcher();
+        roots = roots.iter()
+            .filter_map(|root| std::fs::read_dir(root).ok())
+            .flat_map(|entries| entries.filter_map(Result::ok))
+            .filter(|entry| {
+                let name = entry.file_name();
+                let name = name.to_string_lossy();
+                (component.starts_with('.') || !name.starts_with('.')) && matcher.is_match(name.as_ref()) && entry.path().is_dir()
+            })
+            .map(|entry| entry.path())
+            .collect();
+    }
+    roots.sort();
+    Ok((base, roots))
+}
+
+/// Reads paths separated by NUL bytes, skipping any empty entries such as the one after a trailing separator.
+fn read_null_paths(mut reader: impl std::io::Read) -> std::io::Result<Vec<PathBuf>> {
+    let mut listing = Vec::new();
+    reader.read_to_end(&mut listing)?;
+    Ok(listing.split(|&byte| byte == b'\0')
+        .filter(|path| !path.is_empty())
+        .map(|path| PathBuf::from(String::from_utf8_lossy(path).as_ref()))
+        .collect())
+}
+
+/// Lists the files git tracks within the directory, relative to it, along with each of their ancestor directories below it. Errors if git can't be run or the directory is not inside a repository.
+fn read_tracked_paths(directory: &Path) -> Result<Vec<String>, String> {
+    let output = std::process::Command::new("git")
+        .arg("-C").arg(directory)
+        .args(["ls-files", "-z"])
+        .output()
+        .map_err(|e| concat_str!("unable to run git: ", e.to_string()))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stderr.lines().next().unwrap_or("not a git repository").trim_start_matches("fatal: ");
+        return Err(reason.to_string());
+    }
+    let mut tracked: Vec<String> = Vec::new();
+    let mut dirs: HashSet<&str> = HashSet::new();
+    let listing = String::from_utf8_lossy(&output.stdout);
+    for file in listing.split('\0').filter(|file| !file.is_empty()) {
+        let mut file_path = file;
+        while let Some((parent, _)) = file_path.rsplit_once('/') {
+            if !dirs.insert(parent) {
+                break;
+            }
+            file_path = parent;
+        }
+        tracked.push(file.to_string());
+    }
+    tracked.extend(dirs.into_iter().map(str::to_string));
+    Ok(tracked)
+}
+
+/// Strips trailing slashes from the directory so `dir/` and `dir` produce identical output, leaving a filesystem root such as `/` or `C:/` as is.
+fn trim_trailing_separators(path: String) -> String {
+    let trimmed_length = path.trim_end_matches('/').len();
+    if trimmed_length == 0 || path[..trimmed_length].ends_with(':') {
+        path[..(trimmed_length + 1).min(path.len())].to_string()
+    } else {
+        path[..trimmed_length].to_string()
+    }
+}
+
+/// Parses and converts the Vec<String> of arguments collected from "ignore" or "pattern" into regex sets based on wildcards present, each anchored to the whole name with `*` matching any characters including a leading dot, so `.*` matches every dotfile
 fn parse_and_convert_patterns(patterns: Vec<&String>, case_insensitive: bool) -> RegexSet {
      let converted_patterns: Vec<String> = patterns.into_iter().filter(|s| !s.is_empty()).map(|s| {
          let pattern = if s.contains('*') {
-              concat_str!("^", regex::escape(&s).replace(r"\*", ".*"), "$")
+              concat_str!("^", regex::escape(s).replace(r"\*", ".*"), "$")
            } else {
-              concat_str!("^", regex::escape(&s), "$")
+              concat_str!("^", regex::escape(s), "$")
          };
          if case_insensitive {
              concat_str!("(?i)", pattern)
@@ -463,42 +2144,91 @@ fn parse_and_convert_patterns(patterns: Vec<&String>, case_insensitive: bool) ->
              pattern
          }
      }).collect();
-     let re_set = RegexSet::new(converted_patterns).expect("Invalid regex patterns");
-     re_set
+     RegexSet::new(converted_patterns).expect("Invalid regex patterns")
 }
 
 /// Summarizes and formats result returned by args after `tree` has been constructed and rendered
 pub fn format_result_summary(args: &'static RippyArgs, num_matched: usize, num_searched: usize, counts: &TreeCounts) -> String {
-     let fmt_result = if num_matched > 0 {
-          if args.is_search {
-              let match_suffix = if num_matched != 1 {"matches"} else {"match"};
-              let match_text = concat_str!(num_matched.to_string(), " ", match_suffix);
-              let match_fmt = ansi_color!(&args.colors.window, bold=!args.is_grayscale, &match_text);
-              let search_text = concat_str!(num_searched.to_string(), " searched");
-              let search_fmt = ansi_color!(&args.colors.search, bold=false, &search_text);
-              concat_str!(match_fmt, ", ", search_fmt)
-          } else {
-              let dirs_suffix = if counts.dir_count != 1 {"directories"} else {"directory"};
-              let dirs_text = concat_str!(counts.dir_count.to_string(), " ", dirs_suffix);
-              let dirs_fmt = ansi_color!(&args.colors.dir, bold=!args.is_grayscale, &dirs_text);
-              let files_suffix = if counts.file_count != 1 {"files"} else {"file"};
-              let files_text = concat_str!(counts.file_count.to_string(), " ", files_suffix);
-              let files_fmt = ansi_color!(&args.colors.file, bold=!args.is_grayscale, &files_text);
-              concat_str!(dirs_fmt, ", ", files_fmt)
-          }
-      } else {
-          if args.is_search {
-              let matches_fmt = ansi_color!(&args.colors.zero, bold=!args.is_grayscale, "0 matches");
-              let searched_fmt = ansi_color!(&args.colors.search, bold=false, concat_str!(num_searched.to_string(), " searched"));
-              concat_str!({if args.is_just_counts {""} else {"\n"}}, matches_fmt, ", ", searched_fmt)
-          } else {
-              let dirs_text = concat_str!(counts.dir_count.to_string(), " directories");
-              let dirs_fmt = ansi_color!(&args.colors.dir, bold=!args.is_grayscale, &dirs_text);
-              let files_text = concat_str!(counts.file_count.to_string(), " files");
-              let files_fmt = ansi_color!(&args.colors.file, bold=!args.is_grayscale, &files_text);
-              concat_str!({if args.is_just_counts {""} else {"\n"}}, &dirs_fmt, ", ", &files_fmt)
-          }
-      };
-      // Return result after summary counts formatted
-      fmt_result
+     let fmt_result = if args.is_search {
+          let match_color = if num_matched > 0 { args.colors.window.as_deref() } else { args.colors.zero };
+          let match_fmt = ansi_color!(match_color, bold=!args.is_no_bold, &format_count(num_matched, "match", "matches"));
+          let pattern_text = args.pattern_text.as_deref().unwrap_or_default();
+          let pattern_fmt = if args.is_quote { concat_str!(" for \"", pattern_text, "\"") } else { concat_str!(" for ", pattern_text) };
+          let search_text = concat_str!(num_searched.to_string(), " searched");
+          let search_fmt = ansi_color!(&args.colors.search, bold=false, &search_text);
+          concat_str!(match_fmt, pattern_fmt, ", ", search_fmt)
+     } else {
+          let dirs_fmt = ansi_color!(&args.colors.dir, bold=!args.is_no_bold, &format_count(counts.dir_count, "directory", "directories"));
+          let files_fmt = ansi_color!(&args.colors.file, bold=!args.is_no_bold, &format_count(counts.file_count, "file", "files"));
+          concat_str!(dirs_fmt, ", ", files_fmt)
+     };
+     // Return result after summary counts formatted
+     fmt_result
+}
+
+/// Formats the count followed by the singular or plural noun depending on the count.
+fn format_count(count: usize, singular: &str, plural: &str) -> String {
+     concat_str!(count.to_string(), " ", if count != 1 { plural } else { singular })
+}
+
+/// Formats the number of distinct extensions to append to the summary as ` across 7 extensions`, returning `None` if not requested.
+pub fn format_ext_count(args: &RippyArgs, ext_count: usize) -> Option<String> {
+    if !args.is_ext_count {
+        return None;
+    }
+    Some(concat_str!(" across ", format_count(ext_count, "extension", "extensions")))
+}
+
+/// Formats the number of hidden and ignored entries skipped while crawling to append to the summary as ` (12 hidden, 30 ignored)`, returning `None` if not requested.
+pub fn format_skipped_counts(args: &RippyArgs, hidden: usize, ignored: usize) -> Option<String> {
+    if !args.is_verbose {
+        return None;
+    }
+    Some(concat_str!(" (", hidden.to_string(), " hidden, ", ignored.to_string(), " ignored)"))
+}
+
+/// Formats the number of entries at each depth as `L1: 3, L2: 12`, returning `None` if not requested.
+pub fn format_depth_summary(args: &RippyArgs, depths: &std::collections::BTreeMap<usize, usize>) -> Option<String> {
+    if !args.is_depth_summary {
+        return None;
+    }
+    let levels = depths.iter()
+        .map(|(depth, count)| concat_str!("L", depth.to_string(), ": ", count.to_string()))
+        .collect::<Vec<String>>()
+        .join(", ");
+    let levels = if levels.is_empty() { "no entries below root".to_string() } else { levels };
+    Some(ansi_color!(args.colors.muted, levels))
+}
+
+/// Formats the time spent in each phase of a run as `crawl: 0.012s, build: 0.001s, render: 0.003s`, returning `None` if not requested.
+pub fn format_phase_times(args: &RippyArgs, phases: &[(&str, std::time::Duration)]) -> Option<String> {
+    if !args.is_time_phases {
+        return None;
+    }
+    let phases = phases.iter()
+        .map(|(label, duration)| format!("{}: {:.3}s", label, duration.as_secs_f32()))
+        .collect::<Vec<String>>()
+        .join(", ");
+    Some(ansi_color!(args.colors.muted, phases))
+}
+
+/// Formats the OSC 9 desktop notification followed by a terminal bell to emit once finished, returning `None` if not requested. Terminals without OSC 9 support ignore the sequence and still ring the bell.
+pub fn format_notification(args: &RippyArgs) -> Option<String> {
+    if !args.is_notify {
+        return None;
+    }
+    let directory = normalize_path(&args.directory);
+    Some(concat_str!("\x1b]9;rippy finished in ", directory, "\x1b\\\x07"))
+}
+
+/// Formats a legend describing the colors used for each type of entry, returning `None` if not requested or if output is grayscale.
+pub fn format_legend(args: &RippyArgs) -> Option<String> {
+    if !args.is_legend || args.is_grayscale {
+        return None;
+    }
+    let legend = StyleKind::ALL.iter()
+        .map(|kind| ansi_color!(kind.color(args), bold=kind.is_bold(args), kind.label()))
+        .collect::<Vec<String>>()
+        .join("  ");
+    Some(concat_str!(ansi_color!(args.colors.muted, "legend: "), legend))
 }
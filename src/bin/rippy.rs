@@ -1,69 +1,294 @@
 // #![allow(unused)]
 #![allow(non_upper_case_globals)]
+use std::io::{IsTerminal, Write};
 use std::sync::LazyLock;
 
 use rippy::args;
 use rippy::tcolor;
 use rippy::tree;
 use rippy::crawl;
+use rippy::select;
 use rippy::ansi_color;
+use rippy::error::{ErrorKind, RippyError};
 
 fn main() -> std::io::Result<()> {
     // Initialize global args from environment
     static args: LazyLock<args::RippyArgs> = LazyLock::new(|| args::parse_args(None));
 
+    // Print the resolved settings without crawling if requested
+    if args.is_show_config {
+        println!("{}", *args);
+        return Ok(());
+    }
+
+    // Print the total alone for scripts, counting every match rather than the files containing them if counts were requested
+    if args.is_count_matches_total {
+        let total = if args.is_search && args.is_just_counts {
+            crawl::search(&args).map(|matches| matches.len())
+        } else {
+            crawl::crawl_directory(&args).map(|result| result.paths.iter().filter(|leaf| !leaf.is_dir).count())
+        };
+        match total {
+            Ok(total) => println!("{total}"),
+            Err(e) => {
+                let directory = args.directory.to_string_lossy().to_string();
+                RippyError::new(ErrorKind::Read, format!("Unable to read directory '{directory}': {e}"), Some(directory)).report(args.is_json_errors, !args.is_no_bold);
+                std::process::exit(1);
+            }
+        }
+        return Ok(());
+    }
+
     // Starts timer if show elapsed present
     let start = if args.show_elapsed { Some(std::time::Instant::now()) } else { None };
 
+    let crawl_start = std::time::Instant::now();
     match crawl::crawl_directory(&args) {
         Ok(result) => {
+            let crawl_elapsed = crawl_start.elapsed();
+            // Build and render durations stay zero if the tree is skipped for counts alone
+            let mut build_elapsed = std::time::Duration::ZERO;
+            let mut render_elapsed = std::time::Duration::ZERO;
             let num_matched = result.paths.len();
             let num_searched = result.paths_searched;
-            let mut tree = tree::build_tree_from_paths(result.paths, &args);
-
-            // Only calculate dir sizes if needed based on is_dir_detail argument present
-            if args.show_size && args.is_dir_detail {
-                tree.calculate_sizes();
-            }
-
-            // Calculate format width for window snippets if arg present
-            if args.is_search && args.is_window {
-                tree.calculate_fmt_width();
-            }
+            let is_truncated = result.is_truncated;
+            let num_unreadable = result.paths_unreadable;
+            let (num_hidden, num_ignored) = (result.paths_hidden, result.paths_ignored);
+            let mounts = result.mounts;
 
-            // Output tree as JSON to file provided
-            if !args.output.is_empty() {
-                match tree.write_to_json_file(&args) {
-                    Ok(_) => {},
-                    Err(e) => eprintln!("{} writing output to file: {}", ansi_color!(tcolor::ERROR_COLOR, bold=true, "Error"), e),
-                }
-            } 
-                        
             // Tracking entry counts
             let mut counts = tree::TreeCounts::new();
-            
-            // Print primary tree with results if not just counts present
-            if args.is_just_counts {
-                tree::count_tree(&tree, &mut counts, true);
+            // Entries at each depth taken from the tree before any display changes if requested
+            let mut depth_counts = None;
+            // Distinct extensions of the files in the tree if requested
+            let mut ext_count = None;
+            // Files and sizes of each extension if requested
+            let mut ext_summary = None;
+            // Rendered results held back until after the summary when it is placed first
+            let mut rendered: Vec<u8> = Vec::new();
+
+            // Counts alone can be taken directly from the crawl results unless the tree is needed for filtering, exporting or selecting
+            if args.is_just_counts && args.fuzzy.is_none() && args.output.is_empty() && !args.is_select && !args.is_depth_summary && !args.is_ext_count && !args.is_summary_by_ext && args.min_children == 0 && !args.is_no_empty_files && !args.is_du && args.top_dirs.is_none() && !args.is_matching_dirs && args.subtree.is_none() && !args.is_prune {
+                tree::count_paths(&result.paths, &args, &mut counts, !args.is_count_root);
             } else {
-                tree::print_tree(&mut tree, &args, &mut counts)?;
+                let build_start = std::time::Instant::now();
+                let mut tree = tree::build_tree_from_paths(result.paths, &args);
+
+                // Focus on the subtree requested, rendering it in place of the root
+                if let Some(subtree) = &args.subtree {
+                    match tree.find_by_path(subtree) {
+                        Some(node) => tree = node.clone(),
+                        None => {
+                            let root = args.directory.to_string_lossy().to_string();
+                            RippyError::new(ErrorKind::InvalidDirectory, format!("The subtree provided, '{subtree}', does not exist within '{root}'."), Some(subtree.to_owned())).report(args.is_json_errors, !args.is_no_bold);
+                            std::process::exit(1);
+                        }
+                    }
+                }
+
+                // Filter down to fuzzy filename matches if query provided
+                if let Some(query) = &args.fuzzy {
+                    tree.retain_fuzzy(query, &args);
+                }
+
+                // Drop zero-byte files before any directory filtering so they don't count as children
+                if args.is_no_empty_files {
+                    tree.retain_non_empty_files();
+                }
+
+                // Hide directories with fewer entries than the minimum, keeping the root regardless
+                if args.min_children > 0 {
+                    tree.retain_min_children(args.min_children);
+                }
+
+                // Remove directories left empty by the filtering above, or without matches if searching
+                if args.is_prune {
+                    if args.is_search {
+                        tree.prune_to_matches();
+                    } else {
+                        tree.prune_empty_dirs();
+                    }
+                }
+
+                // Disk usage and the largest directories are printed alone in place of the tree and summary
+                if args.is_du || args.top_dirs.is_some() {
+                    calculate_sizes(&mut tree, &args, num_matched);
+                    match args.top_dirs {
+                        Some(limit) => println!("{}", tree::format_top_dirs(&tree.largest_dirs(limit), &args)),
+                        None => println!("{}", tree::format_du(&tree, &args)),
+                    }
+                    return Ok(());
+                }
+
+                // Directories holding the files kept are printed alone in place of the tree and summary
+                if args.is_matching_dirs {
+                    tree.matching_dirs().into_iter().for_each(|dir| println!("{}", tree::to_native_separators(dir, &args)));
+                    return Ok(());
+                }
+
+                // Only calculate dir sizes if needed based on is_dir_detail argument present, shallow sizes are already those of the entry itself
+                if args.show_size && args.is_dir_detail && !args.is_shallow_size {
+                    calculate_sizes(&mut tree, &args, num_matched);
+                }
+
+                // Sort once after sizes are known so every output shares the same ordering
+                tree.sort_levels(&args);
+
+                // Calculate format width for window snippets if arg present
+                if args.is_search && (args.is_window || args.is_line_numbers) && !args.is_window_below {
+                    tree.calculate_fmt_width();
+                }
+
+                // Output tree to each file provided using the format of its extension
+                for output in args.output.iter() {
+                    match tree.write_to_file_with_elapsed(output, &args, start.map(|time| time.elapsed())) {
+                        Ok(_) => {},
+                        Err(e) => RippyError::new(ErrorKind::Output, format!("Unable to write output to file '{output}': {e}"), Some(output.to_owned())).report(args.is_json_errors, !args.is_no_bold),
+                    }
+                }
+
+                // Interactive selection replaces rendering, printing only the selected paths for piping
+                if args.is_select {
+                    match select::run_select(&select::collect_rows(&tree, &args)) {
+                        Ok(paths) => paths.iter().for_each(|path| println!("{path}")),
+                        Err(e) => {
+                            RippyError::new(ErrorKind::Select, format!("Unable to select results: {e}"), None).report(args.is_json_errors, !args.is_no_bold);
+                            std::process::exit(1);
+                        }
+                    }
+                    return Ok(());
+                }
+
+                if args.is_depth_summary {
+                    depth_counts = Some(tree.depth_counts());
+                }
+                if args.is_ext_count {
+                    ext_count = Some(tree.distinct_extensions().len());
+                }
+                if args.is_summary_by_ext {
+                    ext_summary = Some(tree.extension_summary());
+                }
+
+                build_elapsed = build_start.elapsed();
+                let render_start = std::time::Instant::now();
+
+                // Mark what changed since the snapshot for display, after the exports so those only hold what is on disk
+                if let Some(snapshot) = args.diff.as_ref() {
+                    tree.diff_against(snapshot, &args);
+                }
+
+                // Collapse single child directory chains for display, counting the merged directories up front
+                if args.is_collapse && !args.is_just_counts {
+                    counts.dir_count += tree.collapse_chains(&args);
+                }
+
+                // Group numbered sibling files for display, counting the files merged away up front
+                if args.is_group_similar && !args.is_just_counts {
+                    counts.file_count += tree.group_similar();
+                }
+
+                // Print primary tree with results if not just counts present
+                if args.is_just_counts {
+                    tree::count_tree(&tree, &mut counts, !args.is_count_root);
+                } else if args.is_summary_top {
+                    tree::write_results_to_buf(&mut tree, &args, &mut counts, &mut rendered)?;
+                } else if args.is_table {
+                    tree::print_table(&mut tree, &args, &mut counts)?;
+                } else if args.is_breadth_first {
+                    tree::print_breadth_first(&mut tree, &args, &mut counts)?;
+                } else {
+                    tree::print_tree(&mut tree, &args, &mut counts)?;
+                }
+                render_elapsed = render_start.elapsed();
             }
 
             // Big things have small beginnings...
             let mut fmt_result = args::format_result_summary(&args, num_matched, num_searched, &counts);
+            if let Some(ext_summary) = ext_count.and_then(|count| args::format_ext_count(&args, count)) {
+                fmt_result.push_str(&ext_summary);
+            }
+            if let Some(skipped_summary) = args::format_skipped_counts(&args, num_hidden, num_ignored) {
+                fmt_result.push_str(&skipped_summary);
+            }
     
             fmt_result = match start {
                 Some(time) => format!("{} ({:.3}s)", fmt_result, time.elapsed().as_secs_f32()),
                 None => fmt_result
             };
     
-            // Print the rendered tree
-            println!("{fmt_result}");
+            // Print the summary unless left out entirely or there is nothing to summarize and it should be left out
+            let is_empty_result = if args.is_search { num_matched == 0 } else { counts.dir_count + counts.file_count <= usize::from(args.is_count_root) };
+            if !(args.is_no_report || (args.is_no_summary_on_empty && is_empty_result)) {
+                println!("{fmt_result}");
+            }
+
+            // Append entries per depth if requested
+            if let Some(depth_summary) = depth_counts.and_then(|depths| args::format_depth_summary(&args, &depths)) {
+                println!("{depth_summary}");
+            }
+
+            // Append the files and size of each extension if requested
+            if let Some(mut extensions) = ext_summary.filter(|extensions| !extensions.is_empty()) {
+                println!("{}", tree::format_ext_summary(&mut extensions, &args));
+            }
+
+            // Append the entries and size on each file system if requested
+            if args.is_mount_summary && !mounts.is_empty() {
+                println!("{}", tree::format_mount_summary(&mounts, &args));
+            }
+
+            // Append the time spent in each phase if requested
+            if let Some(phase_times) = args::format_phase_times(&args, &[("crawl", crawl_elapsed), ("build", build_elapsed), ("render", render_elapsed)]) {
+                println!("{phase_times}");
+            }
+
+            // Print the results held back if the summary goes first
+            if args.is_summary_top {
+                std::io::stdout().write_all(&rendered)?;
+            }
+
+            // Note partial results if the entry or size budget stopped the crawl early
+            if is_truncated {
+                let budget = match (args.max_entries, args.max_total_size) {
+                    (usize::MAX, Some(max_total_size)) => format!("scanning {max_total_size} bytes of files"),
+                    (max_entries, None) => format!("visiting {max_entries} entries"),
+                    (max_entries, Some(max_total_size)) => format!("visiting {max_entries} entries or scanning {max_total_size} bytes of files"),
+                };
+                eprintln!("{} crawl stopped after {}, results are partial", ansi_color!(tcolor::WARN_COLOR, bold=!args.is_no_bold, "Warning:"), budget);
+            }
+
+            // Note files that could not be read so an incomplete search isn't mistaken for no matches
+            if num_unreadable > 0 {
+                let hint = if args.is_show_unreadable { "" } else { " (use --show-unreadable to list them)" };
+                eprintln!("{} {} {} could not be read and {} not searched{}", ansi_color!(tcolor::WARN_COLOR, bold=!args.is_no_bold, "Warning:"), num_unreadable, if num_unreadable == 1 { "file" } else { "files" }, if num_unreadable == 1 { "was" } else { "were" }, hint);
+            }
+
+            // Append color legend if requested and output is styled
+            if let Some(legend) = args::format_legend(&args) {
+                println!("{legend}");
+            }
+
+            // Alert once finished if requested, skipped if output isn't a terminal where the escape sequence would end up in files or pipes
+            if let Some(notification) = args::format_notification(&args).filter(|_| std::io::stdout().is_terminal()) {
+                print!("{notification}");
+                std::io::stdout().flush()?;
+            }
     
         },
         Err(e) => {
-            eprintln!("{} reading directory: {}", ansi_color!(tcolor::ERROR_COLOR, bold=true, "Error"), e)
+            let directory = args.directory.to_string_lossy().to_string();
+            RippyError::new(ErrorKind::Read, format!("Unable to read directory '{directory}': {e}"), Some(directory)).report(args.is_json_errors, !args.is_no_bold);
         }
     }
     Ok(())
 }
+
+/// Calculates the size of directories from their contents, only up to the maximum depth if directories there were measured directly, showing the progress against the number of entries crawled if requested.
+fn calculate_sizes(tree: &mut tree::Tree, args: &args::RippyArgs, total_entries: usize) {
+    let max_depth = if args.is_deep_size { args.max_depth } else { usize::MAX };
+    if args.is_progress && std::io::stderr().is_terminal() {
+        tree.calculate_sizes_with_progress(max_depth, &mut tree::SizeProgress::new(total_entries));
+    } else {
+        tree.calculate_sizes_to_depth(max_depth, 0);
+    }
+}
@@ -0,0 +1,138 @@
+use std::path::{Path, PathBuf};
+
+/// Name of the config file discovered in the current directory when `--config` is not provided.
+pub const CONFIG_FILE_NAME: &str = "rippy.toml";
+
+/// Single value assigned to a key in the config file, with arrays flattened into one value per element.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigValue {
+    Bool(bool),
+    Text(String),
+}
+
+/// Finds the config file to load, preferring the path given to `--config` in the raw arguments over a `rippy.toml` in the current directory.
+pub fn find_config_path(raw_args: &[String]) -> Option<PathBuf> {
+    let mut raw_args = raw_args.iter().skip(1);
+    while let Some(arg) = raw_args.next() {
+        if arg == "--" {
+            break;
+        } else if arg == "--config" {
+            return raw_args.next().map(PathBuf::from);
+        } else if let Some(path) = arg.strip_prefix("--config=") {
+            return Some(PathBuf::from(path));
+        }
+    }
+    Some(PathBuf::from(CONFIG_FILE_NAME)).filter(|path| path.is_file())
+}
+
+/// Reads and parses the config file at the path provided.
+pub fn read_config(path: &Path) -> Result<Vec<(String, Vec<ConfigValue>)>, String> {
+    let contents = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    parse_config(&contents)
+}
+
+/// Parses the subset of TOML used for config files, top level `key = value` pairs where the value is a string, number, boolean or single line array of those. Keys are normalized to use dashes so `max_depth` and `max-depth` are equivalent.
+pub fn parse_config(contents: &str) -> Result<Vec<(String, Vec<ConfigValue>)>, String> {
+    let mut entries = Vec::new();
+    for (i, line) in contents.lines().enumerate() {
+        let line = strip_comment(line).trim();
+        if line.is_empty() {
+            continue;
+        }
+        let line_error = |message: &str| format!("line {}: {}", i + 1, message);
+        if line.starts_with('[') {
+            return Err(line_error("tables are not supported, keys must be at the top level"));
+        }
+        let (key, value) = line.split_once('=').ok_or_else(|| line_error("expected 'key = value'"))?;
+        let key = key.trim().trim_matches('"').replace('_', "-");
+        if key.is_empty() {
+            return Err(line_error("missing key"));
+        }
+        let value = value.trim();
+        let values = match value.strip_prefix('[').and_then(|value| value.strip_suffix(']')) {
+            Some(items) => split_array(items).into_iter().map(parse_scalar).collect::<Result<Vec<ConfigValue>, String>>(),
+            None => parse_scalar(value).map(|value| vec![value]),
+        }.map_err(|e| line_error(&e))?;
+        entries.push((key, values));
+    }
+    Ok(entries)
+}
+
+/// Removes a trailing `#` comment from the line while leaving any `#` inside of strings.
+fn strip_comment(line: &str) -> &str {
+    let mut quote: Option<char> = None;
+    let mut is_escaped = false;
+    for (i, c) in line.char_indices() {
+        match quote {
+            Some('"') if is_escaped => is_escaped = false,
+            Some('"') if c == '\\' => is_escaped = true,
+            Some(q) if c == q => quote = None,
+            Some(_) => {},
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if c == '#' => return &line[..i],
+            None => {},
+        }
+    }
+    line
+}
+
+/// Splits the items of an array on the commas outside of strings, ignoring a trailing comma.
+fn split_array(items: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut quote: Option<char> = None;
+    let mut is_escaped = false;
+    let mut start = 0;
+    for (i, c) in items.char_indices() {
+        match quote {
+            Some('"') if is_escaped => is_escaped = false,
+            Some('"') if c == '\\' => is_escaped = true,
+            Some(q) if c == q => quote = None,
+            Some(_) => {},
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if c == ',' => {
+                parts.push(items[start..i].trim());
+                start = i + 1;
+            },
+            None => {},
+        }
+    }
+    parts.push(items[start..].trim());
+    parts.into_iter().filter(|part| !part.is_empty()).collect()
+}
+
+/// Parses a single string, number or boolean value.
+fn parse_scalar(value: &str) -> Result<ConfigValue, String> {
+    if let Some(text) = value.strip_prefix('\'').and_then(|value| value.strip_suffix('\'')) {
+        return Ok(ConfigValue::Text(text.to_string()));
+    }
+    if let Some(text) = value.strip_prefix('"').and_then(|value| value.strip_suffix('"')) {
+        return unescape(text).map(ConfigValue::Text);
+    }
+    match value {
+        "true" => Ok(ConfigValue::Bool(true)),
+        "false" => Ok(ConfigValue::Bool(false)),
+        _ if !value.is_empty() && value.replace('_', "").parse::<f64>().is_ok() => Ok(ConfigValue::Text(value.replace('_', ""))),
+        _ => Err(format!("unsupported value '{value}'")),
+    }
+}
+
+/// Resolves the escape sequences allowed in basic TOML strings.
+fn unescape(text: &str) -> Result<String, String> {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => unescaped.push('\n'),
+            Some('t') => unescaped.push('\t'),
+            Some('r') => unescaped.push('\r'),
+            Some('"') => unescaped.push('"'),
+            Some('\\') => unescaped.push('\\'),
+            other => return Err(format!("unsupported escape '\\{}'", other.map(String::from).unwrap_or_default())),
+        }
+    }
+    Ok(unescaped)
+}
@@ -1,25 +1,44 @@
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+
 use is_executable::IsExecutable;
 use jwalk::WalkDirGeneric;
+use serde::{Deserialize, Serialize};
+use rayon::prelude::*;
 use crate::args::RippyArgs;
+use crate::tree::{EntryType, FlatRecord, StyleKind, convert_path_relative_to, normalize_path, normalize_path_lexically, to_native_separators, truncate_name};
 use crate::{ansi_color, concat_str};
 
 #[derive(Clone, Debug, Default)]
 /// Custom implementation to streamline usage of `ignore::gitignore::Gitignore` down to only the most basic functions required for `rippy`.
+/// Matchers accumulate from the root down so nested `.gitignore` files extend rather than replace those found in parent directories.
 pub struct Ignorer {
-    pub matcher: Option<ignore::gitignore::Gitignore>
+    pub matchers: Vec<ignore::gitignore::Gitignore>
 }
 impl Ignorer {
     /// Creates a new `Ignorer` from a filepath to what is assumed to be a `.gitignore` like format containing globs to match or whitelist.
     pub fn new<P: AsRef<std::path::Path>>(gitignore_path: P) -> Self {
-        Ignorer { matcher: Some(ignore::gitignore::Gitignore::new(gitignore_path).0) }
+        Ignorer { matchers: vec![ignore::gitignore::Gitignore::new(gitignore_path).0] }
+    }
+    /// Adds the `.gitignore` at the filepath provided, taking precedence over any matchers added from parent directories.
+    pub fn push<P: AsRef<std::path::Path>>(&mut self, gitignore_path: P) {
+        self.matchers.push(ignore::gitignore::Gitignore::new(gitignore_path).0);
     }
-    /// Check if path should be ignored based on current `matcher` presence, value and whether path represents directory.
+    /// Check if path should be ignored based on the matchers present, with the deepest `.gitignore` that matches deciding so that negated `!pattern` whitelists re-include paths.
     pub fn is_ignore<P: AsRef<std::path::Path>>(&self, path: P, is_dir: bool) -> bool {
-        self.matcher.as_ref().map_or_else(|| false, |m| m.matched(path, is_dir).is_ignore())
+        let path = path.as_ref();
+        for matcher in self.matchers.iter().rev().filter(|m| path.starts_with(m.path())) {
+            match matcher.matched_path_or_any_parents(path, is_dir) {
+                ignore::Match::Ignore(_) => return true,
+                ignore::Match::Whitelist(_) => return false,
+                ignore::Match::None => continue,
+            }
+        }
+        false
     }
-    /// Check if `matcher` has been initialized with a `Gitignore`.
+    /// Check if any `matchers` have been initialized with a `Gitignore`.
     pub fn has_matcher(&self) -> bool {
-        self.matcher.as_ref().is_some()
+        !self.matchers.is_empty()
     }
 }
 impl<P: AsRef<std::path::Path>> From<P> for Ignorer {
@@ -36,13 +55,34 @@ pub struct TreeLeaf {
     pub last_modified: Option<f64>,
     pub size: Option<u64>,
     pub window: Option<String>,
+    pub window_match: Option<WindowMatch>, // Unstyled window with the position of the match so exports can highlight it in their own format
     pub display: String, // New display field to preformat the needed string earlier
     pub is_sym: bool, // New for coloring sym links correctly when displayed
 }
 impl TreeLeaf {
     /// Create new `TreeLeaf`
+    #[allow(clippy::too_many_arguments)]
     pub fn new(name: impl Into<String>, relative_path: impl Into<String>, is_dir: bool, last_modified: Option<f64>, size: Option<u64>, window: Option<String>, display: impl Into<String>, is_sym: bool ) -> TreeLeaf {
-        TreeLeaf { name: name.into(), relative_path: relative_path.into(), is_dir, last_modified, size, window, display: display.into(), is_sym }
+        TreeLeaf { name: name.into(), relative_path: relative_path.into(), is_dir, last_modified, size, window, window_match: None, display: display.into(), is_sym }
+    }
+    /// Flattens the leaf into the same record of text fields produced for entries of a built tree.
+    pub fn to_record(&self, settings: &RippyArgs) -> FlatRecord {
+        let entry_type = if self.is_dir { EntryType::Directory } else { EntryType::File };
+        FlatRecord::new(self.relative_path.clone(), entry_type, self.last_modified, self.size, &self.window, settings)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+/// Window displayed for a match without any styling, along with the byte range of the match within it.
+pub struct WindowMatch {
+    pub text: String,
+    pub start: usize,
+    pub end: usize,
+}
+impl WindowMatch {
+    /// Splits the text into the parts before, within and after the match.
+    pub fn split(&self) -> (&str, &str, &str) {
+        (&self.text[..self.start], &self.text[self.start..self.end], &self.text[self.end..])
     }
 }
 // Implement Display for EntryType to convert to string
@@ -58,170 +98,668 @@ impl std::fmt::Display for TreeLeaf {
 pub struct CrawlResults {
     pub paths: Vec<TreeLeaf>,
     pub paths_searched: usize,
+    pub is_truncated: bool, // Crawl stopped early after reaching `args.max_entries` or `args.max_total_size`
+    pub paths_unreadable: usize, // Files that could not be read while searching, distinct from those without a match
+    pub paths_hidden: usize, // Hidden entries skipped without descending into them
+    pub paths_ignored: usize, // Entries skipped by ignore files or patterns without descending into them
+    pub mounts: Vec<MountUsage>, // Entries and sizes found on each file system, only gathered for `args.is_mount_summary`
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Number of entries and total size of the files found on a single file system, named by where it is mounted if known.
+pub struct MountUsage {
+    pub device: u64,
+    pub mount_point: Option<String>,
+    pub entries: usize,
+    pub size: u64,
+}
+
+/// Details of a single pattern match found while searching file contents, exposed for library consumers building their own output.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchMatch {
+    /// Path of the file containing the match using forward slashes.
+    pub path: String,
+    /// One-based line number the match starts on.
+    pub line_number: usize,
+    /// Full text of the line the match starts on without line terminators.
+    pub line: String,
+    /// Byte range of the match within the file contents.
+    pub byte_range: std::ops::Range<usize>,
+    /// Unstyled snippet surrounding the match bounded by the snippet radius and the line.
+    pub window: String,
+}
+
+/// Maximum bytes scanned on either side of a match for line boundaries when windowing by words, bounding the cost of very long lines such as minified files.
+const WORD_SCAN_LIMIT: usize = 4096;
+
+/// Character radius of the window around matches on lines longer than `--max-line-length`, whatever the radius requested.
+const LONG_LINE_RADIUS: usize = 16;
+
+/// Byte offsets bounding a snippet window around a match, clamped to the line containing the match.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct SnippetBounds {
+    start: usize,
+    end: usize,
+}
+
+/// Returns true if the byte is a carriage return or line feed, neither of which can occur within a multibyte UTF-8 sequence.
+fn is_line_break(byte: u8) -> bool {
+    byte == b'\n' || byte == b'\r'
+}
+
+/// Returns the closest char boundary at or before the index.
+fn floor_char_boundary(contents: &str, mut index: usize) -> usize {
+    while !contents.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+/// Returns the closest char boundary at or after the index.
+fn ceil_char_boundary(contents: &str, mut index: usize) -> usize {
+    while !contents.is_char_boundary(index) {
+        index += 1;
+    }
+    index
+}
+
+/// Finds the start of the line containing the index by scanning back at most `limit` bytes, returning the furthest point scanned if no line break was found within it.
+fn bounded_line_start(contents: &str, index: usize, limit: usize) -> usize {
+    let floor = floor_char_boundary(contents, index.saturating_sub(limit));
+    contents.as_bytes()[floor..index].iter().rposition(|&b| is_line_break(b)).map_or(floor, |pos| floor + pos + 1)
+}
+
+/// Finds the end of the line containing the index by scanning forward at most `limit` bytes, returning the furthest point scanned if no line break was found within it.
+fn bounded_line_end(contents: &str, index: usize, limit: usize) -> usize {
+    let ceil = ceil_char_boundary(contents, index.saturating_add(limit).min(contents.len()));
+    contents.as_bytes()[index..ceil].iter().position(|&b| is_line_break(b)).map_or(ceil, |pos| index + pos)
+}
+
+/// Computes the snippet window bounds for the match using the word radius if provided or the character radius otherwise, capped to a small radius on lines longer than `--max-line-length`.
+fn snippet_bounds(contents: &str, mat_start: usize, mat_end: usize, args: &RippyArgs) -> SnippetBounds {
+    if let Some(max_line_length) = args.max_line_length {
+        let line_start = bounded_line_start(contents, mat_start, max_line_length.saturating_add(1));
+        let line_end = bounded_line_end(contents, mat_end, max_line_length.saturating_add(1));
+        if line_end - line_start > max_line_length {
+            return snippet_char_bounds(contents, mat_start, mat_end, LONG_LINE_RADIUS.min(args.radius_before), LONG_LINE_RADIUS.min(args.radius_after));
+        }
+    }
+    match args.word_radius {
+        Some(words) => snippet_word_bounds(contents, mat_start, mat_end, words),
+        None => snippet_char_bounds(contents, mat_start, mat_end, args.radius_before, args.radius_after),
+    }
+}
+
+/// Computes the snippet window bounds by expanding outward from the words containing the match by the number of whitespace delimited words provided, never splitting a word.
+fn snippet_word_bounds(contents: &str, mat_start: usize, mat_end: usize, words: usize) -> SnippetBounds {
+    let line_start = bounded_line_start(contents, mat_start, WORD_SCAN_LIMIT);
+    let line_end = bounded_line_end(contents, mat_end, WORD_SCAN_LIMIT);
+    let before = &contents[line_start..mat_start];
+    // Include the remainder of the word the match begins in before counting words
+    let mut start = before.rfind(char::is_whitespace).map_or(0, |pos| pos + 1);
+    for _ in 0..words {
+        let Some(word_end) = before[..start].rfind(|c: char| !c.is_whitespace()) else { break };
+        start = before[..word_end].rfind(char::is_whitespace).map_or(0, |pos| pos + 1);
+    }
+    // Nothing is elided if only whitespace remains before the window
+    let start = if before[..start].trim().is_empty() { line_start } else { line_start + start };
+
+    let after = &contents[mat_end..line_end];
+    let mut end = after.find(char::is_whitespace).unwrap_or(after.len());
+    for _ in 0..words {
+        let Some(word_start) = after[end..].find(|c: char| !c.is_whitespace()).map(|pos| end + pos) else { break };
+        end = after[word_start..].find(char::is_whitespace).map_or(after.len(), |pos| word_start + pos);
+    }
+    let end = if after[end..].trim().is_empty() { line_end } else { mat_end + end };
+    SnippetBounds { start, end }
+}
+
+/// Computes the snippet window bounds for the match using the character radius provided on either side, ensuring the bounds fall on valid UTF-8 boundaries.
+/// Line boundaries are only searched for within one byte beyond the radius, since any further away would be elided regardless.
+fn snippet_char_bounds(contents: &str, mat_start: usize, mat_end: usize, radius_before: usize, radius_after: usize) -> SnippetBounds {
+    let line_start = bounded_line_start(contents, mat_start, radius_before.saturating_add(1));
+    let line_end = bounded_line_end(contents, mat_end, radius_after.saturating_add(1));
+    let snippet_start = if mat_start > line_start + radius_before { mat_start - radius_before } else { line_start };
+    let snippet_end = if mat_end + radius_after < line_end { mat_end + radius_after } else { line_end };
+    // Ensure we slice at valid UTF-8 boundaries
+    SnippetBounds { start: floor_char_boundary(contents, snippet_start), end: ceil_char_boundary(contents, snippet_end) }
+}
+
+/// Formats the styled snippet displayed alongside a matching file, highlighting the match and marking any elided content on either side, along with the same snippet unstyled.
+fn format_snippet(contents: &str, mat_start: usize, mat_end: usize, args: &RippyArgs) -> (String, WindowMatch) {
+    let bounds = snippet_bounds(contents, mat_start, mat_end, args);
+    let valid_snippet = &contents[bounds.start..bounds.end];
+    let match_start_index = mat_start - bounds.start;
+    let match_end_index = mat_end - bounds.start;
+    let match_text = &valid_snippet[match_start_index..match_end_index];
+    let match_text = match &args.mark {
+        Some((left, right)) => concat_str!(left, match_text, right),
+        None => match_text.to_string(),
+    };
+    let (before, after) = (valid_snippet[..match_start_index].trim_start(), valid_snippet[match_end_index..].trim_end());
+    let snippet_mark = 
+        color_lines(args.colors.muted, false, before) +
+        &color_lines(StyleKind::Match.color(args), StyleKind::Match.is_bold(args), &match_text) +
+        &color_lines(args.colors.muted, false, after);
+    // Content was elided if the window starts or ends partway through the line
+    let is_start_elided = bounds.start > 0 && !is_line_break(contents.as_bytes()[bounds.start - 1]);
+    let is_end_elided = bounds.end < contents.len() && !is_line_break(contents.as_bytes()[bounds.end]);
+    let end_elipses = if is_end_elided {ansi_color!(&args.colors.muted, bold=false, &args.ellipsis)} else {"".to_string()};
+    let start_elipses = if is_start_elided {ansi_color!(&args.colors.muted, bold=false, &args.ellipsis)} else {"".to_string()};
+    let plain_before = concat_str!(if is_start_elided { &args.ellipsis } else { "" }, before);
+    let plain_after = concat_str!(after, if is_end_elided { &args.ellipsis } else { "" });
+    let window_match = WindowMatch { text: concat_str!(plain_before, match_text, plain_after), start: plain_before.len(), end: plain_before.len() + match_text.len() };
+    (start_elipses + &snippet_mark + &end_elipses, window_match)
+}
+
+/// Returns the 1-based line number containing the byte index, counting only `\n` so `\r\n` line endings are not counted twice.
+fn line_number_at(contents: &str, index: usize) -> usize {
+    contents.as_bytes()[..index].iter().filter(|&&byte| byte == b'\n').count() + 1
+}
+
+/// Applies the styling to each line of the text separately so multiline snippets remain styled when rendered beneath a tree prefix.
+fn color_lines(color: Option<&str>, is_bold: bool, text: &str) -> String {
+    if !text.contains('\n') {
+        return ansi_color!(color, bold=is_bold, text);
+    }
+    text.split('\n').map(|line| ansi_color!(color, bold=is_bold, line.trim_end_matches('\r'))).collect::<Vec<String>>().join("\n")
+}
+
+/// Reads the contents of the file to search, decoding them from the encoding requested or failing on anything other than valid UTF-8 by default unless invalid bytes are to be replaced.
+fn read_contents(path: impl AsRef<std::path::Path>, args: &RippyArgs) -> std::io::Result<String> {
+    match args.encoding {
+        Some(encoding) => std::fs::read(path).map(|bytes| encoding.decode(&bytes).0.into_owned()),
+        None if args.is_utf8_lossy => std::fs::read(path).map(|bytes| String::from_utf8_lossy(&bytes).into_owned()),
+        None => std::fs::read_to_string(path),
+    }
+}
+
+/// Outcome of searching a single file, with the window snippet to display if there was a match, the same window unstyled if requested, whether the file couldn't be read and every match if they were requested.
+#[derive(Debug, Default)]
+struct FileSearch {
+    window: Option<String>,
+    window_match: Option<WindowMatch>,
+    is_unreadable: bool,
+    matches: Vec<SearchMatch>,
+}
+
+/// Builds the details of a match found within the contents of the file at the path.
+fn search_match(path: &str, contents: &str, mat: regex::Match, args: &RippyArgs) -> SearchMatch {
+    let bounds = snippet_bounds(contents, mat.start(), mat.end(), args);
+    SearchMatch {
+        path: path.to_string(),
+        line_number: line_number_at(contents, mat.start()),
+        line: contents[..mat.start()].rfind(['\r', '\n']).map_or(contents, |pos| &contents[pos + 1..]).lines().next().unwrap_or_default().to_string(),
+        byte_range: mat.range(),
+        window: contents[bounds.start..bounds.end].trim().to_string(),
+    }
+}
+
+/// Searches the file for the first match to build the window snippet from, also collecting the details of every match if requested.
+fn search_file(path: &std::path::Path, is_symlink: bool, is_all_matches: bool, args: &RippyArgs) -> FileSearch {
+    let re = args.pattern.as_ref().unwrap(); // if args.is_search then args.pattern will have valid Regex else Error would've been raised during args parsing.
+    // Let symlinks fall through since its cheaper to let the File::open fail than to check through a syscall and traverse to find out if its a file or not
+    if let Ok(contents) = read_contents(path, args) {
+        // Only the first match is used for the snippet, no snippet needed if window not requested
+        let Some(mat) = re.find(&contents) else { return FileSearch::default() };
+        let matches = if is_all_matches { re.find_iter(&contents).map(|mat| search_match(&normalize_path(path), &contents, mat, args)).collect() } else { Vec::new() };
+        let (snippet, window_match) = if args.is_window { format_snippet(&contents, mat.start(), mat.end(), args) } else { ("".to_string(), WindowMatch::default()) };
+        if args.is_line_numbers {
+            let line_label = concat_str!("L", line_number_at(&contents, mat.start()).to_string(), ":");
+            let styled_label = ansi_color!(&args.colors.detail, bold=false, &line_label);
+            if snippet.is_empty() {
+                FileSearch { window: Some(styled_label), matches, ..FileSearch::default() }
+            } else {
+                let offset = line_label.len() + 1;
+                let window_match = WindowMatch { text: concat_str!(line_label, " ", window_match.text), start: window_match.start + offset, end: window_match.end + offset };
+                FileSearch { window: Some(concat_str!(styled_label, " ", snippet)), window_match: Some(window_match), is_unreadable: false, matches }
+            }
+        } else {
+            FileSearch { window: Some(snippet), window_match: Some(window_match).filter(|_| args.is_window), is_unreadable: false, matches }
+        }
+    } else if is_symlink && path.is_dir() {
+        // Symlinked directories fall through to here and are not files that failed to be read
+        FileSearch::default()
+    } else {
+        // File read error, tracked separately from files without a match
+        FileSearch { window: if args.is_show_unreadable { Some("".to_string()) } else { None }, is_unreadable: true, ..FileSearch::default() }
+    }
+}
+
+/// Searches the files found using the same filtering as `crawl_directory` and returns the details of every match found, ordered by path then position. Returns an empty result if no search pattern was provided.
+pub fn search(args: &'static RippyArgs) -> std::io::Result<Vec<SearchMatch>> {
+    if args.pattern.is_none() || !args.is_search {
+        return Ok(Vec::new());
+    }
+    // Matches are collected as each file is read during the crawl rather than reading the files again, kept only for the files left in the results
+    let (crawl_results, mut file_matches) = walk_directory(args, true)?;
+    Ok(crawl_results.paths.iter().filter_map(|leaf| file_matches.remove(&leaf.relative_path)).flatten().collect())
+}
+
+/// Marker appended to links to a directory containing them, which are listed without being followed.
+const LINK_CYCLE_MARKER: &str = " [cycle, not followed]";
+
+/// Marker appended to links whose target is missing or unreadable.
+const LINK_BROKEN_MARKER: &str = " [broken link]";
+
+/// Returns true if the last modified time falls within `--since` and `--until` and the file was last used before any `--atime-older` cutoff, treating unknown times as outside of any range.
+fn is_modified_within(metadata: Option<std::fs::Metadata>, args: &RippyArgs) -> bool {
+    let as_secs = |time: std::io::Result<std::time::SystemTime>| time.ok().and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok()).map(|duration| duration.as_secs_f64());
+    let Some(metadata) = metadata else {
+        return false;
+    };
+    let Some(modified) = as_secs(metadata.modified()) else {
+        return false;
+    };
+    // Access times are left stale on file systems mounted with `noatime`, so a file modified since is taken as used then, falling back to the modified time where access times aren't available
+    let last_used = as_secs(metadata.accessed()).map_or(modified, |accessed| accessed.max(modified));
+    args.since.is_none_or(|since| modified >= since) && args.until.is_none_or(|until| modified < until) && args.accessed_before.is_none_or(|cutoff| last_used < cutoff)
+}
+
+/// Checks if the symbolic link resolves to a directory containing the link itself, which would be walked endlessly if followed.
+fn is_link_cycle(path: &std::path::Path) -> bool {
+    match (std::fs::canonicalize(path), path.parent().map(std::fs::canonicalize)) {
+        (Ok(target), Some(Ok(parent))) => parent.starts_with(target),
+        _ => false,
+    }
+}
+
+/// Creates the entry for a symbolic link that failed to be followed, displayed with its target and a marker, returning `None` if the path isn't a link or is filtered out by name.
+fn unfollowed_link_leaf(path: &std::path::Path, is_cycle: bool, args: &RippyArgs) -> Option<TreeLeaf> {
+    if !std::fs::symlink_metadata(path).is_ok_and(|metadata| metadata.file_type().is_symlink()) {
+        return None;
+    }
+    let name = path.file_name()?.to_string_lossy().to_string();
+    let is_hidden = !args.include_all && name.starts_with('.');
+    if is_hidden || args.ignore_patterns.as_ref().is_some_and(|patterns| patterns.is_match(&name)) || args.include_patterns.as_ref().is_some_and(|patterns| !patterns.is_match(&name)) || args.filename_regex.as_ref().is_some_and(|re| !re.is_match(&name)) || args.name_style.as_ref().is_some_and(|style| !style.is_match(&name)) {
+        return None;
+    }
+    let relative_path = normalize_path(path);
+    let display = match &args.relative_to {
+        Some(base) => convert_path_relative_to(&relative_path, base),
+        None => if args.show_relative_path || args.show_full_path { relative_path.clone() } else { name.clone() },
+    };
+    let display = truncate_name(to_native_separators(display, args), args);
+    let display = if args.is_quote { concat_str!("\"", display, "\"") } else { display };
+    let target = std::fs::read_link(path).map_or_else(|_| "[unable to resolve]".to_string(), |target| normalize_path(&target));
+    let marker = if is_cycle { LINK_CYCLE_MARKER } else { LINK_BROKEN_MARKER };
+    let display = concat_str!(ansi_color!(args.colors.sym, bold=false, display), " -> ", target, ansi_color!(&args.colors.muted, bold=false, marker));
+    Some(TreeLeaf::new(name, relative_path, false, None, None, None, display, true))
+}
+
+/// Strips the root directory from the start of the path so it can be matched relative to the root.
+fn strip_root<'a>(path: &'a str, root: &std::path::Path) -> &'a str {
+    let root = root.to_string_lossy();
+    path.strip_prefix(root.as_ref()).map_or(path, |relative| relative.trim_start_matches('/'))
+}
+
+/// Sums the size of every file beneath the directory without following symbolic links, skipping any entries that cannot be read.
+fn measure_dir_size(path: &std::path::Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(path) else { return 0 };
+    entries.flatten().map(|entry| match entry.file_type() {
+        Ok(file_type) if file_type.is_dir() => measure_dir_size(&entry.path()),
+        Ok(file_type) if file_type.is_file() => entry.metadata().map_or(0, |m| m.len()),
+        _ => 0,
+    }).sum()
+}
+
+/// Identifies the file system the path resides on using its device id on unix or its volume prefix on Windows, returning `None` if it can't be determined.
+pub fn file_system_id(path: &std::path::Path) -> Option<u64> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        std::fs::metadata(path).ok().map(|metadata| metadata.dev())
+    }
+    #[cfg(not(unix))]
+    {
+        use std::hash::{Hash, Hasher};
+        let full_path = std::path::absolute(path).ok()?;
+        let Some(std::path::Component::Prefix(prefix)) = full_path.components().next() else { return None };
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        prefix.as_os_str().to_ascii_lowercase().hash(&mut hasher);
+        Some(hasher.finish())
+    }
+}
+
+/// Aggregates the device and size of each entry into the usage of each file system, naming them with the mount points provided and ordering by the most entries.
+pub fn aggregate_mounts(entries: &[(u64, u64)], mount_points: &std::collections::HashMap<u64, String>) -> Vec<MountUsage> {
+    let mut mounts: Vec<MountUsage> = Vec::new();
+    for &(device, size) in entries {
+        match mounts.iter_mut().find(|mount| mount.device == device) {
+            Some(mount) => {
+                mount.entries += 1;
+                mount.size += size;
+            },
+            None => mounts.push(MountUsage { device, mount_point: mount_points.get(&device).cloned(), entries: 1, size }),
+        }
+    }
+    mounts.sort_by(|a, b| b.entries.cmp(&a.entries).then(a.device.cmp(&b.device)));
+    mounts
+}
+
+/// Finds where each of the devices provided is mounted from `/proc/mounts`, keeping the first mount point listed for each. Returns no mount points where this isn't available.
+pub fn read_mount_points(devices: &std::collections::HashSet<u64>) -> std::collections::HashMap<u64, String> {
+    let mut mount_points = std::collections::HashMap::new();
+    let Ok(mounts) = std::fs::read_to_string("/proc/mounts") else { return mount_points };
+    for mount_point in mounts.lines().filter_map(|line| line.split_whitespace().nth(1)) {
+        if mount_points.len() == devices.len() {
+            break;
+        }
+        // Spaces and other whitespace within mount points are escaped as octal sequences such as `\040`
+        let mount_point = unescape_octal(mount_point);
+        if let Some(device) = file_system_id(std::path::Path::new(&mount_point)).filter(|device| devices.contains(device)) {
+            mount_points.entry(device).or_insert(mount_point);
+        }
+    }
+    mount_points
+}
+
+/// Replaces the `\NNN` octal escapes used in `/proc/mounts` with the characters they represent.
+fn unescape_octal(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(pos) = rest.find('\\') {
+        unescaped.push_str(&rest[..pos]);
+        match rest.get(pos + 1..pos + 4).and_then(|octal| u8::from_str_radix(octal, 8).ok()) {
+            Some(byte) => {
+                unescaped.push(char::from(byte));
+                rest = &rest[pos + 4..];
+            },
+            None => {
+                unescaped.push('\\');
+                rest = &rest[pos + 1..];
+            },
+        }
+    }
+    unescaped.push_str(rest);
+    unescaped
+}
+
+/// Crawls a file given in place of the directory, returning it as the only entry unless it was searched without a match.
+fn crawl_file_root(args: &RippyArgs, is_all_matches: bool) -> (CrawlResults, FileMatches) {
+    let metadata = std::fs::metadata(&args.directory).ok();
+    let root = normalize_path(&args.directory);
+    let name = args.directory.file_name().map_or_else(|| root.clone(), |name| name.to_string_lossy().to_string());
+    let last_modified = if args.is_stat_date {
+        metadata.as_ref().map_or(Some(0_f64), |m| m.modified().ok().and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok().map(|duration| duration.as_secs_f64())))
+    } else {
+        None
+    };
+    let size = if args.is_stat_size || args.is_size_sort { metadata.map_or(Some(0_u64), |m| Some(m.len())) } else { None };
+    let FileSearch { window, window_match, is_unreadable, matches } = if args.is_search { search_file(&args.directory, false, is_all_matches, args) } else { FileSearch::default() };
+    let paths = if args.is_search && window.is_none() {
+        Vec::new()
+    } else {
+        vec![TreeLeaf { window_match, ..TreeLeaf::new(name, &root, false, last_modified, size, window, &root, false) }]
+    };
+    let file_matches = if matches.is_empty() { FileMatches::new() } else { FileMatches::from([(root, matches)]) };
+    (CrawlResults { paths, paths_searched: 1, is_truncated: false, paths_unreadable: usize::from(is_unreadable), paths_hidden: 0, paths_ignored: 0, mounts: Vec::new() }, file_matches)
 }
 
 /// Primary directory crawl, returns `CrawlResults` struct containing Vec<TreeLeaf>.
 pub fn crawl_directory(args: &'static RippyArgs) -> std::io::Result<CrawlResults> {
+    walk_directory(args, false).map(|(crawl_results, _)| crawl_results)
+}
+
+/// Every match found within each file searched, keyed by the relative path of the file.
+type FileMatches = std::collections::HashMap<String, Vec<SearchMatch>>;
+
+/// Walks the directory for `crawl_directory`, also returning the details of every match within each file searched if requested.
+fn walk_directory(args: &'static RippyArgs, is_all_matches: bool) -> std::io::Result<(CrawlResults, FileMatches)> {
+    if args.is_file_root {
+        return Ok(crawl_file_root(args, is_all_matches));
+    }
+    // Shared across the read dir threads to bound the total entries visited and bytes of files scanned
+    let entries_visited = Arc::new(AtomicUsize::new(0));
+    let bytes_scanned = Arc::new(AtomicU64::new(0));
+    let is_truncated = Arc::new(AtomicBool::new(false));
+    let walk_truncated = Arc::clone(&is_truncated);
+    let paths_unreadable = Arc::new(AtomicUsize::new(0));
+    let walk_unreadable = Arc::clone(&paths_unreadable);
+    // Entries skipped while walking, counted where they are dropped so their contents are never included
+    let paths_hidden = Arc::new(AtomicUsize::new(0));
+    let walk_hidden = Arc::clone(&paths_hidden);
+    let paths_ignored = Arc::new(AtomicUsize::new(0));
+    let walk_ignored = Arc::clone(&paths_ignored);
+    // Links that couldn't be followed, listed un-followed once the walk completes
+    let unfollowed_links: Arc<Mutex<Vec<TreeLeaf>>> = Arc::new(Mutex::new(Vec::new()));
+    let walk_unfollowed = Arc::clone(&unfollowed_links);
+    // Device and size of each entry kept, looked up by path once the walk completes for `--mount-summary`
+    let entry_devices: Arc<Mutex<std::collections::HashMap<String, (u64, u64)>>> = Arc::new(Mutex::new(std::collections::HashMap::new()));
+    let walk_devices = Arc::clone(&entry_devices);
+    // Matches of each file searched, only collected for `search`
+    let file_matches: Arc<Mutex<FileMatches>> = Arc::new(Mutex::new(FileMatches::new()));
+    let walk_matches = Arc::clone(&file_matches);
+
+    // File system of the root that directories must share to be descended into if staying on one file system
+    let root_file_system = if args.is_one_file_system { file_system_id(&args.directory) } else { None };
+    // Separate pool for the reads done while searching so their concurrency can be bounded independently of the walk
+    let read_pool = args.read_threads.map(|threads| rayon::ThreadPoolBuilder::new().num_threads(threads).build()).transpose().map_err(std::io::Error::other)?;
+
     let walk_dir = WalkDirGeneric::<(Ignorer, TreeLeaf)>::new(&args.directory)
         .skip_hidden(false) // Modified from `skip_hidden(!args.include_all)` after new ignorer.rs module and process added.
         .max_depth(args.max_depth)
         .follow_links(args.is_follow_links)
-        .process_read_dir(|_depth, _path, ignorer, children| {
+        .process_read_dir(move |_depth, _path, ignorer, children| {
+
+            // 0. Enforce entry budget by dropping children beyond it and preventing any further descent once exhausted
+            if args.max_entries != usize::MAX {
+                let visited = entries_visited.fetch_add(children.len(), Ordering::Relaxed);
+                let remaining = args.max_entries.saturating_sub(visited);
+                if children.len() > remaining {
+                    children.truncate(remaining);
+                    walk_truncated.store(true, Ordering::Relaxed);
+                }
+            }
+            // 0. Enforce size budget by summing the size of each file as it is visited, dropping every file from the first beyond it
+            if let Some(max_total_size) = args.max_total_size {
+                children.retain(|child| child.as_ref().is_ok_and(|dir_entry| !dir_entry.file_type.is_file()) || {
+                    let size = child.as_ref().ok().and_then(|dir_entry| dir_entry.metadata().ok()).map_or(0, |metadata| metadata.len());
+                    let is_within = !walk_truncated.load(Ordering::Relaxed) && bytes_scanned.fetch_add(size, Ordering::Relaxed).saturating_add(size) <= max_total_size;
+                    if !is_within {
+                        walk_truncated.store(true, Ordering::Relaxed);
+                    }
+                    is_within
+                });
+            }
+            // Once a budget is exhausted no further directories are descended into
+            if walk_truncated.load(Ordering::Relaxed) {
+                children.iter_mut().flatten().for_each(|dir_entry| dir_entry.read_children_path = None);
+            }
             
+            // Links whose targets are missing or lead back to an ancestor fail to be followed, so they are set aside to be listed with a marker rather than dropped
+            if args.is_follow_links {
+                let links = children.iter()
+                    .filter_map(|child| child.as_ref().err())
+                    .filter_map(|error| error.path().and_then(|path| unfollowed_link_leaf(path, error.loop_ancestor().is_some(), args)));
+                if let Ok(mut unfollowed) = walk_unfollowed.lock() {
+                    unfollowed.extend(links);
+                }
+            }
+
             // Track within directories whether new matcher requires second iteration
             let mut requires_second_filter = false;
 
             // 1. Custom filter first pass
             children.retain(|dir_entry_result| {
-                dir_entry_result.as_ref().map_or(false, |dir_entry| {
+                dir_entry_result.as_ref().is_ok_and(|dir_entry| {
                     // Convert the file name to a string slice
                     dir_entry.file_name().to_str()
-                        .map_or(false, |fname| {
+                        .is_some_and(|fname| {
                             let dir_entry_path = dir_entry.path();
                             let dir_entry_ftype = dir_entry.file_type;
                             let is_ftype_dir = dir_entry_ftype.is_dir() || ( dir_entry_ftype.is_symlink() && dir_entry_path.is_dir() );
                             let is_ftype_file = dir_entry_ftype.is_file() || ( dir_entry_ftype.is_symlink() && dir_entry_path.is_file() );
                             let is_hidden_file = _depth.is_some() && fname.starts_with(".");
 
-                            if is_hidden_file && args.is_gitignore && fname == ".gitignore" {
-                                // Grab the .gitignore file now unless user wants to include all
-                                *ignorer = Ignorer::new(&dir_entry_path);
+                            if is_hidden_file && ((args.is_gitignore && fname == ".gitignore") || (args.is_rippyignore && fname == ".rippyignore")) {
+                                // Grab the .gitignore or .rippyignore file now unless user wants to include all
+                                ignorer.push(&dir_entry_path);
                                 requires_second_filter = true;
                             }
+                            // Output files within the root are never listed
+                            if !args.excluded_paths.is_empty() && std::path::absolute(&dir_entry_path).is_ok_and(|path| args.excluded_paths.contains(&normalize_path_lexically(&path))) {
+                                return false
+                            }
+                            // Only files tracked by git or read from stdin and the directories containing them are listed if requested
+                            if args.listed_paths.as_ref().is_some_and(|listed| !std::path::absolute(&dir_entry_path).is_ok_and(|path| listed.contains(&normalize_path_lexically(&path)))) {
+                                return false
+                            }
+                            // Only the directories matched by a glob root, their contents and the directories leading to them are listed
+                            if args.glob_roots.as_ref().is_some_and(|roots| !std::path::absolute(&dir_entry_path).is_ok_and(|path| {
+                                let path = normalize_path_lexically(&path);
+                                roots.iter().any(|root| path.starts_with(root) || root.starts_with(&path))
+                            })) {
+                                return false
+                            }
                             // Separated checks for hidden file and gitignored file
-                            if !args.include_all && is_hidden_file {
+                            if !args.include_all && is_hidden_file && !(is_ftype_file && args.dotfile_include_patterns.as_ref().is_some_and(|patterns| patterns.is_match(fname))) {
+                                walk_hidden.fetch_add(1, Ordering::Relaxed);
                                 return false
                             }
                             // Needs to be ignored irrespective of file or directory type
                             if ignorer.is_ignore(&dir_entry_path, is_ftype_dir) 
-                                || args.ignore_patterns.as_ref().map_or(false, |patterns| patterns.is_match(fname)) {
+                                || args.ignore_patterns.as_ref().is_some_and(|patterns| patterns.is_match(fname)) {
                                 // println!("Skipped due to mathcing ignore glob: {:?}", dir_entry_path);
+                                walk_ignored.fetch_add(1, Ordering::Relaxed);
+                                return false
+                            }
+                            // Excluded directories are dropped here so their contents are never read
+                            if is_ftype_dir && args.exclude_dir_patterns.as_ref().is_some_and(|patterns| patterns.is_match(fname)) {
+                                walk_ignored.fetch_add(1, Ordering::Relaxed);
                                 return false
                             }
                             // Return true for dirs that have already passed ignore check
                             if is_ftype_dir {
-                                return true
+                                true
                             } else {
                                 // Result of boolean checks for passing include if is file or return false by boolean fail if filetype is not resolved
-                                return is_ftype_file && args.include_patterns.as_ref().map_or(true, |patterns| patterns.is_match(fname)) 
+                                is_ftype_file && args.include_patterns.as_ref().is_none_or(|patterns| patterns.is_match(fname)) && args.filename_regex.as_ref().is_none_or(|re| re.is_match(fname)) && args.name_style.as_ref().is_none_or(|style| style.is_match(fname))
+                                    && ((args.since.is_none() && args.until.is_none() && args.accessed_before.is_none()) || is_modified_within(dir_entry.metadata().ok(), args))
                             }
                         }) // Defaults to false if file_name is None or to_str fails
                 }) // Defaults to false if dir_entry_result is Err
             });
 
             // 2. Custom filter second pass if needed due to gitignore initialization point
-            if args.is_gitignore && ignorer.has_matcher() && requires_second_filter {
+            if ignorer.has_matcher() && requires_second_filter {
                 children.retain(|dir_entry_result| {
-                    dir_entry_result.as_ref().map_or(false, |dir_entry| {
+                    dir_entry_result.as_ref().is_ok_and(|dir_entry| {
                         let dir_entry_ftype = dir_entry.file_type;
                         let is_ftype_dir = dir_entry_ftype.is_dir() || ( dir_entry_ftype.is_symlink() && dir_entry.path().is_dir() );
                         // Results in skipping those entries that may have been missed in first retention check due to timing of gitignore instantiation
-                        !ignorer.is_ignore(&dir_entry.path(), is_ftype_dir)
+                        let is_ignored = ignorer.is_ignore(dir_entry.path(), is_ftype_dir);
+                        if is_ignored {
+                            walk_ignored.fetch_add(1, Ordering::Relaxed);
+                        }
+                        !is_ignored
                     })
                 });
             }
 
-            // 3. Create the client state for entries we intend to keep and build the tree from
-            children.iter_mut().for_each(|dir_entry_result| {
+            // 3. Search the files kept, dispatching the reads to the separate pool if one was requested so they don't hold up the walk
+            let mut searched: Vec<FileSearch> = if args.is_search {
+                let files: Vec<Option<(std::path::PathBuf, bool)>> = children.iter()
+                    .map(|dir_entry_result| dir_entry_result.as_ref().ok().filter(|dir_entry| !dir_entry.file_type().is_dir()).map(|dir_entry| (dir_entry.path(), dir_entry.file_type().is_symlink())))
+                    .collect();
+                let search = |file: &Option<(std::path::PathBuf, bool)>| file.as_ref().map_or_else(FileSearch::default, |(path, is_symlink)| search_file(path, *is_symlink, is_all_matches, args));
+                match &read_pool {
+                    Some(pool) => pool.install(|| files.par_iter().map(search).collect()),
+                    None => files.iter().map(search).collect(),
+                }
+            } else {
+                Vec::new()
+            };
+            walk_unreadable.fetch_add(searched.iter().filter(|file| file.is_unreadable).count(), Ordering::Relaxed);
+
+            // 4. Create the client state for entries we intend to keep and build the tree from
+            children.iter_mut().enumerate().for_each(|(i, dir_entry_result)| {
                 if let Ok(dir_entry) = dir_entry_result {
-                    // Let symlinks fall through since its cheaper to let the File::open fail than to check through a syscall and traverse to find out if its a file or not
-                    let window_snippet: Option<String> = if !args.is_search || dir_entry.file_type().is_dir() { None } else {
-                        let re = args.pattern.as_ref().unwrap(); // if args.is_search then args.pattern will have valid Regex else Error would've been raised during args parsing.
-                        let snippet_from_file_read: Option<String> = if let Ok(contents) = std::fs::read_to_string(dir_entry.path()) {
-                            if re.is_match(&contents) {
-                                if args.is_window {
-                                    if let Some(mat) = re.find(&contents) {
-                                        // Snippet extraction begins here
-                                        let line_start = contents[..mat.start()].rfind(&['\r', '\n']).map(|pos| pos + 1).unwrap_or(0);
-                                        let line_end = contents[mat.end()..].find(&['\r', '\n']).map(|pos| mat.end() + pos).unwrap_or(contents.len());
-                                        let snippet_start = if mat.start() > line_start + args.radius { mat.start() - args.radius } else { line_start };
-                                        let snippet_end = if mat.end() + args.radius < line_end { mat.end() + args.radius } else { line_end };
-                                        let snippet_start_adjusted = if snippet_start < line_start { line_start } else { snippet_start };
-                                        let snippet_end_adjusted = if snippet_end > line_end { line_end } else { snippet_end };
-                                        // Ensure we slice at valid UTF-8 boundaries
-                                        let valid_snippet_start = if contents.is_char_boundary(snippet_start_adjusted) {
-                                            snippet_start_adjusted
-                                        } else {
-                                            contents.char_indices().take_while(|&(i, _)| i < snippet_start_adjusted).last().map(|(i, _)| i).unwrap_or(snippet_start_adjusted)
-                                        };
-                                        let valid_snippet_end = if contents.is_char_boundary(snippet_end_adjusted) {
-                                            snippet_end_adjusted
-                                        } else {
-                                            contents.char_indices().take_while(|&(i, _)| i < snippet_end_adjusted).last().map(|(i, c)| i + c.len_utf8()).unwrap_or(snippet_end_adjusted)
-                                        };
-                                        let valid_snippet = &contents[valid_snippet_start..valid_snippet_end];
-                                        let match_start_index = mat.start() - valid_snippet_start;
-                                        let match_end_index = mat.end() - valid_snippet_start;
-                                        let snippet_mark = 
-                                            ansi_color!(&args.colors.muted, bold=false, &valid_snippet[..match_start_index].trim_start().to_owned()) +
-                                            &ansi_color!(&args.colors.window, bold=!args.is_grayscale, &valid_snippet[match_start_index..match_end_index]) +
-                                            &ansi_color!(&args.colors.muted, bold=false, valid_snippet[match_end_index..].trim_end());
-                                        let end_elipses = if snippet_end != line_end {ansi_color!(&args.colors.muted, bold=false, "...")} else {"".to_string()};
-                                        let start_elipses = if snippet_start != line_start {ansi_color!(&args.colors.muted, bold=false, "...")} else {"".to_string()};
-                                        let snippet_fmt = start_elipses.to_owned() + &snippet_mark + &end_elipses;
-                                            // Snippet extraction ends, return matched snippet
-                                            Some(snippet_fmt)
-                                        } else {
-                                            // File still matched but unable to find snippet due to reading contents to string
-                                            Some("".to_string())
-                                        }
-                                } else {
-                                    // File matches search pattern but no snippet needed due to args
-                                    Some("".to_string())
-                                }
-                            } else {
-                                // No match due to `re.is_match()` is False
-                                None
-                            }
-                        } else {
-                            // File read error from `if let Ok(contents) = std::fs::read_to_string(path)`
-                            None 
-                        };
-                    // Gets assigned to `window_snippet` on line ~86
-                    snippet_from_file_read
-                    };
+                    let FileSearch { window: window_snippet, window_match, is_unreadable, matches } = searched.get_mut(i).map(std::mem::take).unwrap_or_default();
 
                     if !args.is_search || dir_entry.file_type().is_dir() || window_snippet.is_some() || ( dir_entry.file_type().is_symlink() && dir_entry.path().is_dir() ) {
                         let is_symbolic = dir_entry.file_type().is_symlink();
                         let name = dir_entry.file_name().to_string_lossy().to_string();
-                        let relative_path = dir_entry.path().to_string_lossy().replace("\\", "/");
+                        let relative_path = normalize_path(&dir_entry.path());
                         let entry_path = dir_entry.path();
                         
-                        let last_modified = if args.show_date {
+                        let last_modified = if args.is_stat_date {
                             dir_entry.metadata().map_or(Some(0_f64), |m| m.modified().ok().and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok().map(|duration| duration.as_secs_f64())))
                         } else {
                             None
                         };
-                        let size = if args.show_size {
-                            dir_entry.metadata().map_or(Some(0_u64), |m| Some(m.len()))
+                        let is_dir = dir_entry.file_type().is_dir() || ( is_symbolic && entry_path.is_dir() );
+                        // Directories exactly at the depth limit are always listed but never descended into, rather than leaving jwalk to read them as empty
+                        let is_at_depth_limit = is_dir && dir_entry.depth >= args.max_depth;
+                        if is_at_depth_limit {
+                            dir_entry.read_children_path = None;
+                        }
+                        let size = if args.is_deep_size && is_at_depth_limit {
+                            // Directories at the depth limit are not descended into so their content is measured directly
+                            Some(measure_dir_size(&dir_entry.path()))
+                        } else if args.is_stat_size || (args.is_size_sort && !is_dir) {
+                            // Sizes are also needed to sort by, recognize empty files or total extensions even when not displayed
+                            let metadata = if args.is_dereference && is_symbolic {
+                                // Broken links have no target to measure so fall back to the link itself
+                                std::fs::metadata(&entry_path).ok().or_else(|| dir_entry.metadata().ok())
+                            } else {
+                                dir_entry.metadata().ok()
+                            };
+                            metadata.map_or(Some(0_u64), |m| Some(m.len()))
                         } else {
                             None
                         };
-                        let is_dir = dir_entry.file_type().is_dir() || ( is_symbolic && entry_path.is_dir() );
-                        let display = if args.show_relative_path || args.show_full_path { &relative_path } else { &name };
+                        let display = match &args.relative_to {
+                            Some(base) => &convert_path_relative_to(&relative_path, base),
+                            None => if args.show_relative_path || args.show_full_path { &relative_path } else { &name },
+                        };
+                        let display = if args.is_native_separators { &to_native_separators(display.to_owned(), args) } else { display };
+                        let display = if args.max_name_length.is_some() { &truncate_name(display.to_owned(), args) } else { display };
                         let display = if args.is_quote { &concat_str!("\"", display, "\"") } else { display };
+                        // Opaque directories are kept as leaves by clearing the path jwalk would otherwise descend into
+                        let is_opaque = is_dir && args.opaque_dirs.iter().any(|dir| dir == &name);
+                        // Followed links leading back to a directory containing them are kept without descending so the cycle isn't walked
+                        let is_cycle = is_dir && args.is_follow_links && dir_entry.path_is_symlink() && is_link_cycle(&entry_path);
+                        if is_opaque || is_cycle || (is_dir && root_file_system.is_some() && file_system_id(&entry_path) != root_file_system) {
+                            dir_entry.read_children_path = None;
+                        }
+                        let display = if is_opaque { &concat_str!(display, "/", ansi_color!(&args.colors.muted, bold=false, " (contents hidden)")) } else { display };
+                        let display = if is_cycle { &concat_str!(display, ansi_color!(&args.colors.muted, bold=false, LINK_CYCLE_MARKER)) } else { display };
+                        let display = if is_unreadable { &concat_str!(display, ansi_color!(&args.colors.muted, bold=false, " [unreadable]")) } else { display };
                         let display = if is_symbolic {
                             let sym_path = std::fs::read_link(&entry_path)
                             .map_or("[unable to resolve]".to_string(), |p| { 
                                 let (color, is_bold) = if is_dir {
-                                    (args.colors.dir, !args.is_grayscale)
+                                    (args.colors.dir, !args.is_no_bold)
                                 } else if p.is_executable() || entry_path.is_executable() {
                                     (args.colors.exec, false)
                                 } else {
                                     (args.colors.file, false)
                                 };
-                                let sym_display = if args.show_relative_path || args.show_full_path { p.to_string_lossy().replace("\\", "/") } else {p.file_name().map_or_else(|| p.to_string_lossy().replace("\\", "/"), |p| p.to_string_lossy().replace("\\", "/"))};
+                                let sym_display = if args.show_relative_path || args.show_full_path { normalize_path(&p) } else {p.file_name().map_or_else(|| normalize_path(&p), |name| normalize_path(std::path::Path::new(name)))};
+                                let sym_display = to_native_separators(sym_display, args);
                                 let sym_display = if args.is_quote {concat_str!("\"", sym_display, "\"")} else {sym_display};
                                 // Now we have it as a string with the right color scheme and display style
                                 let sym_display = ansi_color!(color, bold=is_bold, sym_display);
                                 sym_display
                                 }
                             );
-                            &concat_str!(ansi_color!(args.colors.sym, bold=is_dir && !args.is_grayscale, display), " -> ", sym_path)
+                            &concat_str!(ansi_color!(args.colors.sym, bold=is_dir && !args.is_no_bold, display), " -> ", sym_path)
                         } else {
                             display
                         };
-                        dir_entry.client_state = TreeLeaf::new(&name, &relative_path, is_dir, last_modified, size, window_snippet, display, is_symbolic);
+                        if args.is_mount_summary {
+                            if let (Some(device), Ok(mut devices)) = (file_system_id(&entry_path), walk_devices.lock()) {
+                                let file_size = if is_dir { 0 } else { size.or_else(|| dir_entry.metadata().ok().map(|m| m.len())).unwrap_or_default() };
+                                devices.insert(relative_path.clone(), (device, file_size));
+                            }
+                        }
+                        if !matches.is_empty() {
+                            if let Ok(mut file_matches) = walk_matches.lock() {
+                                file_matches.insert(relative_path.clone(), matches);
+                            }
+                        }
+                        dir_entry.client_state = TreeLeaf { window_match, ..TreeLeaf::new(&name, &relative_path, is_dir, last_modified, size, window_snippet, display, is_symbolic) };
                     }
                 }
             });
@@ -231,12 +769,13 @@ pub fn crawl_directory(args: &'static RippyArgs) -> std::io::Result<CrawlResults
     let mut paths_searched:usize = 0;
 
     for entry_result in walk_dir {
-        let entry = entry_result.unwrap();
+        // Entries that fail to be read are left out rather than aborting the rest of the walk
+        let Ok(entry) = entry_result else { continue };
         if entry.file_type().is_file() && entry.depth > 0 {
             paths_searched += 1;
         }
-        // Skip entry if its the root dir or if we're searching for matching patterns and none was found or if we're targeting specific file patterns and the empty dir has no matches and itself doesnt match the pattern
-        if entry.depth() == 0 || (args.is_search && entry.client_state.window.is_none()) || (entry.client_state.is_dir && args.include_patterns.as_ref().map_or(false, |patterns| !patterns.is_match(&entry.file_name().to_string_lossy().to_string()))) {
+        // Skip entry if its the root dir or if we're searching for matching patterns and none was found or if its path doesnt match the glob
+        if entry.depth() == 0 || (args.is_search && entry.client_state.window.is_none()) || args.path_glob.as_ref().is_some_and(|glob| !glob.is_match(strip_root(&entry.client_state.relative_path, &args.directory))) {
             // DEBUG only:
             // println!("Entry skipped at depth [{}]: {:?} with client state: {:?}", entry.depth, entry.file_name(), entry.client_state);
             continue;
@@ -244,5 +783,34 @@ pub fn crawl_directory(args: &'static RippyArgs) -> std::io::Result<CrawlResults
             paths.push(entry.client_state);
         }
     }
-    Ok( CrawlResults { paths, paths_searched } )
+    // Links that couldn't be followed have no contents to search
+    if !args.is_search {
+        if let Ok(mut unfollowed) = unfollowed_links.lock() {
+            paths.extend(unfollowed.drain(..).filter(|leaf| args.path_glob.as_ref().is_none_or(|glob| glob.is_match(strip_root(&leaf.relative_path, &args.directory)))));
+        }
+    }
+    // Directories are kept for their included contents rather than their own names, dropping those left without any
+    if args.include_patterns.is_some() || args.filename_regex.is_some() || args.name_style.is_some() || args.since.is_some() || args.until.is_some() || args.accessed_before.is_some() {
+        let mut ancestors: std::collections::HashSet<String> = std::collections::HashSet::new();
+        for leaf in paths.iter().filter(|leaf| !leaf.is_dir) {
+            let mut leaf_path = leaf.relative_path.as_str();
+            while let Some((parent, _)) = leaf_path.rsplit_once('/') {
+                if !ancestors.insert(parent.to_string()) {
+                    break;
+                }
+                leaf_path = parent;
+            }
+        }
+        paths.retain(|leaf| !leaf.is_dir || ancestors.contains(&leaf.relative_path));
+    }
+    // File systems are only summarized for the entries left after filtering
+    let mounts = match entry_devices.lock() {
+        Ok(devices) if args.is_mount_summary => {
+            let entries: Vec<(u64, u64)> = paths.iter().filter_map(|leaf| devices.get(&leaf.relative_path).copied()).collect();
+            aggregate_mounts(&entries, &read_mount_points(&entries.iter().map(|(device, _)| *device).collect()))
+        },
+        _ => Vec::new(),
+    };
+    let file_matches = file_matches.lock().map(|mut file_matches| std::mem::take(&mut *file_matches)).unwrap_or_default();
+    Ok( (CrawlResults { paths, paths_searched, is_truncated: is_truncated.load(Ordering::Relaxed), paths_unreadable: paths_unreadable.load(Ordering::Relaxed), paths_hidden: paths_hidden.load(Ordering::Relaxed), paths_ignored: paths_ignored.load(Ordering::Relaxed), mounts }, file_matches) )
 }
\ No newline at end of file
@@ -0,0 +1,75 @@
+use serde_json::json;
+
+use crate::{ansi_color, concat_str};
+use crate::tcolor::{ERROR_COLOR, WARN_COLOR};
+
+/// Category of an error, included when errors are reported as JSON so tools can react without parsing the message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    Config,
+    Git,
+    InvalidDirectory,
+    InvalidGlob,
+    InvalidRegex,
+    PatternFile,
+    Output,
+    Read,
+    Select,
+}
+impl ErrorKind {
+    /// Name of the kind as written in JSON errors.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorKind::Config => "config",
+            ErrorKind::Git => "git",
+            ErrorKind::InvalidDirectory => "invalid_directory",
+            ErrorKind::InvalidGlob => "invalid_glob",
+            ErrorKind::InvalidRegex => "invalid_regex",
+            ErrorKind::PatternFile => "pattern_file",
+            ErrorKind::Output => "output",
+            ErrorKind::Read => "read",
+            ErrorKind::Select => "select",
+        }
+    }
+}
+
+/// Error reported on stderr either as styled text or as a JSON object, with the path involved quoted within the message if there is one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RippyError {
+    pub kind: ErrorKind,
+    pub message: String,
+    pub path: Option<String>,
+}
+impl RippyError {
+    /// Creates a new error of the kind provided, expecting any path to already be quoted within the message as `'path'`.
+    pub fn new(kind: ErrorKind, message: impl Into<String>, path: Option<String>) -> Self {
+        RippyError { kind, message: message.into(), path }
+    }
+    /// Converts the error to a JSON object `{"error": "...", "path": "...", "kind": "..."}`.
+    pub fn to_json(&self) -> serde_json::Value {
+        json!({
+            "error": self.message,
+            "path": self.path,
+            "kind": self.kind.as_str(),
+        })
+    }
+    /// Formats the error as text prefixed by `error:`, highlighting the quoted path.
+    pub fn to_text(&self, is_bold: bool) -> String {
+        let message = match &self.path {
+            Some(path) => {
+                let quoted = concat_str!("'", path, "'");
+                self.message.replacen(&quoted, &concat_str!("'", ansi_color!(WARN_COLOR, bold=false, path), "'"), 1)
+            },
+            None => self.message.to_owned(),
+        };
+        concat_str!(ansi_color!(ERROR_COLOR, bold=is_bold, "error:"), " ", message)
+    }
+    /// Writes the error to stderr as a single line in the format requested.
+    pub fn report(&self, is_json: bool, is_bold: bool) {
+        if is_json {
+            eprintln!("{}", self.to_json());
+        } else {
+            eprintln!("{}", self.to_text(is_bold));
+        }
+    }
+}
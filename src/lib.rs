@@ -1,4 +1,7 @@
 pub mod args;
+pub mod config;
 pub mod crawl;
+pub mod error;
+pub mod select;
 pub mod tcolor;
 pub mod tree;
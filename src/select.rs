@@ -0,0 +1,101 @@
+use std::io::{self, IsTerminal, Write};
+
+use crossterm::{cursor, execute, queue, terminal};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::{Attribute, Print, SetAttribute};
+
+use crate::args::RippyArgs;
+use crate::concat_str;
+use crate::tree::{EntryType, Tree};
+
+/// Single selectable line of the interactive tree holding the plain text rendered and the path printed when selected.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelectRow {
+    pub line: String,
+    pub path: String,
+    pub entry_type: EntryType,
+}
+
+/// Flattens the tree into rows in the same sorted depth-first order used when rendering, with each row's path joined from the root name.
+pub fn collect_rows(tree: &Tree, args: &RippyArgs) -> Vec<SelectRow> {
+    let mut rows = Vec::new();
+    collect_rows_inner(tree, args, "", &tree.name, &mut rows);
+    rows
+}
+
+fn collect_rows_inner(tree: &Tree, args: &RippyArgs, prefix: &str, path: &str, rows: &mut Vec<SelectRow>) {
+    let mut children: Vec<&Tree> = tree.children.values().collect();
+    let sort_by = tree.child_order(args);
+    children.sort_by(|a, b| sort_by(a, b));
+    let last_index = children.len().saturating_sub(1);
+    for (i, child) in children.into_iter().enumerate() {
+        let is_last = i == last_index;
+        let connector = concat_str!(if is_last { args.glyphs.elbow } else { args.glyphs.tee }, args.glyphs.dash.repeat(2), " ");
+        let child_path = concat_str!(path.trim_end_matches('/'), "/", &child.name);
+        rows.push(SelectRow {
+            line: concat_str!(prefix, connector, &child.name),
+            path: child_path.clone(),
+            entry_type: child.entry_type,
+        });
+        let child_prefix = if is_last { concat_str!(prefix, "    ") } else { concat_str!(prefix, args.glyphs.pipe, "   ") };
+        collect_rows_inner(child, args, &child_prefix, &child_path, rows);
+    }
+}
+
+/// Runs the interactive selection over the rows, drawing to stderr so the selected paths can be piped from stdout. Returns the paths of the rows marked when confirmed, or nothing if cancelled.
+/// Errors if stdin or stderr is not a terminal since there is nothing to interact with.
+pub fn run_select(rows: &[SelectRow]) -> io::Result<Vec<String>> {
+    if !io::stdin().is_terminal() || !io::stderr().is_terminal() {
+        return Err(io::Error::new(io::ErrorKind::Unsupported, "interactive selection requires a terminal"));
+    }
+    let mut stderr = io::stderr();
+    terminal::enable_raw_mode()?;
+    execute!(stderr, terminal::EnterAlternateScreen, cursor::Hide)?;
+    let result = select_loop(rows, &mut stderr);
+    // Restore the terminal even if the loop failed
+    execute!(stderr, cursor::Show, terminal::LeaveAlternateScreen)?;
+    terminal::disable_raw_mode()?;
+    let marked = result?;
+    Ok(rows.iter().zip(marked).filter(|(_, is_marked)| *is_marked).map(|(row, _)| row.path.clone()).collect())
+}
+
+/// Handles drawing and key events until the selection is confirmed or cancelled, returning which rows are marked.
+fn select_loop(rows: &[SelectRow], writer: &mut impl Write) -> io::Result<Vec<bool>> {
+    let mut marked = vec![false; rows.len()];
+    let mut position: usize = 0;
+    let mut offset: usize = 0;
+    loop {
+        let (_, height) = terminal::size()?;
+        // Reserve the last line for the key hints
+        let visible = (height as usize).saturating_sub(1).max(1);
+        if position < offset {
+            offset = position;
+        } else if position >= offset + visible {
+            offset = position + 1 - visible;
+        }
+        queue!(writer, terminal::Clear(terminal::ClearType::All), cursor::MoveTo(0, 0))?;
+        for (i, row) in rows.iter().enumerate().skip(offset).take(visible) {
+            let mark = if marked[i] { "[x] " } else { "[ ] " };
+            if i == position {
+                queue!(writer, SetAttribute(Attribute::Reverse), Print(concat_str!(mark, &row.line)), SetAttribute(Attribute::Reset))?;
+            } else {
+                queue!(writer, Print(concat_str!(mark, &row.line)))?;
+            }
+            queue!(writer, Print("\r\n"))?;
+        }
+        queue!(writer, cursor::MoveTo(0, height.saturating_sub(1)), Print("up/down: move  space: toggle  enter: confirm  esc: cancel"))?;
+        writer.flush()?;
+
+        if let Event::Key(KeyEvent { code, modifiers, kind: KeyEventKind::Press, .. }) = event::read()? {
+            match code {
+                KeyCode::Up | KeyCode::Char('k') => position = position.saturating_sub(1),
+                KeyCode::Down | KeyCode::Char('j') => position = (position + 1).min(rows.len().saturating_sub(1)),
+                KeyCode::Char(' ') => if let Some(is_marked) = marked.get_mut(position) { *is_marked = !*is_marked },
+                KeyCode::Enter => return Ok(marked),
+                KeyCode::Esc | KeyCode::Char('q') => return Ok(vec![false; rows.len()]),
+                KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => return Ok(vec![false; rows.len()]),
+                _ => {},
+            }
+        }
+    }
+}
@@ -16,10 +16,34 @@ const FILE_COLOR: Option<&'static str> = None; // Revised 2024-09-19
 const SYM_COLOR: Option<&'static str> = Some("\x1b[38;5;147m");
 const DETAILS_COLOR: Option<&'static str> = Some("\x1b[38;5;248m");
 const MATCHES_COLOR: Option<&'static str> = Some("\x1b[38;5;42m");
-const SEARCH_COLOR: Option<&'static str> = Some("\x1b[38;5;220m");
+const SEARCH_COLOR: Option<&'static str> = Some("\x1b[38;5;110m"); // Kept apart from the zero and match colors so the summary counts stay distinct
 const ZERO_COLOR: Option<&'static str> = Some("\x1b[38;5;220m");
+const ADDED_COLOR: Option<&'static str> = Some("\x1b[38;5;114m");
+const REMOVED_COLOR: Option<&'static str> = Some("\x1b[38;5;203m");
+const CHANGED_COLOR: Option<&'static str> = Some("\x1b[38;5;221m");
 const NONE_COLOR: Option<&'static str> = None;
 
+/* ================== 8 bit ANSI per-extension file palette ================== */
+const SOURCE_COLOR: Option<&'static str> = Some("\x1b[38;5;150m");
+const CONFIG_COLOR: Option<&'static str> = Some("\x1b[38;5;179m");
+const DOCS_COLOR: Option<&'static str> = Some("\x1b[38;5;252m");
+const IMAGE_COLOR: Option<&'static str> = Some("\x1b[38;5;176m");
+const ARCHIVE_COLOR: Option<&'static str> = Some("\x1b[38;5;167m");
+
+/* ================= 8 bit ANSI size gradient for --size-heat ================= */
+const HEAT_TINY_COLOR: Option<&'static str> = Some("\x1b[38;5;42m");
+const HEAT_SMALL_COLOR: Option<&'static str> = Some("\x1b[38;5;149m");
+const HEAT_MEDIUM_COLOR: Option<&'static str> = Some("\x1b[38;5;220m");
+const HEAT_LARGE_COLOR: Option<&'static str> = Some("\x1b[38;5;208m");
+const HEAT_HUGE_COLOR: Option<&'static str> = Some("\x1b[38;5;196m");
+
+/* =============== 8 bit ANSI age gradient for --color-scale date =============== */
+const AGE_DAY_COLOR: Option<&'static str> = Some("\x1b[38;5;231m");
+const AGE_WEEK_COLOR: Option<&'static str> = Some("\x1b[38;5;253m");
+const AGE_MONTH_COLOR: Option<&'static str> = Some("\x1b[38;5;249m");
+const AGE_YEAR_COLOR: Option<&'static str> = Some("\x1b[38;5;245m");
+const AGE_OLD_COLOR: Option<&'static str> = Some("\x1b[38;5;240m");
+
 #[cfg(windows)]
 extern "system" {
     fn GetStdHandle(nStdHandle: u32) -> *mut std::ffi::c_void;
@@ -29,17 +53,24 @@ extern "system" {
 
 /// Enable ANSI escape sequences if currently on Windows. Returns `true` if successful or unnecessary (i.e., not Windows) or `false` if enabling ANSI support on Windows failed.
 pub fn enable_ansi_support() -> bool {
+    try_enable_ansi_support().is_ok()
+}
+
+/// Enable ANSI escape sequences if currently on Windows, returning the error from the console if enabling failed so the reason can be reported.
+pub fn try_enable_ansi_support() -> io::Result<()> {
     if cfg!(windows) {
-        match enable_windows_ansi_support() {
-            Ok(()) => true,
-            Err(_) => false,
-        }
+        enable_windows_ansi_support()
     } else {
-        // On non-Windows systems, no need to enable ANSI, so we return `true`.
-        true
+        // On non-Windows systems, no need to enable ANSI, so there is nothing to fail.
+        Ok(())
     }
 }
 
+/// Formats the warning explaining colors were disabled because ANSI support couldn't be enabled, left unstyled since escape sequences wouldn't be rendered.
+pub fn format_ansi_warning(error: &io::Error) -> String {
+    format!("Warning: colors were disabled because the Windows console could not enable virtual terminal processing ({error})")
+}
+
 #[cfg(not(windows))]
 /// Dummy implementation for non-Windows platforms, required for compilation bounds checks.
 fn enable_windows_ansi_support() -> io::Result<()> {
@@ -76,9 +107,12 @@ pub struct RippySchema {
     pub sym: Option<&'static str>,
     pub detail: Option<&'static str>,
     pub search: Option<&'static str>,
-    pub window: Option<&'static str>,
+    pub window: Option<String>, // Owned so it can be overridden with `--match-color`
     pub muted: Option<&'static str>,
     pub zero: Option<&'static str>,
+    pub added: Option<&'static str>,
+    pub removed: Option<&'static str>,
+    pub changed: Option<&'static str>,
 }
 
 impl RippySchema {
@@ -93,9 +127,12 @@ impl RippySchema {
                 sym: NONE_COLOR,
                 detail: NONE_COLOR,
                 search: NONE_COLOR,
-                window: NONE_COLOR,
+                window: None,
                 muted: NONE_COLOR,
                 zero: NONE_COLOR,
+                added: NONE_COLOR,
+                removed: NONE_COLOR,
+                changed: NONE_COLOR,
             }
         } else {
             RippySchema {
@@ -106,14 +143,78 @@ impl RippySchema {
                 sym: SYM_COLOR,
                 detail: DETAILS_COLOR,
                 search: SEARCH_COLOR,
-                window: MATCHES_COLOR,
+                window: MATCHES_COLOR.map(str::to_string),
                 muted: DETAILS_COLOR,
                 zero: ZERO_COLOR,
+                added: ADDED_COLOR,
+                removed: REMOVED_COLOR,
+                changed: CHANGED_COLOR,
             }
         }
     }
 }
 
+/// Returns the default color for files with the provided lowercase extension grouped by category, or `None` if the extension has no default and the base file color should be used.
+pub fn default_ext_color(ext: &str) -> Option<&'static str> {
+    match ext {
+        "rs" | "py" | "js" | "ts" | "jsx" | "tsx" | "go" | "c" | "h" | "cpp" | "hpp" | "cc" | "java" | "kt" | "rb" | "php" | "swift" | "cs" | "lua" | "zig" => SOURCE_COLOR,
+        "toml" | "yaml" | "yml" | "json" | "ini" | "cfg" | "conf" | "lock" | "xml" | "env" => CONFIG_COLOR,
+        "md" | "txt" | "rst" | "pdf" | "doc" | "docx" | "org" | "tex" => DOCS_COLOR,
+        "png" | "jpg" | "jpeg" | "gif" | "svg" | "bmp" | "ico" | "webp" | "tiff" => IMAGE_COLOR,
+        "zip" | "tar" | "gz" | "tgz" | "bz2" | "xz" | "7z" | "rar" | "zst" => ARCHIVE_COLOR,
+        _ => NONE_COLOR,
+    }
+}
+
+/// Counts the characters of the text that are displayed, skipping ANSI escape sequences such as colors so styled text can be aligned by its visible width.
+pub fn visible_width(text: &str) -> usize {
+    let mut width = 0;
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            width += 1;
+        } else if chars.next() == Some('[') {
+            // Control sequences run until a final character in the range `@` to `~`
+            chars.by_ref().find(|c| ('@'..='~').contains(c));
+        }
+    }
+    width
+}
+
+/// Names the color set by an ANSI escape code the way it is given to the color options, as the 256 color code or hex value, with the basic colors named.
+pub fn color_name(code: &str) -> String {
+    const BASIC_NAMES: [&str; 16] = ["black", "red", "green", "yellow", "blue", "magenta", "cyan", "white", "bright black", "bright red", "bright green", "bright yellow", "bright blue", "bright magenta", "bright cyan", "bright white"];
+    let params: Vec<&str> = code.trim_start_matches("\x1b[").trim_end_matches('m').split(';').collect();
+    match params.as_slice() {
+        ["38", "5", index] => index.parse::<usize>().ok().and_then(|i| BASIC_NAMES.get(i)).map_or_else(|| format!("color {index}"), |name| name.to_string()),
+        ["38", "2", channels @ ..] => channels.iter().fold("#".to_string(), |hex, channel| format!("{hex}{:02x}", channel.parse::<u8>().unwrap_or_default())),
+        _ => code.escape_debug().to_string(),
+    }
+}
+
+/// Returns the color for the size in bytes on a gradient from green for small sizes to red for sizes of a gigabyte or more.
+pub fn size_heat_color(size: u64) -> Option<&'static str> {
+    match size {
+        0..1_000 => HEAT_TINY_COLOR,
+        1_000..1_000_000 => HEAT_SMALL_COLOR,
+        1_000_000..100_000_000 => HEAT_MEDIUM_COLOR,
+        100_000_000..1_000_000_000 => HEAT_LARGE_COLOR,
+        _ => HEAT_HUGE_COLOR,
+    }
+}
+
+/// Returns the color for an entry last modified the number of seconds ago on a gradient from bright within the day to dim beyond a year.
+pub fn age_color(age_secs: f64) -> Option<&'static str> {
+    const DAY: f64 = 86_400.0;
+    match age_secs {
+        age if age < DAY => AGE_DAY_COLOR,
+        age if age < 7.0 * DAY => AGE_WEEK_COLOR,
+        age if age < 30.0 * DAY => AGE_MONTH_COLOR,
+        age if age < 365.0 * DAY => AGE_YEAR_COLOR,
+        _ => AGE_OLD_COLOR,
+    }
+}
+
 #[macro_export]
 /// Formats and returns a String with the provided ANSI terminal styling commands using an optional keyword argument for bold.
 macro_rules! ansi_color {
@@ -1,4 +1,5 @@
 // #![allow(unused)]
+use std::collections::HashMap;
 use std::hash::BuildHasherDefault;
 use std::fmt;
 use std::fs;
@@ -8,9 +9,10 @@ use std::io::{self, Write, stdout};
 use std::path::PathBuf;
 use std::time::{UNIX_EPOCH, Duration};
 
-use crate::args::RippyArgs;
+use crate::args::{ColorScale, JsonPaths, RippyArgs};
 use crate::{ansi_color, concat_str};
-use crate::crawl::TreeLeaf;
+use crate::crawl::{MountUsage, TreeLeaf, WindowMatch};
+use crate::tcolor::{age_color, size_heat_color, visible_width};
 
 use chrono;
 use is_executable::is_executable;
@@ -28,10 +30,64 @@ const MB:f64 = 1_000_000.0;
 const GB:f64 = 1_000_000_000.0;
 
 /// Global left margin for entire single space tree offset. 
-const MARGIN_LEFT: &'static str = "\u{0020}";
+const MARGIN_LEFT: &str = "\u{0020}";
 
 /// Non-breaking single space for output com­pat­i­bil­i­ty with UNIX `tree` command
-const NB_SINGLE: &'static str = "\u{00A0}";
+const NB_SINGLE: &str = "\u{00A0}";
+
+/// Fewest sibling files named alike apart from a number that are grouped into a single entry.
+pub const MIN_SIMILAR_GROUP: usize = 3;
+
+/// Byte order mark written at the start of exported files when requested.
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// Characters used to draw the connectors and guides of the tree.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TreeGlyphs {
+    pub tee: &'static str,
+    pub elbow: &'static str,
+    pub pipe: &'static str,
+    pub dash: &'static str,
+}
+
+/// Default box-drawing characters used to render the tree.
+pub const UNICODE_GLYPHS: TreeGlyphs = TreeGlyphs { tee: "├", elbow: "╰", pipe: "│", dash: "─" };
+
+/// Plain ASCII characters used to render the tree where UTF-8 may be mangled.
+pub const ASCII_GLYPHS: TreeGlyphs = TreeGlyphs { tee: "|", elbow: "`", pipe: "|", dash: "-" };
+
+/// Separators used when rendering numbers such as sizes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NumberFormat {
+    pub thousands: char,
+    pub decimal: char,
+}
+
+/// Default separators rendering numbers as `1,234.5`.
+pub const US_NUMBER_FORMAT: NumberFormat = NumberFormat { thousands: ',', decimal: '.' };
+
+impl NumberFormat {
+    /// Formats the number with the decimal places provided, grouping the digits of the integer part by thousands.
+    pub fn format(&self, number: f64, decimals: usize) -> String {
+        let formatted = format!("{:.*}", decimals, number.abs());
+        let (integer, fraction) = formatted.split_once('.').map_or((formatted.as_str(), None), |(integer, fraction)| (integer, Some(fraction)));
+        let mut grouped = String::with_capacity(formatted.len() + integer.len() / 3 + 1);
+        if number < 0.0 {
+            grouped.push('-');
+        }
+        for (i, digit) in integer.chars().enumerate() {
+            if i > 0 && (integer.len() - i) % 3 == 0 {
+                grouped.push(self.thousands);
+            }
+            grouped.push(digit);
+        }
+        if let Some(fraction) = fraction {
+            grouped.push(self.decimal);
+            grouped.push_str(fraction);
+        }
+        grouped
+    }
+}
 
 /// Enum to differentiate between Directory and File type objects in Tree struct.
 #[derive(Debug, Clone, PartialEq, Eq, Copy, Serialize, Deserialize, PartialOrd, Ord)] // Derive Serialize and Deserialize
@@ -64,6 +120,76 @@ impl<'a> Iterator for TreeIter<'a> {
         }
     }
 }
+/// Number and total size of the files sharing an extension, as listed by `--summary-by-ext`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtSummary {
+    pub extension: String,
+    pub files: usize,
+    pub size: u64,
+}
+
+/// Entry flattened into text fields for the line based exports, so CSV and NDJSON list the same path, type, size, date and matched window for every entry.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct FlatRecord {
+    pub path: String,
+    #[serde(rename = "type")]
+    pub entry_type: String,
+    pub size: String,
+    pub date: String,
+    pub matched: String,
+}
+impl FlatRecord {
+    /// Column names written as the first row of the CSV export.
+    pub const CSV_HEADER: &'static str = "path,type,size,date,matched";
+
+    /// Creates the record from the fields of an entry, leaving the size, date and matched window empty where missing.
+    pub fn new(path: String, entry_type: EntryType, last_modified: Option<f64>, size: Option<u64>, window: &Option<String>, settings: &RippyArgs) -> Self {
+        FlatRecord {
+            path: to_native_separators(path, settings),
+            entry_type: entry_type.to_string(),
+            size: size.map(|size| size.to_string()).unwrap_or_default(),
+            date: format_json_datetime(stable_timestamp(last_modified, settings)).unwrap_or_default(),
+            matched: format_json_window(window).unwrap_or_default(),
+        }
+    }
+    /// Formats the record as a CSV row, quoting any field containing a comma, quote or line break.
+    pub fn to_csv_row(&self) -> String {
+        let quote = |field: &str| if field.contains([',', '"', '\n', '\r']) { concat_str!("\"", field.replace('"', "\"\""), "\"") } else { field.to_string() };
+        [&self.path, &self.entry_type, &self.size, &self.date, &self.matched].map(|field| quote(field)).join(",")
+    }
+}
+
+/// Progress of the directory size pass as the share of the crawled entries processed, written to stderr on a single line that is only rewritten when the percentage changes.
+#[derive(Debug, Default)]
+pub struct SizeProgress {
+    pub processed: usize,
+    pub total: usize,
+    percent: Option<usize>,
+}
+impl SizeProgress {
+    /// Creates the progress for a size pass over the number of entries provided, typically those found by the crawl.
+    pub fn new(total: usize) -> Self {
+        SizeProgress { processed: 0, total, percent: None }
+    }
+    /// Marks another entry as processed, rewriting the percentage if it changed. Entries beyond the total are capped at 100%.
+    pub fn tick(&mut self) {
+        self.processed += 1;
+        let percent = (self.processed * 100 / self.total.max(1)).min(100);
+        if self.percent != Some(percent) {
+            self.percent = Some(percent);
+            eprint!("\rCalculating sizes... {percent:>3}%");
+            let _ = io::stderr().flush();
+        }
+    }
+    /// Clears the progress line if anything was written so the results start on a clean line.
+    pub fn finish(&mut self) {
+        if self.percent.take().is_some() {
+            eprint!("\r{}\r", " ".repeat("Calculating sizes... 100%".len()));
+            let _ = io::stderr().flush();
+        }
+    }
+}
+
 /// Primary struct for tree module, providing methods for core functionality.
 #[derive(Clone, Serialize, Deserialize, PartialEq)] // Derive Serialize and Deserialize
 pub struct Tree {
@@ -74,23 +200,102 @@ pub struct Tree {
     pub last_modified: Option<f64>,
     pub size: Option<u64>,
     pub window: Option<String>,
+    #[serde(default)]
+    pub window_match: Option<WindowMatch>,
+    #[serde(default)]
+    pub truncated: Option<usize>, // Number of files left out of the directory by `--max-files`
+    #[serde(default)]
+    pub diff: Option<DiffStatus>, // Whether the entry was added, removed or changed since the snapshot given to `--diff`
+    #[serde(default)]
+    pub fuzzy_score: Option<i64>, // How closely the name matches the query given to `--fuzzy`, the best of its files for a directory
     pub fmt_width: Option<usize>,
     pub children: TreeMap,
 }
 
+/// Status of an entry compared against the snapshot given to `--diff`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DiffStatus {
+    Added,
+    Removed,
+    Changed,
+}
+impl DiffStatus {
+    /// Marker written before the entry name so the status reads the same without color.
+    pub fn marker(&self) -> &'static str {
+        match self {
+            DiffStatus::Added => "+ ",
+            DiffStatus::Removed => "- ",
+            DiffStatus::Changed => "~ ",
+        }
+    }
+}
+
+/// Tree previously exported as JSON with `--output`, compared against with `--diff` to mark the entries added, removed or changed since.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snapshot {
+    pub name: String,
+    pub entry_type: EntryType,
+    pub last_modified: Option<String>,
+    pub size: Option<u64>,
+    pub children: IndexMap<String, Snapshot>,
+}
+impl Snapshot {
+    /// Reads the snapshot from a JSON export, either a document holding the tree under `tree` or a bare tree written with `--bare-json`.
+    pub fn read(file_path: impl AsRef<path::Path>) -> Result<Self, String> {
+        let contents = fs::read_to_string(file_path).map_err(|e| e.to_string())?;
+        let json: serde_json::Value = serde_json::from_str(&contents).map_err(|e| e.to_string())?;
+        let root = if json.get("schema").is_some() { &json["tree"] } else { &json };
+        Snapshot::from_json(root).ok_or_else(|| "expected a tree exported as JSON with --output".to_string())
+    }
+    fn from_json(json: &serde_json::Value) -> Option<Self> {
+        let entry_type = match json.get("entry_type")?.as_str()? {
+            "Directory" => EntryType::Directory,
+            "File" => EntryType::File,
+            _ => return None,
+        };
+        let children = match json.get("children").and_then(|children| children.as_array()) {
+            Some(children) => children.iter().map(Snapshot::from_json).collect::<Option<Vec<Snapshot>>>()?,
+            None => Vec::new(),
+        };
+        Some(Snapshot {
+            name: json.get("name")?.as_str()?.to_string(),
+            entry_type,
+            last_modified: json.get("last_modified").and_then(|date| date.as_str()).map(str::to_string),
+            size: json.get("size").and_then(|size| size.as_u64()),
+            children: children.into_iter().map(|child| (child.name.clone(), child)).collect(),
+        })
+    }
+    /// Checks whether the entry differs from the snapshot by type, or for files by any size or date recorded in both.
+    fn is_changed(&self, tree: &Tree, settings: &RippyArgs) -> bool {
+        let last_modified = format_json_datetime(stable_timestamp(tree.last_modified, settings));
+        self.entry_type != tree.entry_type || (tree.entry_type == EntryType::File && (
+            self.size.zip(tree.size).is_some_and(|(previous, current)| previous != current)
+            || self.last_modified.as_ref().zip(last_modified).is_some_and(|(previous, current)| *previous != current)
+        ))
+    }
+    /// Converts the entry and everything below it into entries marked as removed.
+    fn to_removed(&self) -> Tree {
+        let mut tree = Tree::new(self.name.as_str(), self.name.as_str(), None, self.entry_type, None, self.size, None, None);
+        tree.diff = Some(DiffStatus::Removed);
+        tree.children = self.children.values().map(|child| (child.name.clone(), child.to_removed())).collect();
+        tree
+    }
+}
+
 impl From<TreeLeaf> for Tree {
     /// Converts a TreeLeaf into a Tree by consuming the original and avoiding redundant or unnecessary allocations during the processs.
     fn from(value: TreeLeaf) -> Self {
-        let (entry_type, path, fmt_width, window) = if value.is_dir {
-            (EntryType::Directory, None, None, None)
+        let (entry_type, path, fmt_width, window, window_match) = if value.is_dir {
+            (EntryType::Directory, None, None, None, None)
         } else {
-            (EntryType::File, if !value.is_sym { Some(PathBuf::from(value.relative_path)) } else { None }, None, value.window)
+            (EntryType::File, if !value.is_sym { Some(PathBuf::from(value.relative_path)) } else { None }, None, value.window, value.window_match)
         };
-        Tree::new(value.display, value.name, path, entry_type, value.last_modified, value.size, fmt_width, window)
+        Tree { window_match, ..Tree::new(value.display, value.name, path, entry_type, value.last_modified, value.size, fmt_width, window) }
     }
 }
 impl Tree {
     /// Creates a new tree using a root path and TreeMap for children nodes
+    #[allow(clippy::too_many_arguments)]
     pub fn new(display: impl Into<String>, name: impl Into<String>, path: Option<PathBuf>, entry_type: EntryType, last_modified: Option<f64>, size: Option<u64>, fmt_width: Option<usize>, window: Option<String>) -> Self {
         Tree {
             display: display.into(),
@@ -101,54 +306,167 @@ impl Tree {
             size,
             fmt_width,
             window,
+            window_match: None,
+            truncated: None,
+            diff: None,
+            fuzzy_score: None,
             children: TreeMap::default(),
         }
     }
     /// Creates a new `Tree` given a path explicitely for creating missing `Directory` components. Assumes path given is already standardized to contain forward slashes only.
     pub fn from_dir(path: std::path::PathBuf, args: &RippyArgs) -> Self {
         let name = path.file_name().map_or_else(|| path.to_string_lossy().to_string(), |p| p.to_string_lossy().to_string());
-        let display = if args.show_relative_path {
+        let display = if let Some(base) = &args.relative_to {
+            convert_path_relative_to(path.to_string_lossy().as_ref(), base)
+        } else if args.show_relative_path {
             path.to_string_lossy().to_string()
         } else if args.show_full_path {
-            convert_relative_to_abs_path(&path.to_string_lossy().to_string())
+            convert_relative_to_abs_path(path.to_string_lossy().as_ref())
         } else {
             name.clone()
         };
+        let display = truncate_name(to_native_separators(display, args), args);
         let display = if args.is_quote { concat_str!("\"", display, "\"") } else { display };        
         let entry_type = EntryType::Directory;
-        let (last_modified, size) = if args.show_size || args.show_date {
+        let (last_modified, size) = if args.show_size || args.is_stat_date {
             let metadata = fs::metadata(&path).ok();
-            let last_modified = if args.show_date { convert_metadata_to_f64(&metadata) } else { None };
+            let last_modified = if args.is_stat_date { convert_metadata_to_f64(&metadata) } else { None };
             let size = if args.show_size { metadata.as_ref().map(|meta| meta.len()) } else { None };
             (last_modified, size)
         } else {
             (None, None)
         };
         let (fmt_width, window, children) = (None, None, TreeMap::default());
-        Tree { display, name, path: None, entry_type, last_modified, size, fmt_width, window, children }
+        Tree { display, name, path: None, entry_type, last_modified, size, fmt_width, window, window_match: None, truncated: None, diff: None, fuzzy_score: None, children }
     }
-    /// Recursively calculates the size of directories based on their children
+    /// Recursively calculates the size of directories based on their children, only counting content that was crawled.
     pub fn calculate_sizes(&mut self) {
+        self.calculate_sizes_to_depth(usize::MAX, 0);
+    }
+    /// Recursively calculates the size of directories based on their children, keeping the size already measured for directories at the maximum depth since their content was not crawled.
+    pub fn calculate_sizes_to_depth(&mut self, max_depth: usize, depth: usize) {
+        self.sum_sizes(max_depth, depth, None);
+    }
+    /// Calculates the size of directories like `calculate_sizes_to_depth` from the root, reporting the share of entries processed to stderr and clearing it once done.
+    pub fn calculate_sizes_with_progress(&mut self, max_depth: usize, progress: &mut SizeProgress) {
+        self.sum_sizes(max_depth, 0, Some(progress));
+        progress.finish();
+    }
+    fn sum_sizes(&mut self, max_depth: usize, depth: usize, mut progress: Option<&mut SizeProgress>) {
         if self.entry_type == EntryType::Directory {
+            if depth >= max_depth {
+                return;
+            }
             let mut total_size = 0;
 
             for child in self.children.values_mut() {
-                child.calculate_sizes();
+                child.sum_sizes(max_depth, depth + 1, progress.as_deref_mut());
                 if let Some(size) = child.size {
                     total_size += size;
                 }
+                if let Some(progress) = progress.as_deref_mut() {
+                    progress.tick();
+                }
             }
             self.size = Some(total_size);
         }
     }
-    /// Calculates the max file name length for all the files in a single directory and assigns that value to the self.fmt_width property for the directory and its children.
+    /// Collects the distinct lowercase extensions of the files beneath this entry, with files without an extension collected as `(none)`.
+    pub fn distinct_extensions(&self) -> std::collections::HashSet<String> {
+        let mut extensions = std::collections::HashSet::new();
+        let mut stack: Vec<&Tree> = vec![self];
+        while let Some(tree) = stack.pop() {
+            if tree.entry_type == EntryType::File {
+                let ext = path::Path::new(&tree.name).extension().map_or_else(|| "(none)".to_string(), |ext| ext.to_string_lossy().to_lowercase());
+                extensions.insert(ext);
+            }
+            stack.extend(tree.children.values());
+        }
+        extensions
+    }
+    /// Totals the number and size of the files beneath this entry for each distinct lowercase extension, with files without an extension collected as `(none)`. Ordered by extension.
+    pub fn extension_summary(&self) -> Vec<ExtSummary> {
+        let mut extensions: std::collections::BTreeMap<String, ExtSummary> = std::collections::BTreeMap::new();
+        let mut stack: Vec<&Tree> = vec![self];
+        while let Some(tree) = stack.pop() {
+            if tree.entry_type == EntryType::File {
+                let ext = path::Path::new(&tree.name).extension().map_or_else(|| "(none)".to_string(), |ext| concat_str!(".", ext.to_string_lossy().to_lowercase()));
+                let summary = extensions.entry(ext.clone()).or_insert_with(|| ExtSummary { extension: ext, files: 0, size: 0 });
+                summary.files += 1;
+                summary.size += tree.size.unwrap_or_default();
+            }
+            stack.extend(tree.children.values());
+        }
+        extensions.into_values().collect()
+    }
+    /// Counts the entries at each depth below this one, keyed by depth starting at 1 for its immediate children.
+    pub fn depth_counts(&self) -> std::collections::BTreeMap<usize, usize> {
+        let mut depths = std::collections::BTreeMap::new();
+        let mut stack: Vec<(&Tree, usize)> = vec![(self, 0)];
+        while let Some((tree, depth)) = stack.pop() {
+            for child in tree.children.values() {
+                *depths.entry(depth + 1).or_insert(0) += 1;
+                stack.push((child, depth + 1));
+            }
+        }
+        depths
+    }
+    /// Counts the directories and files in the tree, counting the directory this is called on only if `include_root` is true. A file is always counted even if it is the root.
+    pub fn counts(&self, include_root: bool) -> TreeCounts {
+        let mut counts = TreeCounts::new();
+        let mut stack: Vec<(&Tree, bool)> = vec![(self, include_root)];
+        while let Some((tree, is_counted)) = stack.pop() {
+            match tree.entry_type {
+                EntryType::Directory => if is_counted { counts.dir_count += 1 },
+                EntryType::File => counts.file_count += 1,
+            }
+            stack.extend(tree.children.values().map(|child| (child, true)));
+        }
+        counts
+    }
+    /// Collects the paths and sizes of the largest directories below this one, largest first with ties ordered by path, expecting the sizes to have already been calculated.
+    pub fn largest_dirs(&self, limit: usize) -> Vec<(String, u64)> {
+        let mut dirs: Vec<(String, u64)> = Vec::new();
+        let mut stack: Vec<(&Tree, String)> = vec![(self, self.name.clone())];
+        while let Some((tree, tree_path)) = stack.pop() {
+            for child in tree.children.values().filter(|child| child.entry_type == EntryType::Directory) {
+                let child_path = concat_str!(tree_path, "/", child.name);
+                dirs.push((child_path.clone(), child.size.unwrap_or_default()));
+                stack.push((child, child_path));
+            }
+        }
+        let by_size = |a: &(String, u64), b: &(String, u64)| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0));
+        // Only the directories kept need to be in order
+        if limit < dirs.len() {
+            dirs.select_nth_unstable_by(limit, by_size);
+            dirs.truncate(limit);
+        }
+        dirs.sort_unstable_by(by_size);
+        dirs
+    }
+    /// Collects the paths of the directories directly holding at least one of the files kept, this one included, each listed once in order of path.
+    pub fn matching_dirs(&self) -> Vec<String> {
+        let mut dirs: Vec<String> = Vec::new();
+        let mut stack: Vec<(&Tree, String)> = vec![(self, self.name.clone())];
+        while let Some((tree, tree_path)) = stack.pop() {
+            if tree.children.values().any(|child| child.entry_type == EntryType::File) {
+                dirs.push(tree_path.clone());
+            }
+            for child in tree.children.values().filter(|child| child.entry_type == EntryType::Directory) {
+                stack.push((child, concat_str!(tree_path.trim_end_matches('/'), "/", child.name)));
+            }
+        }
+        dirs.sort_unstable();
+        dirs
+    }
+    /// Calculates the max visible file name width for all the files in a single directory and assigns that value to the self.fmt_width property for the directory and its children.
     pub fn calculate_fmt_width(&mut self) {
         if self.entry_type == EntryType::Directory {
             let mut max_length = 0;
 
-            // Find the max file name length in the current directory
+            // Find the max file name width in the current directory, measured without any styling like that of symlinks
             for child in self.children.values() {
-                let name_length = child.display.len();
+                let name_length = visible_width(&child.display);
                 if name_length > max_length {
                     max_length = name_length;
                 }
@@ -157,7 +475,7 @@ impl Tree {
             // Set fmt_width for all children in the current directory
             for child in self.children.values_mut() {
                 if child.entry_type == EntryType::File {
-                    child.fmt_width = Some(max_length as usize);
+                    child.fmt_width = Some(max_length);
                 } else if child.entry_type == EntryType::Directory {
                     // Recursively calculate and set fmt_width for the child directory
                     child.calculate_fmt_width();
@@ -168,7 +486,9 @@ impl Tree {
     /// LEGACY: Recursively prints the tree structure tied to the `Tree` instance directly as an uncolored legacy version compatible with `tree` output.
     /// For example, using a valid object of type `Tree`, call with:
     /// 
-    ///     tree.print_legacy("", 0, true);
+    /// ```ignore
+    /// tree.print_legacy("", 0, true);
+    /// ```
     /// 
     /// Which will render output that can be diff'd against the unix tree command:
     /// 
@@ -207,72 +527,466 @@ impl Tree {
         let child_count = self.children.len().saturating_sub(1);
 
         for (i, child) in self.children.values_mut().enumerate() {
-            child.print_legacy(&new_indent, depth + 1, i == child_count);
+            child.print_legacy(new_indent, depth + 1, i == child_count);
         }
 
         // Newline when tree is finished
         if depth == 0 {
-            println!("");
+            println!();
         }
     }
-    /// Converts the Tree structure to JSON and writes it to a file
-    pub fn write_to_json_file(&self, settings: &RippyArgs) -> std::io::Result<()> {
-        // Harmonize into expected generic type
-        let file_path = &settings.output;
-
-        // Use a closure to capture `settings`
-        let convert_children = |children: &TreeMap| {
-            children.values().map(|child| child.to_json(settings)).collect::<Vec<serde_json::Value>>()
-        };
-
-        // Construct the json
-        let json_value = json!({
-            "name": self.name,
-            "entry_type": self.entry_type.to_string(),
-            "last_modified": format_json_datetime(self.last_modified),
-            "size": self.size,
-            "window": format_json_window(&self.window),
-            "children": convert_children(&self.children),
+    /// Writes the Tree structure to the file provided using the format inferred from its extension, with `.yaml`, `.md`, `.html`, `.dot`, `.csv` and `.ndjson` supported and JSON used otherwise.
+    pub fn write_to_file(&self, file_path: impl AsRef<path::Path>, settings: &RippyArgs) -> std::io::Result<()> {
+        self.write_to_file_with_elapsed(file_path, settings, None)
+    }
+    /// Writes the Tree structure to the file provided like `write_to_file`, recording the time elapsed so far in the header of JSON documents if provided.
+    pub fn write_to_file_with_elapsed(&self, file_path: impl AsRef<path::Path>, settings: &RippyArgs, elapsed: Option<Duration>) -> std::io::Result<()> {
+        let file_path = file_path.as_ref();
+        // Chains of single child directories are merged for a shallower export and files beyond the maximum are left out, without changing the tree rendered
+        let prepared = (settings.is_flatten_single_child || settings.max_files != usize::MAX).then(|| {
+            let mut prepared = self.clone();
+            if settings.is_flatten_single_child {
+                prepared.collapse_chains(settings);
+            }
+            prepared.truncate_files(settings);
+            prepared
         });
-
+        let tree = prepared.as_ref().unwrap_or(self);
+        let extension = file_path.extension().map(|ext| ext.to_string_lossy().to_lowercase()).unwrap_or_default();
+        let contents = match extension.as_str() {
+            "yaml" | "yml" => tree.to_yaml(settings),
+            "md" | "markdown" => tree.to_markdown(settings),
+            "html" | "htm" => tree.to_html(settings),
+            "dot" | "gv" => tree.to_dot(settings),
+            "csv" => tree.to_csv(settings),
+            "ndjson" | "jsonl" => tree.to_ndjson(settings),
+            _ => return tree.write_json(file_path, settings, elapsed),
+        };
+        let bom: &[u8] = if settings.is_bom { UTF8_BOM } else { &[] };
+        fs::write(file_path, [bom, contents.as_bytes()].concat())
+    }
+    /// Converts the Tree structure to JSON and writes it to a file
+    pub fn write_to_json_file(&self, file_path: impl AsRef<path::Path>, settings: &RippyArgs) -> std::io::Result<()> {
+        self.write_json(file_path, settings, None)
+    }
+    fn write_json(&self, file_path: impl AsRef<path::Path>, settings: &RippyArgs, elapsed: Option<Duration>) -> std::io::Result<()> {
         // Open the file and wrap it in BufWriter for efficient writing
         let file = std::fs::File::create(file_path)?;
-        let buf_wrtier = io::BufWriter::new(file);
+        let mut buf_wrtier = io::BufWriter::new(file);
+        if settings.is_bom {
+            buf_wrtier.write_all(UTF8_BOM)?;
+        }
 
-        serde_json::to_writer_pretty(buf_wrtier, &json_value)?;
+        // Directories without any matches are only scaffolding and can be left out of exported search results
+        let pruned = (settings.is_json_matches_only && settings.is_search).then(|| {
+            let mut matches_only = self.clone();
+            matches_only.prune_to_matches();
+            matches_only
+        });
+        let tree = pruned.as_ref().unwrap_or(self);
+        let json = if settings.is_bare_json { tree.to_json(settings) } else { tree.to_json_document_with_elapsed(settings, elapsed) };
+        if settings.json_indent == 0 {
+            serde_json::to_writer(buf_wrtier, &json)?;
+        } else {
+            let indent = " ".repeat(settings.json_indent);
+            let mut serializer = serde_json::Serializer::with_formatter(buf_wrtier, serde_json::ser::PrettyFormatter::with_indent(indent.as_bytes()));
+            json.serialize(&mut serializer)?;
+        }
 
         Ok(())
     }
+    /// Converts the Tree structure to YAML using the same fields as the JSON export, relying on JSON strings also being valid YAML double-quoted scalars.
+    pub fn to_yaml(&self, settings: &RippyArgs) -> String {
+        let mut yaml = String::new();
+        write_yaml_value(&self.to_json(settings), 0, &mut yaml);
+        yaml
+    }
+    /// Converts the Tree structure to a nested Markdown list headed by the root name, marking directories with a trailing slash.
+    pub fn to_markdown(&self, settings: &RippyArgs) -> String {
+        let mut markdown = concat_str!("# ", self.name, "\n\n");
+        self.write_markdown_children(0, settings, &mut markdown);
+        markdown
+    }
+    fn write_markdown_children(&self, depth: usize, settings: &RippyArgs, markdown: &mut String) {
+        let mut children: Vec<&Tree> = self.children.values().collect();
+        let sort_by = self.child_order(settings);
+        children.sort_by(|a, b| sort_by(a, b));
+        for child in children {
+            let suffix = if child.entry_type == EntryType::Directory { "/" } else { "" };
+            markdown.push_str(&concat_str!("  ".repeat(depth), "- ", child.name, suffix));
+            // The match is set apart in bold between the code spans on either side so the highlight is kept
+            if let Some(window_match) = &child.window_match {
+                let code_span = |text: &str| if text.is_empty() { "".to_string() } else { concat_str!("`", text.replace('`', "'"), "`") };
+                let (before, matched, after) = window_match.split();
+                markdown.push_str(&concat_str!(": ", code_span(before), "**", code_span(matched), "**", code_span(after)));
+            } else if let Some(window) = format_json_window(&child.window).filter(|w| !w.is_empty()) {
+                markdown.push_str(&concat_str!(": `", window.replace('`', "'"), "`"));
+            }
+            markdown.push('\n');
+            child.write_markdown_children(depth + 1, settings, markdown);
+        }
+    }
+    /// Converts the Tree structure to an HTML document of nested lists headed by the root name, marking directories with a trailing slash and the match within each search window with `<mark>`.
+    pub fn to_html(&self, settings: &RippyArgs) -> String {
+        let title = escape_html(&self.name);
+        let mut html = concat_str!("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>", title, "</title>\n</head>\n<body>\n<h1>", title, "</h1>\n");
+        self.write_html_children(settings, &mut html);
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+    fn write_html_children(&self, settings: &RippyArgs, html: &mut String) {
+        if self.children.is_empty() {
+            return;
+        }
+        let mut children: Vec<&Tree> = self.children.values().collect();
+        let sort_by = self.child_order(settings);
+        children.sort_by(|a, b| sort_by(a, b));
+        html.push_str("<ul>\n");
+        for child in children {
+            let suffix = if child.entry_type == EntryType::Directory { "/" } else { "" };
+            html.push_str(&concat_str!("<li>", escape_html(&child.name), suffix));
+            if let Some(window_match) = &child.window_match {
+                let (before, matched, after) = window_match.split();
+                html.push_str(&concat_str!(": <code>", escape_html(before), "<mark>", escape_html(matched), "</mark>", escape_html(after), "</code>"));
+            } else if let Some(window) = format_json_window(&child.window).filter(|w| !w.is_empty()) {
+                html.push_str(&concat_str!(": <code>", escape_html(&window), "</code>"));
+            }
+            if !child.children.is_empty() {
+                html.push('\n');
+                child.write_html_children(settings, html);
+            }
+            html.push_str("</li>\n");
+        }
+        html.push_str("</ul>\n");
+    }
+    /// Marks the entries added or changed since the snapshot and adds back those removed as entries marked so, comparing the children of each directory by name.
+    pub fn diff_against(&mut self, snapshot: &Snapshot, settings: &RippyArgs) {
+        for child in self.children.values_mut() {
+            match snapshot.children.get(&child.name) {
+                Some(previous) => {
+                    if previous.is_changed(child, settings) {
+                        child.diff = Some(DiffStatus::Changed);
+                    }
+                    child.diff_against(previous, settings);
+                },
+                None => child.mark_added(),
+            }
+        }
+        for previous in snapshot.children.values() {
+            if !self.children.contains_key(&previous.name) {
+                self.children.insert(previous.name.clone(), previous.to_removed());
+            }
+        }
+    }
+    fn mark_added(&mut self) {
+        self.diff = Some(DiffStatus::Added);
+        self.children.values_mut().for_each(Tree::mark_added);
+    }
+    /// Sorts the children of every directory and leaves out the files beyond `--max-files`, recording how many were left out of each in `truncated`.
+    pub fn truncate_files(&mut self, settings: &RippyArgs) {
+        truncate_children(self, settings);
+        for child in self.children.values_mut() {
+            child.truncate_files(settings);
+        }
+    }
+    /// Flattens the entry into a record of text fields, using the path provided since directories don't keep their own.
+    pub fn to_record(&self, path: impl Into<String>, settings: &RippyArgs) -> FlatRecord {
+        FlatRecord::new(path.into(), self.entry_type, self.last_modified, self.size, &self.window, settings)
+    }
+    /// Flattens every entry below the root into records in display order, with paths joined from the root name.
+    pub fn to_records(&self, settings: &RippyArgs) -> Vec<FlatRecord> {
+        let mut records = Vec::new();
+        self.push_child_records(&self.name, settings, &mut records);
+        records
+    }
+    fn push_child_records(&self, parent: &str, settings: &RippyArgs, records: &mut Vec<FlatRecord>) {
+        let mut children: Vec<&Tree> = self.children.values().collect();
+        let sort_by = self.child_order(settings);
+        children.sort_by(|a, b| sort_by(a, b));
+        for child in children {
+            let path = concat_str!(parent.trim_end_matches('/'), "/", child.name);
+            records.push(child.to_record(path.as_str(), settings));
+            child.push_child_records(&path, settings, records);
+        }
+    }
+    /// Converts the Tree structure to CSV with a header row and one row per entry.
+    pub fn to_csv(&self, settings: &RippyArgs) -> String {
+        let mut csv = concat_str!(FlatRecord::CSV_HEADER, "\n");
+        for record in self.to_records(settings) {
+            csv.push_str(&concat_str!(record.to_csv_row(), "\n"));
+        }
+        csv
+    }
+    /// Converts the Tree structure to newline delimited JSON with one object per entry.
+    pub fn to_ndjson(&self, settings: &RippyArgs) -> String {
+        self.to_records(settings).iter().map(|record| concat_str!(json!(record).to_string(), "\n")).collect()
+    }
+    /// Converts the Tree structure to a Graphviz DOT digraph with an edge from each directory to its children.
+    pub fn to_dot(&self, settings: &RippyArgs) -> String {
+        let mut dot = String::from("digraph rippy {\n  node [shape=box];\n");
+        let mut next_id: usize = 0;
+        self.write_dot_node(&mut next_id, settings, &mut dot);
+        dot.push_str("}\n");
+        dot
+    }
+    fn write_dot_node(&self, next_id: &mut usize, settings: &RippyArgs, dot: &mut String) -> usize {
+        let id = *next_id;
+        *next_id += 1;
+        let shape = if self.entry_type == EntryType::Directory { "folder" } else { "note" };
+        let label = self.name.replace('\\', "\\\\").replace('"', "\\\"");
+        dot.push_str(&format!("  n{id} [label=\"{label}\", shape={shape}];\n"));
+        let mut children: Vec<&Tree> = self.children.values().collect();
+        let sort_by = self.child_order(settings);
+        children.sort_by(|a, b| sort_by(a, b));
+        for child in children {
+            let child_id = child.write_dot_node(next_id, settings, dot);
+            dot.push_str(&format!("  n{id} -> n{child_id};\n"));
+        }
+        id
+    }
 
     /// Converts the Tree structure to JSON Value
     pub fn to_json(&self, settings: &RippyArgs) -> serde_json::Value {
+        let root_path = self.path.as_deref().map_or_else(|| self.name.clone(), normalize_path);
+        self.to_json_at(&root_path, &root_path, settings)
+    }
+    fn to_json_at(&self, path: &str, root_path: &str, settings: &RippyArgs) -> serde_json::Value {
         let convert_children = |children: &TreeMap| {
-            children.values().map(|child| child.to_json(settings)).collect::<Vec<serde_json::Value>>()
+            children.values().map(|child| child.to_json_at(&concat_str!(path.trim_end_matches('/'), "/", child.name), root_path, settings)).collect::<Vec<serde_json::Value>>()
         };
-        json!({
+        let mut json = json!({
             "name": self.name,
             "entry_type": self.entry_type.to_string(),
-            "last_modified": format_json_datetime(self.last_modified),
+            "last_modified": format_json_datetime(stable_timestamp(self.last_modified, settings)),
             "size": self.size,
             "window": format_json_window(&self.window),
             "children": convert_children(&self.children),
-        })
+        });
+        // Paths are only included when requested, in the form requested and following the name
+        if let (Some(json_paths), Some(object)) = (&settings.json_paths, json.as_object_mut()) {
+            object.shift_insert(1, "path".to_string(), json!(to_native_separators(format_json_path(path, root_path, json_paths), settings)));
+        }
+        // Only directories with files left out by `--max-files` note how many
+        if let Some(truncated) = self.truncated {
+            json["truncated"] = json!(truncated);
+        }
+        json
+    }
+    /// Wraps the JSON of the tree in a versioned document with a header describing when and how it was generated, so consumers can check the `schema` before reading the nested `tree`.
+    pub fn to_json_document(&self, settings: &RippyArgs) -> serde_json::Value {
+        self.to_json_document_with_elapsed(settings, None)
+    }
+    /// Wraps the JSON of the tree in a versioned document like `to_json_document`, recording the number of entries exported and the seconds elapsed if provided as `elapsed_secs`.
+    pub fn to_json_document_with_elapsed(&self, settings: &RippyArgs, elapsed: Option<Duration>) -> serde_json::Value {
+        let generated = if settings.is_deterministic {
+            chrono::DateTime::from_timestamp(DETERMINISTIC_TIMESTAMP as i64, 0).unwrap_or_default()
+        } else {
+            chrono::Utc::now()
+        };
+        let patterns = |set: &Option<regex::RegexSet>| set.as_ref().map(|set| set.patterns().to_vec());
+        let counts = self.counts(settings.is_count_root);
+        let mut document = json!({
+            "schema": JSON_SCHEMA_VERSION,
+            "root": to_native_separators(normalize_path(&settings.directory), settings),
+            "generated": generated.to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            "args": {
+                "pattern": settings.pattern_text,
+                "include": patterns(&settings.include_patterns),
+                "ignore": patterns(&settings.ignore_patterns),
+                "max_depth": if settings.max_depth == usize::MAX { None } else { Some(settings.max_depth) },
+                "all": settings.include_all,
+                "gitignore": settings.is_gitignore,
+                "follow_links": settings.is_follow_links,
+                "size": settings.show_size,
+                "date": settings.show_date,
+            },
+            "counts": {
+                "directories": counts.dir_count,
+                "files": counts.file_count,
+            },
+            "tree": self.to_json(settings),
+        });
+        // Elapsed time is only known when timed, so the field is left out otherwise
+        if let (Some(elapsed), Some(object)) = (elapsed, document.as_object_mut()) {
+            object.shift_insert(3, "elapsed_secs".to_string(), json!(elapsed.as_secs_f64()));
+        }
+        document
     }
     /// Tree for root with specific considerations for rendering and pathing traversal to facilitate construction and building. Expected display field assigned to name for both name and relative path option, using full path when canonical argument is present.
-    pub fn new_root(root:&std::path::PathBuf, args: &RippyArgs) -> Self {
+    pub fn new_root(root: &std::path::Path, args: &RippyArgs) -> Self {
         // No distinction is made between show_relative_path or not for root of tree, only if full path needed is relevant as root name will be used for building/traversal
         let root_name = if !args.show_full_path {
             root.to_string_lossy().to_string()
         } else {
-            convert_relative_to_abs_path(&root.to_string_lossy().to_string())
+            convert_relative_to_abs_path(root.to_string_lossy().as_ref())
         };
         let name = root_name.clone();
+        // Only the displayed root is made absolute, the name is still needed to build and traverse the tree
+        let root_name = if args.is_show_root_abs && !args.show_full_path { convert_relative_to_abs_path(&root_name) } else { root_name };
+        let root_name = to_native_separators(root_name, args);
         let root_name = if args.is_quote { concat_str!("\"", root_name, "\"") } else { root_name };
         // Create root of tree from directory provided in initial args and a relative path with "/" suffix that can be used for traversal and component building.
         Tree::new( root_name, name, None, EntryType::Directory, None, None, None, None )
     }
+    /// Retains only the files whose names fuzzy match the query, highlighting the matched characters and dropping any directories left without children. Each entry kept holds its score, directories taking the best of their files, so they can be ranked with `--sort score`. Returns `true` if the entry should be kept by its parent.
+    pub fn retain_fuzzy(&mut self, query: &str, args: &RippyArgs) -> bool {
+        match self.entry_type {
+            EntryType::File => match fuzzy_match(query, &self.name) {
+                Some((score, indices)) => {
+                    if !args.is_grayscale && self.display.ends_with(&self.name) {
+                        let prefix = &self.display[..self.display.len() - self.name.len()];
+                        self.display = concat_str!(prefix, highlight_chars(&self.name, &indices, args));
+                    }
+                    self.fuzzy_score = Some(score);
+                    true
+                },
+                None => false,
+            },
+            EntryType::Directory => {
+                self.children.retain(|_, child| child.retain_fuzzy(query, args));
+                self.fuzzy_score = self.children.values().filter_map(|child| child.fuzzy_score).max();
+                !self.children.is_empty()
+            },
+        }
+    }
+    /// Removes directories containing fewer immediate entries than the minimum provided unless they lead to a directory that is kept, so kept directories retain their ancestors for context. Returns `true` if the entry should be kept by its parent.
+    pub fn retain_min_children(&mut self, min_children: usize) -> bool {
+        match self.entry_type {
+            EntryType::File => true,
+            EntryType::Directory => {
+                let has_enough_children = self.children.len() >= min_children;
+                let mut has_kept_dir = false;
+                self.children.retain(|_, child| {
+                    let is_kept = child.retain_min_children(min_children);
+                    has_kept_dir |= is_kept && child.entry_type == EntryType::Directory;
+                    is_kept
+                });
+                has_enough_children || has_kept_dir
+            },
+        }
+    }
+    /// Sorts the children at every level of the tree using the comparator provided, so exports and renders walking the children in order share the same ordering.
+    pub fn sort_recursive(&mut self, sort_by: fn(&Tree, &Tree) -> std::cmp::Ordering) {
+        self.children.sort_by(|_, a, _, b| sort_by(a, b));
+        for child in self.children.values_mut() {
+            child.sort_recursive(sort_by);
+        }
+    }
+    /// Sorts the children at every level of the tree using the ordering from the arguments, with `--reverse-levels` deciding the ordering of directories holding only files apart from the rest.
+    pub fn sort_levels(&mut self, args: &RippyArgs) {
+        let sort_by = self.child_order(args);
+        self.children.sort_by(|_, a, _, b| sort_by(a, b));
+        for child in self.children.values_mut() {
+            child.sort_levels(args);
+        }
+    }
+    /// Provides the comparator for the children of this entry, using the leaf ordering when it holds only files.
+    pub fn child_order(&self, args: &RippyArgs) -> fn(&Tree, &Tree) -> std::cmp::Ordering {
+        if self.children.values().all(|child| child.entry_type == EntryType::File) {
+            args.sort_leaves_by
+        } else {
+            args.sort_by
+        }
+    }
+    /// Removes files without a search window and directories left without any matching descendants, so only the matches and the directories leading to them remain. The entry this is called on is kept even if nothing matched.
+    pub fn prune_to_matches(&mut self) {
+        self.children.retain(|_, child| match child.entry_type {
+            EntryType::File => child.window.is_some(),
+            EntryType::Directory => {
+                child.prune_to_matches();
+                !child.children.is_empty()
+            },
+        });
+    }
+    /// Removes directories without any files beneath them, leaving the entry this is called on even if nothing remains.
+    pub fn prune_empty_dirs(&mut self) {
+        self.children.retain(|_, child| match child.entry_type {
+            EntryType::File => true,
+            EntryType::Directory => {
+                child.prune_empty_dirs();
+                !child.children.is_empty()
+            },
+        });
+    }
+    /// Removes zero-byte files throughout the tree, leaving directories in place even if they end up empty.
+    pub fn retain_non_empty_files(&mut self) {
+        self.children.retain(|_, child| child.entry_type == EntryType::Directory || child.size != Some(0));
+        for child in self.children.values_mut() {
+            child.retain_non_empty_files();
+        }
+    }
+    /// Merges chains of directories whose only child is another directory into a single entry displayed as `a/b/c`, leaving the root and directories containing files as is. Returns the number of directories merged away so counts can remain accurate.
+    pub fn collapse_chains(&mut self, args: &RippyArgs) -> usize {
+        let mut merged = 0;
+        let separator = if args.is_native_separators { path::MAIN_SEPARATOR_STR } else { "/" };
+        for child in self.children.values_mut() {
+            if child.entry_type != EntryType::Directory {
+                continue;
+            }
+            while child.children.len() == 1 && child.children[0].entry_type == EntryType::Directory {
+                let Some((_, grandchild)) = child.children.pop() else { break };
+                child.display = if args.show_relative_path || args.show_full_path {
+                    grandchild.display
+                } else if args.is_quote {
+                    concat_str!(child.display.trim_end_matches('"'), separator, grandchild.display.trim_start_matches('"'))
+                } else {
+                    concat_str!(child.display, separator, grandchild.display)
+                };
+                child.name = concat_str!(child.name, "/", grandchild.name);
+                child.last_modified = grandchild.last_modified;
+                child.size = grandchild.size;
+                child.children = grandchild.children;
+                merged += 1;
+            }
+            merged += child.collapse_chains(args);
+        }
+        merged
+    }
+    /// Replaces runs of at least `MIN_SIMILAR_GROUP` sibling files named alike apart from their last number with a single summary entry displayed as `frame####.png (1200 files)`, totalling their sizes. Entries with search results are left as is. Returns the number of files merged away so counts can remain accurate.
+    pub fn group_similar(&mut self) -> usize {
+        let mut grouped = 0;
+        let is_groupable = |child: &Tree| child.entry_type == EntryType::File && child.window.is_none();
+        let mut group_sizes: HashMap<String, usize> = HashMap::new();
+        for child in self.children.values().filter(|child| is_groupable(child)) {
+            if let Some(pattern) = numeric_pattern(&child.name) {
+                *group_sizes.entry(pattern).or_default() += 1;
+            }
+        }
+        if group_sizes.values().any(|&count| count >= MIN_SIMILAR_GROUP) {
+            let children = std::mem::take(&mut self.children);
+            for (key, child) in children {
+                let pattern = if is_groupable(&child) { numeric_pattern(&child.name) } else { None };
+                let Some(pattern) = pattern.filter(|pattern| group_sizes[pattern] >= MIN_SIMILAR_GROUP) else {
+                    self.children.insert(key, child);
+                    continue;
+                };
+                match self.children.get_mut(&pattern) {
+                    Some(summary) => {
+                        summary.size = summary.size.zip(child.size).map(|(total, size)| total + size);
+                        summary.last_modified = summary.last_modified.into_iter().chain(child.last_modified).reduce(f64::max);
+                        grouped += 1;
+                    },
+                    None => {
+                        let display = concat_str!(numeric_pattern(&child.display).unwrap_or_else(|| pattern.clone()), " (", group_sizes[&pattern].to_string(), " files)");
+                        let summary = Tree::new(display, pattern.clone(), None, EntryType::File, child.last_modified, child.size, None, None);
+                        self.children.insert(pattern, summary);
+                    },
+                }
+            }
+        }
+        for child in self.children.values_mut().filter(|child| child.entry_type == EntryType::Directory) {
+            grouped += child.group_similar();
+        }
+        grouped
+    }
+    /// Finds the entry at the path relative to this one by following the names of its components, returning itself for an empty path.
+    pub fn find_by_path(&self, path: &str) -> Option<&Tree> {
+        path.split(['/', '\\'])
+            .filter(|component| !component.is_empty() && *component != ".")
+            .try_fold(self, |node, component| node.children.get(component))
+    }
     /// Implements a depth-first iterator for `Tree` to traverse the children elements matching the same pre-sorted order as rippy.
-    pub fn iter(&self) -> TreeIter {
+    pub fn iter(&self) -> TreeIter<'_> {
         TreeIter { stack: vec![self] }
     }  
 }
@@ -289,7 +1003,7 @@ impl fmt::Display for Tree {
     /// Display the directory structure and any children of directory entries
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(f, "Name: {} <{:?}>", self.display, self.entry_type)?;
-        if self.children.len() >= 1 {
+        if !self.children.is_empty() {
             writeln!(f, "Children:")?;
             for (name, child) in &self.children {
                 writeln!(f, "  {} <{:?}>", name, child.entry_type)?;
@@ -300,7 +1014,7 @@ impl fmt::Display for Tree {
 }
 
 /// Tracks resulting file and directory counts for summary outputs.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Default)]
 pub struct TreeCounts {
     pub dir_count: usize,
     pub file_count: usize
@@ -324,6 +1038,17 @@ fn convert_metadata_to_f64(metadata: &Option<fs::Metadata>) -> Option<f64> {
         .map(|duration| duration.as_secs_f64()) // Convert the duration to f64
 }
 
+/// Version of the JSON document written by `Tree::to_json_document`, incremented whenever its shape changes.
+pub const JSON_SCHEMA_VERSION: u32 = 1;
+
+/// Fixed timestamp used in place of every last modified time when output should be deterministic, the unix epoch.
+const DETERMINISTIC_TIMESTAMP: f64 = 0.0;
+
+/// Replaces the last modified time with a fixed value when output should be deterministic, leaving missing times as is.
+fn stable_timestamp(last_modified: Option<f64>, settings: &RippyArgs) -> Option<f64> {
+    if settings.is_deterministic { last_modified.map(|_| DETERMINISTIC_TIMESTAMP) } else { last_modified }
+}
+
 /// Formats the seconds since unix epoch as a ISO-8601 tz naive timestamp regardless of settings specifically for JSON export.
 fn format_json_datetime(last_modified: Option<f64>) -> Option<String> {
     let dt_format = "%Y-%m-%d %H:%M:%S";
@@ -335,15 +1060,19 @@ fn format_json_datetime(last_modified: Option<f64>) -> Option<String> {
         })
     }
 
-/// Formats the seconds since unix epoch as a human readable timestamp based on the provided settings and EntryType.
+/// Formats the seconds since unix epoch as a human readable timestamp based on the provided settings and EntryType, limited to a single type if `--file-date` or `--dir-date` is present.
 fn format_display_datetime(last_modified: Option<f64>, settings: &RippyArgs, entry_type: EntryType) -> String {
     if settings.show_date {
-        if !settings.is_dir_detail && entry_type == EntryType::Directory {
+        let is_type_shown = match entry_type {
+            EntryType::Directory => settings.is_dir_date || (!settings.is_file_date && settings.is_dir_detail),
+            EntryType::File => settings.is_file_date || !settings.is_dir_date,
+        };
+        if !is_type_shown {
             return "".to_string();
         }
         // let dt_format = if settings.is_short_date {"%Y-%m-%d"} else {"%Y-%m-%d %H:%M:%S"}; // "%Y-%m-%d %H:%M:%S" for [2024-07-24 15:09:57] or "%d-%b-%y" for [12-Jul-24]
         let dt_format = &settings.date_format;
-        last_modified.map(|timestamp| {
+        stable_timestamp(last_modified, settings).map(|timestamp| {
             // Convert f64 to Duration
             let duration_since_epoch = Duration::from_secs_f64(timestamp);
             let datetime = chrono::DateTime::from_timestamp(duration_since_epoch.as_secs() as i64, duration_since_epoch.subsec_nanos()).unwrap_or_default();
@@ -354,45 +1083,113 @@ fn format_display_datetime(last_modified: Option<f64>, settings: &RippyArgs, ent
     }
 }
 
+/// Recursively writes the JSON value as block style YAML, with scalars and empty collections written inline.
+fn write_yaml_value(value: &serde_json::Value, depth: usize, yaml: &mut String) {
+    let indent = "  ".repeat(depth);
+    match value {
+        serde_json::Value::Object(map) if !map.is_empty() => {
+            for (key, child) in map {
+                yaml.push_str(&concat_str!(indent, key, ":"));
+                write_yaml_child(child, depth, yaml);
+            }
+        },
+        serde_json::Value::Array(items) if !items.is_empty() => {
+            for item in items {
+                yaml.push_str(&concat_str!(indent, "-"));
+                match item {
+                    // Nested mappings start on the same line as the sequence dash
+                    serde_json::Value::Object(map) if !map.is_empty() => {
+                        let mut nested = String::new();
+                        write_yaml_value(item, depth + 1, &mut nested);
+                        yaml.push(' ');
+                        yaml.push_str(nested.trim_start());
+                    },
+                    _ => write_yaml_child(item, depth, yaml),
+                }
+            }
+        },
+        _ => {
+            yaml.push_str(&concat_str!(indent, yaml_scalar(value), "\n"));
+        },
+    }
+}
+
+/// Writes the value following a key or sequence dash, either inline for scalars or on the following lines for non-empty collections.
+fn write_yaml_child(value: &serde_json::Value, depth: usize, yaml: &mut String) {
+    match value {
+        serde_json::Value::Object(map) if !map.is_empty() => {
+            yaml.push('\n');
+            write_yaml_value(value, depth + 1, yaml);
+        },
+        serde_json::Value::Array(items) if !items.is_empty() => {
+            yaml.push('\n');
+            write_yaml_value(value, depth + 1, yaml);
+        },
+        _ => yaml.push_str(&concat_str!(" ", yaml_scalar(value), "\n")),
+    }
+}
+
+/// Formats a scalar or empty collection as an inline YAML value.
+fn yaml_scalar(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Null => "null".to_string(),
+        serde_json::Value::Array(_) => "[]".to_string(),
+        serde_json::Value::Object(_) => "{}".to_string(),
+        // Numbers and booleans are identical and JSON strings are valid double-quoted YAML
+        other => other.to_string(),
+    }
+}
+
+/// Escapes the characters with special meaning in HTML text and attributes.
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// Formats the path of an entry for JSON export relative to the root, relative to a base directory or as an absolute path, with the root itself relative to the root written as `.`.
+fn format_json_path(path: &str, root_path: &str, json_paths: &JsonPaths) -> String {
+    match json_paths {
+        JsonPaths::Relative => {
+            let root = normalize_path_lexically(&path::absolute(root_path).unwrap_or_else(|_| PathBuf::from(root_path)));
+            Some(convert_path_relative_to(path, &root)).filter(|relative| !relative.is_empty()).unwrap_or_else(|| ".".to_string())
+        },
+        JsonPaths::Base(base) => Some(convert_path_relative_to(path, base)).filter(|relative| !relative.is_empty()).unwrap_or_else(|| ".".to_string()),
+        JsonPaths::Absolute => normalize_path(&normalize_path_lexically(&path::absolute(path).unwrap_or_else(|_| PathBuf::from(path)))),
+    }
+}
+
 /// Formats the window context for JSON export by removing all ANSI control and command sequences that may have been used for displaying the results in the tree
 fn format_json_window(input: &Option<String>) -> Option<String> {
     let ansi_escape = Regex::new(r"\x1b\[[0-9;]*[a-zA-Z]").unwrap();
-    input.as_deref().map(|x| ansi_escape.replace_all(&x, "").to_string())
+    input.as_deref().map(|x| ansi_escape.replace_all(x, "").to_string())
 }
 
-/// Formats size according to scale using appropriate units to fit within fixed width to retain alignment when included in display. 
-fn format_size(size:u64) -> String {
+/// Formats size according to scale using appropriate units to fit within fixed width to retain alignment when included in display, using the separators provided.
+fn format_size(size: u64, number_format: &NumberFormat) -> String {
     // Convert size to f64
     let size = size as f64;
 
-    if size < KB {
+    let (size_in_unit, unit) = if size < KB {
         // No conversion, already in bytes
-        let size_in_unit = size;
-        let size_as_str = if size_in_unit < 10.0 {format!("{:.1}", size_in_unit)} else {format!("{:.0}", size_in_unit)};
-        concat_str!(format!("{:>3.3}", size_as_str), " B")
+        (size, " B")
     } else if size < MB {
         // Convert to kilobytes
-        let size_in_unit = size / KB;
-        let size_as_str = if size_in_unit < 10.0 {format!("{:.1}", size_in_unit)} else {format!("{:.0}", size_in_unit)};
-        concat_str!(format!("{:>3.3}", size_as_str), " K")
+        (size / KB, " K")
     } else if size < GB {
         // Convert to megabytes
-        let size_in_unit = size / MB;
-        let size_as_str = if size_in_unit < 10.0 {format!("{:.1}", size_in_unit)} else {format!("{:.0}", size_in_unit)};
-        concat_str!(format!("{:>3.3}", size_as_str), " M")
+        (size / MB, " M")
     } else {
         // Convert to gigabytes
-        let size_in_unit = size / GB;
-        let size_as_str = if size_in_unit < 10.0 {format!("{:.1}", size_in_unit)} else {format!("{:.0}", size_in_unit)};
-        concat_str!(format!("{:>3.3}", size_as_str), " G")
-    }
+        (size / GB, " G")
+    };
+    let size_as_str = number_format.format(size_in_unit, if size_in_unit < 10.0 { 1 } else { 0 });
+    concat_str!(format!("{:>3}", size_as_str), unit)
 }
 
 /// Formats the display size based on the provided settings and entry type
 fn format_display_size(size: Option<u64>, settings: &RippyArgs, entry_type: EntryType) -> String {
     if settings.show_size {
         if settings.is_dir_detail || entry_type == EntryType::File {
-            size.map_or(String::new(), |s| format_size(s))
+            size.map_or(String::new(), |size| format_size(size, &settings.number_format))
         } else {
             "".to_string()
         }
@@ -401,6 +1198,49 @@ fn format_display_size(size: Option<u64>, settings: &RippyArgs, entry_type: Entr
     }
 }
 
+/// Formats the total size of the tree and the root separated by a tab similar to `du -sh`, expecting the sizes to have already been calculated.
+pub fn format_du(tree: &Tree, args: &RippyArgs) -> String {
+    let size = format_size(tree.size.unwrap_or_default(), &args.number_format);
+    concat_str!(ansi_color!(&args.colors.detail, bold=false, size.trim_start()), "\t", ansi_color!(&args.colors.root, bold=!args.is_no_root_bold, tree.display))
+}
+
+/// Formats the largest directories as a list of sizes aligned beside their paths.
+pub fn format_top_dirs(dirs: &[(String, u64)], args: &RippyArgs) -> String {
+    dirs.iter()
+        .map(|(dir_path, size)| concat_str!(MARGIN_LEFT, ansi_color!(&args.colors.detail, bold=false, format_size(*size, &args.number_format)), "  ", ansi_color!(&args.colors.dir, bold=!args.is_no_bold, to_native_separators(dir_path.to_owned(), args))))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Formats the usage of each file system as a list of sizes and entry counts aligned beside their mount points, falling back to the device id when the mount point is unknown.
+pub fn format_mount_summary(mounts: &[MountUsage], args: &RippyArgs) -> String {
+    mounts.iter()
+        .map(|mount| {
+            let entries = concat_str!(mount.entries.to_string(), if mount.entries == 1 { " entry" } else { " entries" });
+            let name = match &mount.mount_point {
+                Some(mount_point) => ansi_color!(&args.colors.dir, bold=!args.is_no_bold, to_native_separators(mount_point.to_owned(), args)),
+                None => ansi_color!(&args.colors.muted, bold=false, concat_str!("device ", mount.device.to_string())),
+            };
+            concat_str!(MARGIN_LEFT, ansi_color!(&args.colors.detail, bold=false, format_size(mount.size, &args.number_format)), "  ", format!("{entries:>12}"), "  ", name)
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Formats the breakdown by extension as one line per extension with its file count and total size aligned, sorted in the order requested.
+pub fn format_ext_summary(extensions: &mut [ExtSummary], args: &RippyArgs) -> String {
+    extensions.sort_by(args.summary_sort.compare());
+    let width = extensions.iter().map(|summary| summary.extension.chars().count()).max().unwrap_or(0);
+    let count_width = extensions.iter().map(|summary| summary.files.to_string().len()).max().unwrap_or(0);
+    extensions.iter()
+        .map(|summary| {
+            let files = concat_str!(format!("{:>count_width$}", summary.files), if summary.files == 1 { " file " } else { " files" });
+            concat_str!(MARGIN_LEFT, format!("{:<width$}", summary.extension), "  ", ansi_color!(&args.colors.file, bold=false, files), "  ", ansi_color!(&args.colors.detail, bold=false, format_size(summary.size, &args.number_format)))
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
 pub fn _tree_peek(paths: &Vec<(String, Option<String>)>) {
         for (path, _window) in paths {
             println!("{}", path);
@@ -409,13 +1249,85 @@ pub fn _tree_peek(paths: &Vec<(String, Option<String>)>) {
 
 /// Converts relative path to full canonical path replacing any backslashes with forward slashes to display with `Tree` results if needed.
 fn convert_relative_to_abs_path(relative_path: &str) -> String {
-    path::absolute(path::Path::new(relative_path)).map_or(relative_path.to_owned(), |path| path.to_string_lossy().replace("\\","/"))
+    path::absolute(path::Path::new(relative_path)).map_or(relative_path.to_owned(), |path| normalize_path(&path))
+}
+
+/// Converts the path to a string standardized to forward slashes, only replacing backslashes on Windows where they are separators so names that contain them elsewhere are left intact.
+pub fn normalize_path(path: &path::Path) -> String {
+    let path = path.to_string_lossy();
+    if cfg!(windows) {
+        path.replace('\\', "/")
+    } else {
+        path.into_owned()
+    }
+}
+
+/// Replaces the last run of digits in the name with a `#` for each digit, returning `None` if the name has no digits.
+fn numeric_pattern(name: &str) -> Option<String> {
+    let end = name.rfind(|c: char| c.is_ascii_digit())? + 1;
+    let start = name[..end].rfind(|c: char| !c.is_ascii_digit()).map_or(0, |i| i + 1);
+    Some(concat_str!(&name[..start], "#".repeat(end - start), &name[end..]))
+}
+
+/// Truncates the name to the maximum number of characters requested, appending an ellipsis if any were removed, written as `...` for ASCII output.
+pub fn truncate_name(name: String, args: &RippyArgs) -> String {
+    match args.max_name_length {
+        Some(max_length) if name.chars().count() > max_length => {
+            let truncated: String = name.chars().take(max_length).collect();
+            concat_str!(truncated, if args.is_ascii { "..." } else { "…" })
+        },
+        _ => name,
+    }
+}
+
+/// Swaps the forward slashes paths are standardized to for the separator native to the platform if requested, leaving the path as is otherwise or where the native separator is already a forward slash.
+pub fn to_native_separators(path: String, args: &RippyArgs) -> String {
+    if args.is_native_separators && path::MAIN_SEPARATOR != '/' {
+        path.replace('/', path::MAIN_SEPARATOR_STR)
+    } else {
+        path
+    }
+}
+
+/// Resolves `.` and `..` components of the path without touching the filesystem so paths can be compared by prefix.
+pub fn normalize_path_lexically(path: &path::Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            path::Component::CurDir => {},
+            path::Component::ParentDir => if !normalized.pop() { normalized.push("..") },
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+/// Converts the path to be relative to the base provided, falling back to the full canonical path when the base is not one of its ancestors. Expects the base to already be absolute and normalized.
+pub fn convert_path_relative_to(path: &str, base: &path::Path) -> String {
+    let full_path = normalize_path_lexically(&path::absolute(path::Path::new(path)).unwrap_or_else(|_| PathBuf::from(path)));
+    match full_path.strip_prefix(base) {
+        Ok(relative) => normalize_path(relative),
+        Err(_) => normalize_path(&full_path),
+    }
 }
 
 /// Optimized version to build the `Tree` structure given an owned set of `TreeLeafs` to iteratively build from.
 pub fn build_tree_from_paths(paths: Vec<TreeLeaf>, args: &'static RippyArgs) -> Tree {
     // Create root of tree from directory provided in initial args
-    let mut root_tree = Tree::new_root(&args.directory, &args);
+    let mut root_tree = Tree::new_root(&args.directory, args);
+
+    // A file given as the root is the whole tree, keeping any details crawled for it
+    if args.is_file_root {
+        root_tree.entry_type = EntryType::File;
+        root_tree.path = Some(args.directory.clone());
+        if let Some(leaf) = paths.into_iter().next() {
+            root_tree.last_modified = leaf.last_modified;
+            root_tree.size = leaf.size;
+            root_tree.window = leaf.window;
+            root_tree.window_match = leaf.window_match;
+        }
+        return root_tree;
+    }
 
     let root_path = args.directory.to_string_lossy().to_string();
     let root_path_length = root_path.len();
@@ -448,7 +1360,7 @@ pub fn build_tree_from_paths(paths: Vec<TreeLeaf>, args: &'static RippyArgs) ->
                 let entry = current_dir.children.entry(parent.to_string());
                 current_dir = entry.or_insert_with(|| {
                     let current_path_state = concat_str!(root_standard_path, &leaf_components[0..=pid].join("/"));
-                    Tree::from_dir(std::path::PathBuf::from(current_path_state), &args)
+                    Tree::from_dir(std::path::PathBuf::from(current_path_state), args)
                 });
             }
             // Insert the leaf
@@ -459,6 +1371,48 @@ pub fn build_tree_from_paths(paths: Vec<TreeLeaf>, args: &'static RippyArgs) ->
     root_tree
 }
 
+/// Case insensitively matches the query as a subsequence of the candidate, returning a score favoring consecutive and word boundary matches along with the char indices matched, or `None` if not every query character was found in order.
+pub fn fuzzy_match(query: &str, candidate: &str) -> Option<(i64, Vec<usize>)> {
+    let mut query_chars = query.chars().flat_map(char::to_lowercase).peekable();
+    let mut indices: Vec<usize> = Vec::with_capacity(query.len());
+    let mut score: i64 = 0;
+    let mut prev: Option<char> = None;
+    for (i, c) in candidate.chars().enumerate() {
+        let Some(&target) = query_chars.peek() else { break };
+        if c.to_lowercase().eq(std::iter::once(target)) {
+            score += 1;
+            // Reward runs of consecutive matches and matches starting a new word
+            if indices.last().is_some_and(|&last| last + 1 == i) {
+                score += 5;
+            }
+            if prev.is_none_or(|p| !p.is_alphanumeric()) {
+                score += 3;
+            }
+            indices.push(i);
+            query_chars.next();
+        }
+        prev = Some(c);
+    }
+    if query_chars.peek().is_some() {
+        return None;
+    }
+    // Shorter candidates rank higher for the same set of matches
+    Some((score - candidate.chars().count() as i64 / 4, indices))
+}
+
+/// Wraps each char at the provided indices with the match styling while leaving the rest of the text as is.
+fn highlight_chars(text: &str, indices: &[usize], args: &RippyArgs) -> String {
+    let mut highlighted = String::with_capacity(text.len() + indices.len() * 16);
+    for (i, c) in text.chars().enumerate() {
+        if indices.contains(&i) {
+            highlighted.push_str(&ansi_color!(StyleKind::Match.color(args), bold=StyleKind::Match.is_bold(args), c.to_string()));
+        } else {
+            highlighted.push(c);
+        }
+    }
+    highlighted
+}
+
 /// Returns the number of digits in the provided value using a more performant log based approach.
 fn count_digits_log(n: usize) -> usize {
     if n == 0 {
@@ -468,8 +1422,173 @@ fn count_digits_log(n: usize) -> usize {
     ((n as f64).log(10.0).floor() as usize) + 1
 }
 
+/// Determines if the path should be styled as an executable, checking the extensions provided by `--exec-ext` first. On Windows the extension list replaces the default detection when provided since executability there is extension based anyway.
+fn is_exec_path(path: &path::Path, args: &RippyArgs) -> bool {
+    if !args.exec_extensions.is_empty() {
+        let is_listed = path.extension().is_some_and(|ext| args.exec_extensions.iter().any(|e| ext.eq_ignore_ascii_case(e)));
+        if is_listed || cfg!(windows) {
+            return is_listed;
+        }
+    }
+    is_executable(path)
+}
+
+/// Returns the color for the file name based on its extension using any overrides provided before the default palette, or `None` if extension colors are disabled or unknown.
+fn ext_color(name: &str, args: &RippyArgs) -> Option<&'static str> {
+    if !args.is_ext_colors {
+        return None;
+    }
+    let ext = path::Path::new(name).extension()?.to_string_lossy().to_lowercase();
+    match args.ext_colors.iter().rev().find(|(e, _)| *e == ext) {
+        Some((_, color)) => Some(*color),
+        None => crate::tcolor::default_ext_color(&ext),
+    }
+}
+
+/// Kind of entry given its own styling, shared by the tree and the `--legend` key so the two always agree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StyleKind {
+    Directory,
+    File,
+    Executable,
+    Symlink,
+    Match,
+}
+impl StyleKind {
+    /// Every kind in the order listed by the legend.
+    pub const ALL: [StyleKind; 5] = [StyleKind::Directory, StyleKind::File, StyleKind::Executable, StyleKind::Symlink, StyleKind::Match];
+    /// Returns the color the kind is drawn with.
+    pub fn color<'a>(&self, args: &'a RippyArgs) -> Option<&'a str> {
+        match self {
+            StyleKind::Directory => args.colors.dir,
+            StyleKind::File => args.colors.file,
+            StyleKind::Executable => args.colors.exec,
+            StyleKind::Symlink => args.colors.sym,
+            StyleKind::Match => args.colors.window.as_deref(),
+        }
+    }
+    /// Returns whether the kind is drawn in bold.
+    pub fn is_bold(&self, args: &RippyArgs) -> bool {
+        match self {
+            StyleKind::Directory | StyleKind::Match => !args.is_no_bold,
+            StyleKind::File | StyleKind::Executable | StyleKind::Symlink => false,
+        }
+    }
+    /// Name of the kind as written in the legend.
+    pub fn label(&self) -> &'static str {
+        match self {
+            StyleKind::Directory => "directory",
+            StyleKind::File => "file",
+            StyleKind::Executable => "executable",
+            StyleKind::Symlink => "symlink",
+            StyleKind::Match => "match",
+        }
+    }
+}
+
+/// Returns the color and bold styling to use for an entry's name based on its type and whether or not it's executable.
+fn entry_style(tree: &Tree, args: &RippyArgs) -> (Option<&'static str>, bool) {
+    match tree.entry_type {
+        EntryType::Directory => (args.colors.dir, StyleKind::Directory.is_bold(args)),
+        // Don't worry about color if its grayscale or if the path is None or then finally if the path is not executable
+        EntryType::File => if args.is_grayscale || tree.path.is_none() {
+            (None, false)
+        } else if let Some(last_modified) = tree.last_modified.filter(|_| args.color_scale == Some(ColorScale::Date)) {
+            let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).map_or(0_f64, |duration| duration.as_secs_f64());
+            (age_color(now - last_modified), false)
+        } else if is_flagged_empty(tree, args) {
+            (args.colors.muted, false)
+        } else if tree.path.as_ref().is_none_or(|p| !is_exec_path(p, args)) {
+            (ext_color(&tree.name, args).or(args.colors.file), StyleKind::File.is_bold(args))
+        } else {
+            (args.colors.exec, StyleKind::Executable.is_bold(args))
+        },
+    }
+}
+
+/// Returns the color of the status of an entry compared against the snapshot given to `--diff`.
+fn diff_color(status: DiffStatus, args: &RippyArgs) -> Option<&'static str> {
+    match status {
+        DiffStatus::Added => args.colors.added,
+        DiffStatus::Removed => args.colors.removed,
+        DiffStatus::Changed => args.colors.changed,
+    }
+}
+
+/// Determines if the entry is a zero-byte file that should be marked as empty.
+fn is_flagged_empty(tree: &Tree, args: &RippyArgs) -> bool {
+    args.is_flag_empty && tree.entry_type == EntryType::File && tree.size == Some(0)
+}
+
+/// Sorts the children of the tree according to args and truncates any files beyond `args.max_files`, counting the files truncated as if they had been written.
+fn sort_and_truncate_children(tree: &mut Tree, args: &RippyArgs, counts: &mut TreeCounts) {
+    truncate_children(tree, args);
+    counts.file_count += tree.truncated.unwrap_or(0);
+}
+
+/// Sorts the children of the tree according to args and removes any files beyond `args.max_files`, recording how many were removed on the tree rather than as an entry.
+fn truncate_children(tree: &mut Tree, args: &RippyArgs) {
+    // Collect children into a single vector and sort according to args
+    let sort_by = tree.child_order(args);
+    tree.children.sort_by(|_, a, _, b| sort_by(a, b));
+
+    // Determine the count of files for truncation
+    let total_files = tree.children.values().filter(|c| c.entry_type == EntryType::File).count();
+
+    // Truncate the list if necessary
+    if total_files > args.max_files {
+        let mut files_seen = 0;
+        tree.children.retain(|_, child| {
+            if child.entry_type == EntryType::File {
+                if files_seen < args.max_files {
+                    files_seen += 1;
+                    true
+                } else {
+                    false
+                }
+            } else {
+                true
+            }
+        });
+
+        tree.truncated = Some(total_files - files_seen);
+    }
+}
+
+/// Formats the label written in place of the files truncated from a directory as `N more ...`.
+fn format_truncation_label(truncated: usize, args: &RippyArgs) -> String {
+    ansi_color!(&args.colors.detail, bold=false, concat_str!(truncated.to_string(), " more ..."))
+}
+
+/// Writes the graphical terminal representation of the tree to the writer provided, such as a file, socket or buffer, adding the entries written to the counts.
+///
+/// ```
+/// use std::sync::LazyLock;
+/// use rippy::args::RippyArgs;
+/// use rippy::crawl::TreeLeaf;
+/// use rippy::tree::{self, TreeCounts};
+///
+/// static ARGS: LazyLock<RippyArgs> = LazyLock::new(RippyArgs::default);
+/// let leaf = TreeLeaf::new("notes.txt", "./notes.txt", false, None, None, None, "notes.txt", false);
+/// let mut tree = tree::build_tree_from_paths(vec![leaf], &ARGS);
+/// let mut counts = TreeCounts::new();
+/// let mut buffer: Vec<u8> = Vec::new();
+/// tree::write_tree(&mut tree, &ARGS, &mut counts, &mut buffer).unwrap();
+/// assert!(String::from_utf8(buffer).unwrap().contains("notes.txt"));
+/// assert_eq!(counts.file_count, 1);
+/// ```
+pub fn write_tree<W: Write>(tree: &mut Tree, args: &RippyArgs, counts: &mut TreeCounts, writer: &mut W) -> io::Result<()> {
+    write_tree_to_buf(tree, "", 0, "", true, args, counts, writer)
+}
+
+/// Left margin written before each line of the rendered tree, left out for flush-left output with `--compact`.
+fn margin_left(args: &RippyArgs) -> &'static str {
+    if args.is_compact { "" } else { MARGIN_LEFT }
+}
+
 /// Creates the graphical terminal representation of the tree by iteratively printing the tree line by line using specified settings with active TTY check for ANSI coloring.
-pub fn write_tree_to_buf(tree: &mut Tree, enumeration: &str, depth: u32, prefix: &str, is_last: bool, args: &RippyArgs, counts: &mut TreeCounts, writer: &mut impl Write) -> io::Result<()> {
+#[allow(clippy::too_many_arguments)]
+fn write_tree_to_buf(tree: &mut Tree, enumeration: &str, depth: u32, prefix: &str, is_last: bool, args: &RippyArgs, counts: &mut TreeCounts, writer: &mut impl Write) -> io::Result<()> {
     // Establish display name format
     let display_name = &tree.display;
     // Handle optional display time or date last modified of contents
@@ -483,30 +1602,41 @@ pub fn write_tree_to_buf(tree: &mut Tree, enumeration: &str, depth: u32, prefix:
         (false, false) => concat_str!("(", display_datetime, ", ", display_size, ") "),
     };
 
-    if depth == 0 {
-        let root_name = ansi_color!(&args.colors.root, bold=!args.is_grayscale, display_name);
-        writeln!(writer, "{}", concat_str!(MARGIN_LEFT, &root_name))?;
+    if depth == 0 && tree.entry_type == EntryType::File {
+        // A file given as the root is rendered alone along with its details and any match
+        counts.file_count += 1;
+        let root_name = ansi_color!(&args.colors.root, bold=!args.is_no_root_bold, display_name);
+        let entry_details = ansi_color!(&args.colors.detail, bold=false, file_date_size_details);
+        let window = tree.window.as_deref().filter(|window| !window.is_empty()).map(|window| concat_str!(" ", window)).unwrap_or_default();
+        writeln!(writer, "{}", concat_str!(margin_left(args), entry_details, root_name, window))?;
+    } else if depth == 0 {
+        if args.is_count_root {
+            counts.dir_count += 1;
+        }
+        let root_name = ansi_color!(&args.colors.root, bold=!args.is_no_root_bold, display_name);
+        writeln!(writer, "{}", concat_str!(margin_left(args), &root_name))?;
     } else {
-        // Count dirs and files and determine styling
-        let (color, time_color, is_bold, padding) = match tree.entry_type {
+        // Count dirs and files and determine styling, with entries removed since the snapshot given to `--diff` shown but not counted
+        let (color, is_bold) = entry_style(tree, args);
+        let color = tree.diff.map_or(color, |status| diff_color(status, args));
+        let is_removed = tree.diff == Some(DiffStatus::Removed);
+        let (time_color, padding) = match tree.entry_type {
             EntryType::Directory => {
-                counts.dir_count += 1;
+                if !is_removed {
+                    counts.dir_count += 1;
+                }
                 (
-                    &args.colors.dir,
                     &args.colors.detail,
-                    !args.is_grayscale,
                     "".to_string(), // Return a &str
                 )
             },
             EntryType::File => {
-                counts.file_count += 1;
-                let window_padding = if args.is_search && args.is_window {tree.fmt_width.map(|w| " ".repeat(w - &tree.display.len() + 1)).unwrap_or_else(|| "".to_string())} else {"".to_string()};
+                if !is_removed {
+                    counts.file_count += 1;
+                }
+                let window_padding = if args.is_search && (args.is_window || args.is_line_numbers) && !args.is_window_below {tree.fmt_width.map(|w| " ".repeat(w.saturating_sub(visible_width(&tree.display)) + 1)).unwrap_or_default()} else {"".to_string()};
                 (
-                    // Don't worry about color if its grayscale or if the path is None or then finally if the path is not executable
-                    if args.is_grayscale || tree.path.is_none() {&None} else { if tree.path.as_ref().map_or_else(|| true, |p| !is_executable(p))  {&args.colors.file} else {&args.colors.exec}},
-                    // if args.is_grayscale || tree.path.as_ref().map_or_else(|| true, |p| !is_executable(p)) { &args.colors.file } else { &args.colors.exec },
                     &args.colors.detail,
-                    false,
                     window_padding,
                 )
             },
@@ -517,13 +1647,13 @@ pub fn write_tree_to_buf(tree: &mut Tree, enumeration: &str, depth: u32, prefix:
         } else {
             &args.colors.dir
         };
-        let indent_bar = "─".repeat(args.indent) + " ";
+        let indent_bar = args.glyphs.dash.repeat(args.indent) + " ";
         let connector = if args.is_flat {
             "".to_string()
         } else if is_last {
-            ansi_color!(connector_color, bold=false, concat_str!("╰", indent_bar))
+            ansi_color!(connector_color, bold=false, concat_str!(args.glyphs.elbow, indent_bar))
         } else {
-            ansi_color!(connector_color, bold=false, concat_str!("├", indent_bar))
+            ansi_color!(connector_color, bold=false, concat_str!(args.glyphs.tee, indent_bar))
         };
 
         // Enumeration prefix
@@ -533,13 +1663,41 @@ pub fn write_tree_to_buf(tree: &mut Tree, enumeration: &str, depth: u32, prefix:
             "".to_string()
         };
 
-        let entry_name = ansi_color!(color,bold=is_bold, display_name);
-        let entry_details = if file_date_size_details.is_empty() { file_date_size_details } else { ansi_color!(time_color, bold=false, file_date_size_details) };
-        let entry_window = tree.window.as_ref().map_or("", |p| p);
-        writeln!(writer, "{}", concat_str!(MARGIN_LEFT,prefix,connector,enum_prefix,entry_details,entry_name,padding,entry_window))?;
+        // Entries are marked with their status when diffing, leaving unchanged entries aligned with the rest
+        let diff_marker = match (tree.diff, &args.diff) {
+            (Some(status), _) => status.marker(),
+            (None, Some(_)) => "  ",
+            (None, None) => "",
+        };
+        let entry_name = ansi_color!(color,bold=is_bold, concat_str!(diff_marker, display_name));
+        let entry_name = if is_flagged_empty(tree, args) { concat_str!(entry_name, ansi_color!(&args.colors.muted, bold=false, " [empty]")) } else { entry_name };
+        let entry_details = match tree.size.filter(|_| args.is_size_heat && !display_size.is_empty()) {
+            // Only the size takes the color of its magnitude with the rest of the details left as is
+            Some(size) => {
+                let date_separator = if display_datetime.is_empty() { "" } else { ", " };
+                concat_str!(ansi_color!(time_color, bold=false, concat_str!("(", display_datetime, date_separator)), ansi_color!(size_heat_color(size), bold=false, display_size), ansi_color!(time_color, bold=false, ") "))
+            },
+            None if file_date_size_details.is_empty() => file_date_size_details,
+            None => ansi_color!(time_color, bold=false, file_date_size_details),
+        };
+        // Multiline windows continue on subsequent lines indented beneath the entry, starting there entirely if placed below
+        let mut window_lines = tree.window.as_deref().map(|window| window.split('\n')).into_iter().flatten();
+        let first_window_line = if args.is_window_below { "" } else { window_lines.next().unwrap_or_default() };
+        writeln!(writer, "{}", concat_str!(margin_left(args),prefix,connector,enum_prefix,entry_details,entry_name,padding,first_window_line))?;
+        let continuation = if args.is_flat {
+            "".to_string()
+        } else if is_last {
+            " ".repeat(args.indent + 2)
+        } else {
+            concat_str!(ansi_color!(connector_color, bold=false, args.glyphs.pipe), " ".repeat(args.indent + 1))
+        };
+        for window_line in window_lines {
+            writeln!(writer, "{}", concat_str!(margin_left(args), prefix, continuation, "  ", window_line))?;
+        }
     }
 
-    let level_indent = NB_SINGLE.repeat(args.indent) + " ";
+    let level_space = if args.is_ascii_space { " " } else { NB_SINGLE };
+    let level_indent = level_space.repeat(args.indent) + " ";
     let new_prefix = if args.is_flat {
         "".to_string()
     } else if depth == 0 {
@@ -552,45 +1710,32 @@ pub fn write_tree_to_buf(tree: &mut Tree, enumeration: &str, depth: u32, prefix:
         } else {
             &args.colors.dir
         };
-        concat_str!(prefix, ansi_color!(pipe_color, bold=false, "│"), level_indent)
+        concat_str!(prefix, ansi_color!(pipe_color, bold=false, args.glyphs.pipe), level_indent)
     };
 
-    // Collect children into a single vector and sort according to args
-    tree.children.sort_by(|_, a, _, b| (args.sort_by)(a, b));
-
-    // Determine the count of files for truncation
-    let total_files = tree.children.values().into_iter().filter(|c| c.entry_type == EntryType::File).count();
-
-    // Truncate the list if necessary
-    if total_files > args.max_files {
-        let mut files_seen = 0;
-        tree.children.retain(|_, child| {
-            if child.entry_type == EntryType::File {
-                if files_seen < args.max_files {
-                    files_seen += 1;
-                    true
-                } else {
-                    false
-                }
-            } else {
-                true
-            }
-        });
+    // Sort children according to args and truncate files beyond the maximum if needed
+    sort_and_truncate_children(tree, args, counts);
 
-        // Add a truncation entry if necessary and count files truncated
-        if files_seen >= args.max_files {
-            let trunc_num = total_files - args.max_files;
-            counts.file_count += trunc_num - 1;
-            let trunc_fmt = concat_str!(trunc_num.to_string(), " more ...");
-            let trunc_label = ansi_color!(&args.colors.detail, bold=false, trunc_fmt);
-            tree.children.insert(trunc_label.to_owned(), Tree::new(&trunc_label, &trunc_label, None, EntryType::File, None, None, None, None));
+    // Children in the middle are elided if requested, still counted as if they had been written
+    let elided = args.elide_middle.filter(|keep| tree.children.len() > keep * 2 + 1).map(|keep| keep..tree.children.len() - keep);
+    if let Some(elided) = &elided {
+        for child in tree.children.values().skip(elided.start).take(elided.len()) {
+            let child_counts = child.counts(true);
+            counts.dir_count += child_counts.dir_count;
+            counts.file_count += child_counts.file_count;
         }
     }
 
     // Print each child
     let last_index = tree.children.len().saturating_sub(1);
     for (i, child) in tree.children.values_mut().enumerate() {
-        let is_last_child = i == last_index;
+        if let Some(elided) = elided.as_ref().filter(|elided| elided.contains(&i)) {
+            if i == elided.start {
+                write_elision_marker(elided.len(), depth + 1, &new_prefix, args, writer)?;
+            }
+            continue;
+        }
+        let is_last_child = i == last_index && tree.truncated.is_none();
         // Enumeration padding if needed
         let enumeration = if args.is_enumerate {
             let enum_padding = count_digits_log(last_index.saturating_add(1)).saturating_sub(count_digits_log(i.saturating_add(1)));
@@ -599,28 +1744,233 @@ pub fn write_tree_to_buf(tree: &mut Tree, enumeration: &str, depth: u32, prefix:
 
         write_tree_to_buf(child, enumeration, depth + 1, &new_prefix, is_last_child, args, counts, writer)?;
     }
+    if let Some(truncated) = tree.truncated {
+        write_truncation_marker(truncated, depth + 1, &new_prefix, args, writer)?;
+    }
 
-    if depth == 1 && is_last {
+    // Finish with a single blank line before the summary, after the last entry or the root alone if there are none
+    if !args.is_compact && ((depth == 1 && is_last) || (depth == 0 && (tree.children.is_empty() || tree.truncated.is_some()))) {
         writeln!(writer)?;
     }
 
     Ok(())
 }
 
+/// Writes the line standing in for the files truncated from a directory as `N more ...`, drawn as its last entry.
+fn write_truncation_marker(truncated: usize, depth: u32, prefix: &str, args: &RippyArgs, writer: &mut impl Write) -> io::Result<()> {
+    let connector = if args.is_flat {
+        "".to_string()
+    } else {
+        let connector_color = if depth == 1 { &args.colors.root } else { &args.colors.dir };
+        ansi_color!(connector_color, bold=false, concat_str!(args.glyphs.elbow, args.glyphs.dash.repeat(args.indent), " "))
+    };
+    writeln!(writer, "{}", concat_str!(margin_left(args), prefix, connector, format_truncation_label(truncated, args)))
+}
+
+/// Writes the line standing in for children elided from the middle of a directory as `... (K hidden) ...`, drawn like an entry that is not the last.
+fn write_elision_marker(hidden: usize, depth: u32, prefix: &str, args: &RippyArgs, writer: &mut impl Write) -> io::Result<()> {
+    let connector = if args.is_flat {
+        "".to_string()
+    } else {
+        let connector_color = if depth == 1 { &args.colors.root } else { &args.colors.dir };
+        ansi_color!(connector_color, bold=false, concat_str!(args.glyphs.tee, args.glyphs.dash.repeat(args.indent), " "))
+    };
+    let marker = ansi_color!(&args.colors.muted, bold=false, concat_str!("... (", hidden.to_string(), " hidden) ..."));
+    writeln!(writer, "{}", concat_str!(margin_left(args), prefix, connector, marker))
+}
+
+/// Writes the results using the layout requested, as a table, a level order list or the tree by default.
+pub fn write_results_to_buf(tree: &mut Tree, args: &RippyArgs, counts: &mut TreeCounts, writer: &mut impl Write) -> io::Result<()> {
+    if args.is_table {
+        write_table_to_buf(tree, args, counts, writer)
+    } else if args.is_breadth_first {
+        write_breadth_first_to_buf(tree, args, counts, writer)
+    } else {
+        write_tree(tree, args, counts, writer)
+    }
+}
+
 /// Wrapper to handle printing of tree without coloring main with result.
 pub fn print_tree(tree: &mut Tree, args: &RippyArgs, counts: &mut TreeCounts) -> io::Result<()> {
     let stdout = stdout();
     let mut writer = io::BufWriter::new(stdout.lock());
-    write_tree_to_buf(tree, "", 0, "", true, &args, counts, &mut writer)
+    write_tree(tree, args, counts, &mut writer)
 }
 
-/// Traverses the tree to return the appropriate counts of each type of entry, ignoring the initial root directory target of the search.
-pub fn count_tree(tree: &Tree, counts: &mut TreeCounts, is_first: bool) {
-    match tree.entry_type {
-        EntryType::Directory => {if !is_first {counts.dir_count += 1;}},
-        EntryType::File => counts.file_count += 1,
+/// Creates a flat list of the tree in level order, listing every entry at one depth before any at the next with the children of each directory in sorted order.
+pub fn write_breadth_first_to_buf(tree: &mut Tree, args: &RippyArgs, counts: &mut TreeCounts, writer: &mut impl Write) -> io::Result<()> {
+    if args.is_count_root {
+        counts.dir_count += 1;
+    }
+    let root_name = ansi_color!(&args.colors.root, bold=!args.is_no_root_bold, &tree.display);
+    writeln!(writer, "{}", concat_str!(margin_left(args), &root_name))?;
+
+    let mut level: Vec<&mut Tree> = vec![tree];
+    while !level.is_empty() {
+        let mut next_level: Vec<&mut Tree> = Vec::new();
+        for parent in level {
+            sort_and_truncate_children(parent, args, counts);
+            let truncated = parent.truncated;
+            for child in parent.children.values_mut() {
+                let (color, is_bold) = entry_style(child, args);
+                match child.entry_type {
+                    EntryType::Directory => counts.dir_count += 1,
+                    EntryType::File => counts.file_count += 1,
+                }
+                let display_datetime = format_display_datetime(child.last_modified, args, child.entry_type);
+                let display_size = format_display_size(child.size, args, child.entry_type);
+                let details = match (display_datetime.is_empty(), display_size.is_empty()) {
+                    (true, true) => "".to_string(),
+                    (true, false) | (false, true) => ansi_color!(&args.colors.detail, bold=false, concat_str!("(", display_datetime, display_size, ") ")),
+                    (false, false) => ansi_color!(&args.colors.detail, bold=false, concat_str!("(", display_datetime, ", ", display_size, ") ")),
+                };
+                let entry_name = ansi_color!(color, bold=is_bold, &child.display);
+                let entry_name = if is_flagged_empty(child, args) { concat_str!(entry_name, ansi_color!(&args.colors.muted, bold=false, " [empty]")) } else { entry_name };
+                let padding = if args.is_search && (args.is_window || args.is_line_numbers) && child.entry_type == EntryType::File {
+                    child.fmt_width.map(|w| " ".repeat(w.saturating_sub(visible_width(&child.display)) + 1)).unwrap_or_default()
+                } else {
+                    "".to_string()
+                };
+                let mut window_lines = child.window.as_deref().unwrap_or_default().split('\n');
+                let first_window_line = window_lines.next().unwrap_or_default();
+                writeln!(writer, "{}", concat_str!(margin_left(args), details, entry_name, padding, first_window_line))?;
+                for window_line in window_lines {
+                    writeln!(writer, "{}", concat_str!(margin_left(args), "  ", window_line))?;
+                }
+                next_level.push(child);
+            }
+            if let Some(truncated) = truncated {
+                writeln!(writer, "{}", concat_str!(margin_left(args), format_truncation_label(truncated, args)))?;
+            }
+        }
+        level = next_level;
+    }
+    if !args.is_compact {
+        writeln!(writer)?;
+    }
+    Ok(())
+}
+
+/// Wrapper to handle printing of the level order list to stdout.
+pub fn print_breadth_first(tree: &mut Tree, args: &RippyArgs, counts: &mut TreeCounts) -> io::Result<()> {
+    let stdout = stdout();
+    let mut writer = io::BufWriter::new(stdout.lock());
+    write_breadth_first_to_buf(tree, args, counts, &mut writer)
+}
+
+/// Single row of the tabular output holding the plain column text used to compute widths before any styling is applied.
+struct TableRow {
+    entry_type: &'static str,
+    size: String,
+    date: String,
+    name: String,
+    color: Option<&'static str>,
+    is_bold: bool,
+    window: Option<String>,
+}
+
+/// Recursively collects the rows for the tabular output in the same depth-first order used when rendering the tree.
+fn collect_table_rows(tree: &mut Tree, args: &RippyArgs, counts: &mut TreeCounts, rows: &mut Vec<TableRow>) {
+    sort_and_truncate_children(tree, args, counts);
+    for child in tree.children.values_mut() {
+        let (color, is_bold) = entry_style(child, args);
+        let entry_type = match child.entry_type {
+            EntryType::Directory => {
+                counts.dir_count += 1;
+                "dir"
+            },
+            EntryType::File => {
+                counts.file_count += 1;
+                "file"
+            },
+        };
+        let size = format_display_size(child.size, args, child.entry_type);
+        let date = format_display_datetime(child.last_modified, args, child.entry_type);
+        rows.push(TableRow {
+            entry_type,
+            size: if size.is_empty() { "-".to_string() } else { size },
+            date: if date.is_empty() { "-".to_string() } else { date },
+            name: child.display.clone(),
+            color,
+            is_bold,
+            window: child.window.clone().filter(|w| !w.is_empty()),
+        });
+        collect_table_rows(child, args, counts, rows);
+    }
+    if let Some(truncated) = tree.truncated {
+        rows.push(TableRow {
+            entry_type: "",
+            size: "".to_string(),
+            date: "".to_string(),
+            name: concat_str!(truncated.to_string(), " more ..."),
+            color: args.colors.detail,
+            is_bold: false,
+            window: None,
+        });
+    }
+}
+
+/// Creates a tabular representation of the tree as aligned columns of type, size, date and name similar to `ls -l`, collecting every row first to compute the column widths.
+pub fn write_table_to_buf(tree: &mut Tree, args: &RippyArgs, counts: &mut TreeCounts, writer: &mut impl Write) -> io::Result<()> {
+    let mut rows: Vec<TableRow> = Vec::new();
+    if args.is_count_root {
+        counts.dir_count += 1;
+    }
+    collect_table_rows(tree, args, counts, &mut rows);
+
+    // Column widths are measured by visible characters across all rows
+    let type_width = rows.iter().map(|row| row.entry_type.chars().count()).max().unwrap_or(0);
+    let size_width = rows.iter().map(|row| row.size.chars().count()).max().unwrap_or(0);
+    let date_width = rows.iter().map(|row| row.date.chars().count()).max().unwrap_or(0);
+    let name_width = rows.iter().filter(|row| row.window.is_some()).map(|row| visible_width(&row.name)).max().unwrap_or(0);
+
+    let root_name = ansi_color!(&args.colors.root, bold=!args.is_no_root_bold, &tree.display);
+    writeln!(writer, "{}", concat_str!(margin_left(args), &root_name))?;
+    for row in rows.iter() {
+        let details = format!("{:<type_width$}  {:>size_width$}  {:<date_width$}  ", row.entry_type, row.size, row.date);
+        let entry_details = ansi_color!(&args.colors.detail, bold=false, details);
+        let entry_name = ansi_color!(row.color, bold=row.is_bold, &row.name);
+        let entry_window = row.window.as_ref().map_or_else(String::new, |window| concat_str!(" ".repeat(name_width.saturating_sub(visible_width(&row.name)) + 1), window));
+        writeln!(writer, "{}", concat_str!(margin_left(args), entry_details, entry_name, entry_window))?;
+    }
+    if !args.is_compact {
+        writeln!(writer)?;
+    }
+    Ok(())
+}
+
+/// Wrapper to handle printing of the tabular output to stdout.
+pub fn print_table(tree: &mut Tree, args: &RippyArgs, counts: &mut TreeCounts) -> io::Result<()> {
+    let stdout = stdout();
+    let mut writer = io::BufWriter::new(stdout.lock());
+    write_table_to_buf(tree, args, counts, &mut writer)
+}
+
+/// Adds the counts of each type of entry in the tree to those provided, ignoring the initial root directory target of the search when `skip_root` is true. See `Tree::counts` to get the counts of a tree alone.
+pub fn count_tree(tree: &Tree, counts: &mut TreeCounts, skip_root: bool) {
+    let tree_counts = tree.counts(!skip_root);
+    counts.dir_count += tree_counts.dir_count;
+    counts.file_count += tree_counts.file_count;
+}
+
+/// Counts the entries directly from the crawl results without building the `Tree`, matching `count_tree` on the tree that would be built from them. Directories only implied by the paths of their descendants, as when searching, are counted once each.
+pub fn count_paths(paths: &[TreeLeaf], args: &RippyArgs, counts: &mut TreeCounts, skip_root: bool) {
+    let root_path = args.directory.to_string_lossy();
+    let mut dirs: std::collections::HashSet<&str> = std::collections::HashSet::new();
+    for leaf in paths.iter() {
+        let traversal_path = leaf.relative_path.strip_prefix(root_path.as_ref()).unwrap_or(&leaf.relative_path).trim_matches('/');
+        if leaf.is_dir {
+            dirs.insert(traversal_path);
+        } else {
+            counts.file_count += 1;
+        }
+        // Every ancestor between the root and the entry exists as a directory in the tree
+        for (i, _) in traversal_path.match_indices('/') {
+            dirs.insert(&traversal_path[..i]);
+        }
     }
-    for child in tree.children.values() {
-        count_tree(child, counts, false);
+    counts.dir_count += dirs.len();
+    if !skip_root && !args.is_file_root {
+        counts.dir_count += 1;
     }
 }
\ No newline at end of file
@@ -92,15 +92,15 @@ impl RootDirectory {
                 // println!("Creating parent: {parent:?}");
             }
             // No need to guard this call since creates intermediates safely
-            fs::create_dir_all(parent).map_err(|e| DirError::Io(e))?;
+            fs::create_dir_all(parent).map_err(DirError::Io)?;
         }
         // Check if the target is a file (has a file name component)  && !targets.exists()
         if targets.file_name().is_some() && !targets.exists() {
             // Create the file (and write content if provided)
             // println!("Creating file target: {targets:?}");
-            let mut file = fs::File::create(targets).map_err(|e| DirError::Io(e) )?;
+            let mut file = fs::File::create(targets).map_err(DirError::Io )?;
             if let Some(data) = content {
-                file.write_all(data.into().as_ref()).map_err(|e| DirError::Io(e))?;
+                file.write_all(data.into().as_ref()).map_err(DirError::Io)?;
             }
         }
         Ok(())            
@@ -119,13 +119,13 @@ impl RootDirectory {
                 // println!("Creating parent: {parent:?}");
             }
             // No need to guard this call since creates intermediates safely
-            fs::create_dir_all(parent).map_err(|e| DirError::Io(e))?;
+            fs::create_dir_all(parent).map_err(DirError::Io)?;
         }
         // Check if the target is a file (has a file name component)  && !targets.exists()
         if targets.file_name().is_some() && !targets.exists() && targets.extension().is_none() {
             // Create the file (and write content if provided)
             // println!("Creating file target: {targets:?}");
-            let _file = fs::create_dir(targets).map_err(|e| DirError::Io(e) )?;
+            fs::create_dir(targets).map_err(DirError::Io )?;
         }
         Ok(())            
     }    
@@ -145,16 +145,16 @@ impl RootDirectory {
                 // println!("Creating parent: {parent:?}");
             }
             // No need to guard this call since creates intermediates safely
-            fs::create_dir_all(parent).map_err(|e| DirError::Io(e))?;
+            fs::create_dir_all(parent).map_err(DirError::Io)?;
         }
         
         // Check if the target is a file (has a file name component)  && !targets.exists()
         if targets.file_name().is_some() && targets.extension().is_some() && !targets.exists() {
             // Create the file (and write content if provided)
             // println!("Creating file target: {targets:?}");
-            let mut file = fs::File::create(targets).map_err(|e| DirError::Io(e) )?;
+            let mut file = fs::File::create(targets).map_err(DirError::Io )?;
             if let Some(data) = content {
-                file.write_all(data.into().as_ref()).map_err(|e| DirError::Io(e))?;
+                file.write_all(data.into().as_ref()).map_err(DirError::Io)?;
             }
         }
     
@@ -166,7 +166,7 @@ impl RootDirectory {
         if !self.root().exists() {
             return Err(DirError::InvalidDirectory(format!("Provided path '{}' does not exist and so no deletion will be performed", self.root().display())))
         }
-        fs::remove_dir_all(self.root()).map_err(|e| DirError::Io(e))?;
+        fs::remove_dir_all(self.root()).map_err(DirError::Io)?;
         Ok(())
     }
 }
\ No newline at end of file
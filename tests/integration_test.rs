@@ -7,7 +7,7 @@ mod tests {
     use std::thread;
     use std::time::Duration;
     use std::path::PathBuf;
-    use rippy::{crawl::{self, CrawlResults, TreeLeaf}, tcolor};
+    use rippy::{crawl::{self, CrawlResults, TreeLeaf, WindowMatch}, tcolor};
     use rippy::tree::{self, Tree, EntryType, TreeMap};
     use regex::{Regex, RegexSet};
     use serde_json::json;
@@ -17,7 +17,7 @@ mod tests {
     /// Sorts by `TreeLeaf.relative_path` field to make comparisons platform agnostic before equality assertion.
     const SORT_RELATIVE: fn(&TreeLeaf, &TreeLeaf) -> std::cmp::Ordering = |a: &TreeLeaf, b: &TreeLeaf| a.relative_path.cmp(&b.relative_path);
     /// Sorts by the second element in a tuple to make comparisons platform agnostic before equality assertion.
-    const SORT_SECOND: fn(&(&str, &str), &(&str, &str)) -> std::cmp::Ordering = |a: &(&str, &str), b: &(&str, &str)| a.1.cmp(&b.1);
+    const SORT_SECOND: fn(&(&str, &str), &(&str, &str)) -> std::cmp::Ordering = |a: &(&str, &str), b: &(&str, &str)| a.1.cmp(b.1);
 
     #[test]
     /// Tests all major program arguments including search include & ignore patterns, primary search target, max depth and case sensitivity application.
@@ -43,7 +43,7 @@ mod tests {
         let expected_pattern = Regex::new("(?i)\\w[A-z]{3}find-me\\b").unwrap();
 
         assert_eq!(rip_args.pattern.unwrap().as_str(), expected_pattern.as_str());
-        assert_eq!(rip_args.is_search, true);
+        assert!(rip_args.is_search);
 
         // Grayscale color schema test
         let test_grayscale = vec!["rippy", ".", "--grayscale"];
@@ -79,7 +79,7 @@ mod tests {
     /// 
     /// Tests a deep and tall directory structure using default options.
     pub fn test_crawl_directory_tall() -> Result<(), DirError> {
-        const ROOT_TEST_DIR: &'static str = "fake-tall";
+        const ROOT_TEST_DIR: &str = "fake-tall";
         static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR]));
         let file_contents: Option<&str> = None;
         let test_dir = RootDirectory::new(ROOT_TEST_DIR);
@@ -89,15 +89,20 @@ mod tests {
         test_dir.generate("a/b/c/file.txt", file_contents)?;
         let mut expected_crawl_results = CrawlResults { 
             paths: vec![
-                TreeLeaf {name: "a".to_string(),relative_path: "fake-tall/a".to_string(),is_dir: true,last_modified: None,size: None,window: None,display: "a".to_string(),is_sym: false,},
-                TreeLeaf {name: "b".to_string(),relative_path: "fake-tall/a/b".to_string(),is_dir: true,last_modified: None,size: None,window: None,display: "b".to_string(),is_sym: false,},
-                TreeLeaf {name: "c".to_string(),relative_path: "fake-tall/a/b/c".to_string(),is_dir: true,last_modified: None,size: None,window: None,display: "c".to_string(),is_sym: false,},
-                TreeLeaf {name: "file.txt".to_string(),relative_path: "fake-tall/a/b/c/file.txt".to_string(),is_dir: false,last_modified: None,size: None,window: None,display: "file.txt".to_string(),is_sym: false,},
-                TreeLeaf {name: "file.txt".to_string(),relative_path: "fake-tall/a/b/file.txt".to_string(),is_dir: false,last_modified: None,size: None,window: None,display: "file.txt".to_string(),is_sym: false,},
-                TreeLeaf {name: "file.txt".to_string(),relative_path: "fake-tall/a/file.txt".to_string(),is_dir: false,last_modified: None,size: None,window: None,display: "file.txt".to_string(),is_sym: false,},
-                TreeLeaf {name: "file.txt".to_string(),relative_path: "fake-tall/file.txt".to_string(),is_dir: false,last_modified: None,size: None,window: None,display: "file.txt".to_string(),is_sym: false,},
+                TreeLeaf {name: "a".to_string(),relative_path: "fake-tall/a".to_string(),is_dir: true,last_modified: None,size: None,window: None,window_match: None,display: "a".to_string(),is_sym: false,},
+                TreeLeaf {name: "b".to_string(),relative_path: "fake-tall/a/b".to_string(),is_dir: true,last_modified: None,size: None,window: None,window_match: None,display: "b".to_string(),is_sym: false,},
+                TreeLeaf {name: "c".to_string(),relative_path: "fake-tall/a/b/c".to_string(),is_dir: true,last_modified: None,size: None,window: None,window_match: None,display: "c".to_string(),is_sym: false,},
+                TreeLeaf {name: "file.txt".to_string(),relative_path: "fake-tall/a/b/c/file.txt".to_string(),is_dir: false,last_modified: None,size: None,window: None,window_match: None,display: "file.txt".to_string(),is_sym: false,},
+                TreeLeaf {name: "file.txt".to_string(),relative_path: "fake-tall/a/b/file.txt".to_string(),is_dir: false,last_modified: None,size: None,window: None,window_match: None,display: "file.txt".to_string(),is_sym: false,},
+                TreeLeaf {name: "file.txt".to_string(),relative_path: "fake-tall/a/file.txt".to_string(),is_dir: false,last_modified: None,size: None,window: None,window_match: None,display: "file.txt".to_string(),is_sym: false,},
+                TreeLeaf {name: "file.txt".to_string(),relative_path: "fake-tall/file.txt".to_string(),is_dir: false,last_modified: None,size: None,window: None,window_match: None,display: "file.txt".to_string(),is_sym: false,},
             ],
             paths_searched: 4,
+            is_truncated: false,
+            paths_unreadable: 0,
+            paths_hidden: 0,
+            paths_ignored: 0,
+            mounts: Vec::new(),
             };
         let mut output_crawl_results = crawl::crawl_directory(&ARGS)?;
         expected_crawl_results.paths.sort_by(SORT_RELATIVE);
@@ -124,7 +129,7 @@ mod tests {
     /// 
     /// Tests a wide and shallow directory structure using default options.
     pub fn test_crawl_directory_wide() -> Result<(), DirError> {
-        const ROOT_TEST_DIR: &'static str = "fake-wide";
+        const ROOT_TEST_DIR: &str = "fake-wide";
         static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR]));
         let no_contents: Option<&str> = None;
         let test_dir = RootDirectory::new(ROOT_TEST_DIR);
@@ -134,15 +139,20 @@ mod tests {
         test_dir.generate("c/file.txt", no_contents)?;
         let mut expected_crawl_results = CrawlResults {
             paths: vec![
-                TreeLeaf {name: "a".to_string(),relative_path: "fake-wide/a".to_string(),is_dir: true,last_modified: None,size: None,window: None,display: "a".to_string(),is_sym: false,},
-                TreeLeaf {name: "file.txt".to_string(),relative_path: "fake-wide/a/file.txt".to_string(),is_dir: false,last_modified: None,size: None,window: None,display: "file.txt".to_string(),is_sym: false,},
-                TreeLeaf {name: "b".to_string(),relative_path: "fake-wide/b".to_string(),is_dir: true,last_modified: None,size: None,window: None,display: "b".to_string(),is_sym: false,},
-                TreeLeaf {name: "file.txt".to_string(),relative_path: "fake-wide/b/file.txt".to_string(),is_dir: false,last_modified: None,size: None,window: None,display: "file.txt".to_string(),is_sym: false,},
-                TreeLeaf {name: "c".to_string(),relative_path: "fake-wide/c".to_string(),is_dir: true,last_modified: None,size: None,window: None,display: "c".to_string(),is_sym: false,},
-                TreeLeaf {name: "file.txt".to_string(),relative_path: "fake-wide/c/file.txt".to_string(),is_dir: false,last_modified: None,size: None,window: None,display: "file.txt".to_string(),is_sym: false,},
-                TreeLeaf {name: "file.md".to_string(),relative_path: "fake-wide/file.md".to_string(),is_dir: false,last_modified: None,size: None,window: None,display: "file.md".to_string(),is_sym: false,},
+                TreeLeaf {name: "a".to_string(),relative_path: "fake-wide/a".to_string(),is_dir: true,last_modified: None,size: None,window: None,window_match: None,display: "a".to_string(),is_sym: false,},
+                TreeLeaf {name: "file.txt".to_string(),relative_path: "fake-wide/a/file.txt".to_string(),is_dir: false,last_modified: None,size: None,window: None,window_match: None,display: "file.txt".to_string(),is_sym: false,},
+                TreeLeaf {name: "b".to_string(),relative_path: "fake-wide/b".to_string(),is_dir: true,last_modified: None,size: None,window: None,window_match: None,display: "b".to_string(),is_sym: false,},
+                TreeLeaf {name: "file.txt".to_string(),relative_path: "fake-wide/b/file.txt".to_string(),is_dir: false,last_modified: None,size: None,window: None,window_match: None,display: "file.txt".to_string(),is_sym: false,},
+                TreeLeaf {name: "c".to_string(),relative_path: "fake-wide/c".to_string(),is_dir: true,last_modified: None,size: None,window: None,window_match: None,display: "c".to_string(),is_sym: false,},
+                TreeLeaf {name: "file.txt".to_string(),relative_path: "fake-wide/c/file.txt".to_string(),is_dir: false,last_modified: None,size: None,window: None,window_match: None,display: "file.txt".to_string(),is_sym: false,},
+                TreeLeaf {name: "file.md".to_string(),relative_path: "fake-wide/file.md".to_string(),is_dir: false,last_modified: None,size: None,window: None,window_match: None,display: "file.md".to_string(),is_sym: false,},
             ],
             paths_searched: 4,
+            is_truncated: false,
+            paths_unreadable: 0,
+            paths_hidden: 0,
+            paths_ignored: 0,
+            mounts: Vec::new(),
         };
         let mut output_crawl_results = crawl::crawl_directory(&ARGS)?;
         expected_crawl_results.paths.sort_by(SORT_RELATIVE);
@@ -169,7 +179,7 @@ mod tests {
     /// Testing functionality of `[--ignore | -I]` and `[--include | -X]` pattern filtering options.
     /// Includes ANSI color commands to highlight matching portions contained in window snippets.
     pub fn test_crawl_directory_search_contents() -> Result<(), DirError> {
-        const ROOT_TEST_DIR: &'static str = "fake-search";
+        const ROOT_TEST_DIR: &str = "fake-search";
         static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "-x", "*.txt", "-I", "b4", r"\b\d{3}\b|\b\d{3}[a-zA-Z]{3,}\b", "-r", "20"]));
         let no_contents: Option<&str> = None;
         let test_dir = RootDirectory::new(ROOT_TEST_DIR);
@@ -185,11 +195,16 @@ mod tests {
         test_dir.generate("b4/i2.txt", Some("123wrongdir should match but wont return due to ignored dir"))?;
         let mut expected_crawl_results = CrawlResults {
             paths: vec![
-                TreeLeaf {name: "f1.txt".to_string(),relative_path: "fake-search/b1/f1.txt".to_string(),is_dir: false,last_modified: None,size: None,window: Some("\u{1b}[38;5;248m...\u{1b}[0m\u{1b}[38;5;248mand should return: \u{1b}[0m\u{1b}[1m\u{1b}[38;5;42m123xyz\u{1b}[0m\u{1b}[38;5;248m\u{1b}[0m".to_string(),),display: "f1.txt".to_string(),is_sym: false,},
-                TreeLeaf {name: "f1.txt".to_string(),relative_path: "fake-search/b2/f1.txt".to_string(),is_dir: false,last_modified: None,size: None,window: Some("\u{1b}[38;5;248m\u{1b}[0m\u{1b}[1m\u{1b}[38;5;42m789\u{1b}[0m\u{1b}[38;5;248m Should match and re\u{1b}[0m\u{1b}[38;5;248m...\u{1b}[0m".to_string(),),display: "f1.txt".to_string(),is_sym: false,},
-                TreeLeaf {name: "x1.txt".to_string(),relative_path: "fake-search/b3/x1.txt".to_string(),is_dir: false,last_modified: None,size: None,window: Some("\u{1b}[38;5;248m\u{1b}[0m\u{1b}[1m\u{1b}[38;5;42m123def\u{1b}[0m\u{1b}[38;5;248m should match and re\u{1b}[0m\u{1b}[38;5;248m...\u{1b}[0m".to_string(),),display: "x1.txt".to_string(),is_sym: false,},
+                TreeLeaf {name: "f1.txt".to_string(),relative_path: "fake-search/b1/f1.txt".to_string(),is_dir: false,last_modified: None,size: None,window: Some("\u{1b}[38;5;248m...\u{1b}[0m\u{1b}[38;5;248mand should return: \u{1b}[0m\u{1b}[1m\u{1b}[38;5;42m123xyz\u{1b}[0m\u{1b}[38;5;248m\u{1b}[0m".to_string(),),window_match: Some(WindowMatch { text: "...and should return: 123xyz".to_string(), start: 22, end: 28 }),display: "f1.txt".to_string(),is_sym: false,},
+                TreeLeaf {name: "f1.txt".to_string(),relative_path: "fake-search/b2/f1.txt".to_string(),is_dir: false,last_modified: None,size: None,window: Some("\u{1b}[38;5;248m\u{1b}[0m\u{1b}[1m\u{1b}[38;5;42m789\u{1b}[0m\u{1b}[38;5;248m Should match and re\u{1b}[0m\u{1b}[38;5;248m...\u{1b}[0m".to_string(),),window_match: Some(WindowMatch { text: "789 Should match and re...".to_string(), start: 0, end: 3 }),display: "f1.txt".to_string(),is_sym: false,},
+                TreeLeaf {name: "x1.txt".to_string(),relative_path: "fake-search/b3/x1.txt".to_string(),is_dir: false,last_modified: None,size: None,window: Some("\u{1b}[38;5;248m\u{1b}[0m\u{1b}[1m\u{1b}[38;5;42m123def\u{1b}[0m\u{1b}[38;5;248m should match and re\u{1b}[0m\u{1b}[38;5;248m...\u{1b}[0m".to_string(),),window_match: Some(WindowMatch { text: "123def should match and re...".to_string(), start: 0, end: 6 }),display: "x1.txt".to_string(),is_sym: false,},
             ],
             paths_searched: 6,
+            is_truncated: false,
+            paths_unreadable: 0,
+            paths_hidden: 1,
+            paths_ignored: 1,
+            mounts: Vec::new(),
         };
         let mut output_crawl_results = crawl::crawl_directory(&ARGS)?;
         output_crawl_results.paths.sort_by(SORT_RELATIVE);
@@ -214,7 +229,7 @@ mod tests {
     /// 
     /// Tests functionality of `[--all | -a]` option to toggle inclusion of hidden entries.
     pub fn test_crawl_directory_hidden() -> Result<(), DirError> {
-        const ROOT_TEST_DIR: &'static str = "fake-hidden";
+        const ROOT_TEST_DIR: &str = "fake-hidden";
         static ARGS_NOT_HIDDEN: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR]));
         let no_contents: Option<&str> = None;
         let test_dir = RootDirectory::new(ROOT_TEST_DIR);
@@ -222,10 +237,15 @@ mod tests {
         test_dir.generate("d1/not-hidden.txt", no_contents)?;
         let expected_crawl_results = CrawlResults {
             paths: vec![
-                TreeLeaf {name: "d1".to_string(),relative_path: "fake-hidden/d1".to_string(),is_dir: true,last_modified: None,size: None,window: None,display: "d1".to_string(),is_sym: false,},
-                TreeLeaf {name: "not-hidden.txt".to_string(),relative_path: "fake-hidden/d1/not-hidden.txt".to_string(),is_dir: false,last_modified: None,size: None,window: None,display: "not-hidden.txt".to_string(),is_sym: false,},
+                TreeLeaf {name: "d1".to_string(),relative_path: "fake-hidden/d1".to_string(),is_dir: true,last_modified: None,size: None,window: None,window_match: None,display: "d1".to_string(),is_sym: false,},
+                TreeLeaf {name: "not-hidden.txt".to_string(),relative_path: "fake-hidden/d1/not-hidden.txt".to_string(),is_dir: false,last_modified: None,size: None,window: None,window_match: None,display: "not-hidden.txt".to_string(),is_sym: false,},
             ],
             paths_searched: 1,
+            is_truncated: false,
+            paths_unreadable: 0,
+            paths_hidden: 1,
+            paths_ignored: 0,
+            mounts: Vec::new(),
         };
         let crawl_results = crawl::crawl_directory(&ARGS_NOT_HIDDEN);
         assert_eq!(crawl_results.unwrap(), expected_crawl_results);
@@ -233,11 +253,16 @@ mod tests {
         static ARGS_ALL: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--all", ROOT_TEST_DIR]));
         let mut expected_crawl_results = CrawlResults {
             paths: vec![
-                TreeLeaf {name: ".hidden".to_string(),relative_path: "fake-hidden/.hidden".to_string(),is_dir: false,last_modified: None,size: None,window: None,display: ".hidden".to_string(),is_sym: false,},
-                TreeLeaf {name: "d1".to_string(),relative_path: "fake-hidden/d1".to_string(),is_dir: true,last_modified: None,size: None,window: None,display: "d1".to_string(),is_sym: false,},
-                TreeLeaf {name: "not-hidden.txt".to_string(),relative_path: "fake-hidden/d1/not-hidden.txt".to_string(),is_dir: false,last_modified: None,size: None,window: None,display: "not-hidden.txt".to_string(),is_sym: false,},
+                TreeLeaf {name: ".hidden".to_string(),relative_path: "fake-hidden/.hidden".to_string(),is_dir: false,last_modified: None,size: None,window: None,window_match: None,display: ".hidden".to_string(),is_sym: false,},
+                TreeLeaf {name: "d1".to_string(),relative_path: "fake-hidden/d1".to_string(),is_dir: true,last_modified: None,size: None,window: None,window_match: None,display: "d1".to_string(),is_sym: false,},
+                TreeLeaf {name: "not-hidden.txt".to_string(),relative_path: "fake-hidden/d1/not-hidden.txt".to_string(),is_dir: false,last_modified: None,size: None,window: None,window_match: None,display: "not-hidden.txt".to_string(),is_sym: false,},
             ],
             paths_searched: 2,
+            is_truncated: false,
+            paths_unreadable: 0,
+            paths_hidden: 0,
+            paths_ignored: 0,
+            mounts: Vec::new(),
         };
         let mut output_crawl_results = crawl::crawl_directory(&ARGS_ALL)?;
         output_crawl_results.paths.sort_by(SORT_RELATIVE);
@@ -264,7 +289,7 @@ mod tests {
     /// 
     /// Tests functionality of `[--max-depth | -L]` option to restrict maximum recursive search depth.
     pub fn test_crawl_directory_max_depth() -> Result<(), DirError> {
-        const ROOT_TEST_DIR: &'static str = "fake-depth";
+        const ROOT_TEST_DIR: &str = "fake-depth";
         static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--max-depth", "3", ROOT_TEST_DIR]));
         let no_contents: Option<&str> = None;
         let test_dir = RootDirectory::new(ROOT_TEST_DIR);
@@ -273,13 +298,18 @@ mod tests {
         test_dir.generate("d1/d2/d3/d4/d5/d6/depth-7.txt", no_contents)?;
         let mut expected_crawl_results = CrawlResults {
             paths: vec![
-                TreeLeaf {name: "d1".to_string(),relative_path: "fake-depth/d1".to_string(),is_dir: true,last_modified: None,size: None,window: None,display: "d1".to_string(),is_sym: false,},
-                TreeLeaf {name: "d2".to_string(),relative_path: "fake-depth/d1/d2".to_string(),is_dir: true,last_modified: None,size: None,window: None,display: "d2".to_string(),is_sym: false,},
-                TreeLeaf {name: "d3".to_string(),relative_path: "fake-depth/d1/d2/d3".to_string(),is_dir: true,last_modified: None,size: None,window: None,display: "d3".to_string(),is_sym: false,},
-                TreeLeaf {name: "depth-3.txt".to_string(),relative_path: "fake-depth/d1/d2/depth-3.txt".to_string(),is_dir: false,last_modified: None,size: None,window: None,display: "depth-3.txt".to_string(),is_sym: false,},
-                TreeLeaf {name: "depth-1.txt".to_string(),relative_path: "fake-depth/depth-1.txt".to_string(),is_dir: false,last_modified: None,size: None,window: None,display: "depth-1.txt".to_string(),is_sym: false,},
+                TreeLeaf {name: "d1".to_string(),relative_path: "fake-depth/d1".to_string(),is_dir: true,last_modified: None,size: None,window: None,window_match: None,display: "d1".to_string(),is_sym: false,},
+                TreeLeaf {name: "d2".to_string(),relative_path: "fake-depth/d1/d2".to_string(),is_dir: true,last_modified: None,size: None,window: None,window_match: None,display: "d2".to_string(),is_sym: false,},
+                TreeLeaf {name: "d3".to_string(),relative_path: "fake-depth/d1/d2/d3".to_string(),is_dir: true,last_modified: None,size: None,window: None,window_match: None,display: "d3".to_string(),is_sym: false,},
+                TreeLeaf {name: "depth-3.txt".to_string(),relative_path: "fake-depth/d1/d2/depth-3.txt".to_string(),is_dir: false,last_modified: None,size: None,window: None,window_match: None,display: "depth-3.txt".to_string(),is_sym: false,},
+                TreeLeaf {name: "depth-1.txt".to_string(),relative_path: "fake-depth/depth-1.txt".to_string(),is_dir: false,last_modified: None,size: None,window: None,window_match: None,display: "depth-1.txt".to_string(),is_sym: false,},
             ],
             paths_searched: 2,
+            is_truncated: false,
+            paths_unreadable: 0,
+            paths_hidden: 0,
+            paths_ignored: 0,
+            mounts: Vec::new(),
         };
         let mut output_crawl_results = crawl::crawl_directory(&ARGS)?;
         expected_crawl_results.paths.sort_by(SORT_RELATIVE);
@@ -301,9 +331,9 @@ mod tests {
     ///1 directory, 2 files
     /// ```
     /// 
-    /// Testing `[--no-gitignore | -g]` option which toggles usage of .gitignore file search patterns in results.
+    /// Testing `[--no-gitignore | -g]` option which toggles usage of .gitignore file search patterns in results, along with the hidden and ignored entries counted for `[--verbose]`.
     pub fn test_crawl_directory_gitignore() -> Result<(), DirError> {
-        const ROOT_TEST_DIR: &'static str = "fake-gitignore";
+        const ROOT_TEST_DIR: &str = "fake-gitignore";
         static USE_GITIGNORE_ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR]));
 
         let gitignore_contents = Some("target/\n*.d\nsecrets.txt");
@@ -318,31 +348,46 @@ mod tests {
         test_dir.generate("src/main.rs", no_contents)?;
         let mut expected_crawl_results = CrawlResults {
             paths: vec![
-                TreeLeaf {name: "README.md".to_string(),relative_path: "fake-gitignore/README.md".to_string(),is_dir: false,last_modified: None,size: None,window: None,display: "README.md".to_string(),is_sym: false,},
-                TreeLeaf {name: "src".to_string(),relative_path: "fake-gitignore/src".to_string(),is_dir: true,last_modified: None,size: None,window: None,display: "src".to_string(),is_sym: false,},
-                TreeLeaf {name: "main.rs".to_string(),relative_path: "fake-gitignore/src/main.rs".to_string(),is_dir: false,last_modified: None,size: None,window: None,display: "main.rs".to_string(),is_sym: false,},
+                TreeLeaf {name: "README.md".to_string(),relative_path: "fake-gitignore/README.md".to_string(),is_dir: false,last_modified: None,size: None,window: None,window_match: None,display: "README.md".to_string(),is_sym: false,},
+                TreeLeaf {name: "src".to_string(),relative_path: "fake-gitignore/src".to_string(),is_dir: true,last_modified: None,size: None,window: None,window_match: None,display: "src".to_string(),is_sym: false,},
+                TreeLeaf {name: "main.rs".to_string(),relative_path: "fake-gitignore/src/main.rs".to_string(),is_dir: false,last_modified: None,size: None,window: None,window_match: None,display: "main.rs".to_string(),is_sym: false,},
             ],
             paths_searched: 2,
+            is_truncated: false,
+            paths_unreadable: 0,
+            paths_hidden: 1,
+            paths_ignored: 4,
+            mounts: Vec::new(),
         };
         let mut output_crawl_results = crawl::crawl_directory(&USE_GITIGNORE_ARGS)?;
         expected_crawl_results.paths.sort_by(SORT_RELATIVE);
         output_crawl_results.paths.sort_by(SORT_RELATIVE);
         assert_eq!(output_crawl_results, expected_crawl_results);
 
+        // Skipped entries are only appended to the summary when verbose
+        static VERBOSE_ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--verbose", ROOT_TEST_DIR]));
+        assert_eq!(rippy::args::format_skipped_counts(&USE_GITIGNORE_ARGS, output_crawl_results.paths_hidden, output_crawl_results.paths_ignored), None);
+        assert_eq!(rippy::args::format_skipped_counts(&VERBOSE_ARGS, output_crawl_results.paths_hidden, output_crawl_results.paths_ignored).as_deref(), Some(" (1 hidden, 4 ignored)"));
+
         static NO_GITIGNORE_ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--no-gitignore", ROOT_TEST_DIR]));
         let mut expected_crawl_results = CrawlResults {
             paths: vec![
-                TreeLeaf {name: "01234.d".to_string(),relative_path: "fake-gitignore/01234.d".to_string(),is_dir: false,last_modified: None,size: None,window: None,display: "01234.d".to_string(),is_sym: false,},
-                TreeLeaf {name: "56789.d".to_string(),relative_path: "fake-gitignore/56789.d".to_string(),is_dir: false,last_modified: None,size: None,window: None,display: "56789.d".to_string(),is_sym: false,},
-                TreeLeaf {name: "README.md".to_string(),relative_path: "fake-gitignore/README.md".to_string(),is_dir: false,last_modified: None,size: None,window: None,display: "README.md".to_string(),is_sym: false,},
-                TreeLeaf {name: "secrets.txt".to_string(),relative_path: "fake-gitignore/secrets.txt".to_string(),is_dir: false,last_modified: None,size: None,window: None,display: "secrets.txt".to_string(),is_sym: false,},
-                TreeLeaf {name: "src".to_string(),relative_path: "fake-gitignore/src".to_string(),is_dir: true,last_modified: None,size: None,window: None,display: "src".to_string(),is_sym: false,},
-                TreeLeaf {name: "main.rs".to_string(),relative_path: "fake-gitignore/src/main.rs".to_string(),is_dir: false,last_modified: None,size: None,window: None,display: "main.rs".to_string(),is_sym: false,},
-                TreeLeaf {name: "target".to_string(),relative_path: "fake-gitignore/target".to_string(),is_dir: true,last_modified: None,size: None,window: None,display: "target".to_string(),is_sym: false,},
-                TreeLeaf {name: "t1".to_string(),relative_path: "fake-gitignore/target/t1".to_string(),is_dir: true,last_modified: None,size: None,window: None,display: "t1".to_string(),is_sym: false,},
-                TreeLeaf {name: "file.txt".to_string(),relative_path: "fake-gitignore/target/t1/file.txt".to_string(),is_dir: false,last_modified: None,size: None,window: None,display: "file.txt".to_string(),is_sym: false,},
+                TreeLeaf {name: "01234.d".to_string(),relative_path: "fake-gitignore/01234.d".to_string(),is_dir: false,last_modified: None,size: None,window: None,window_match: None,display: "01234.d".to_string(),is_sym: false,},
+                TreeLeaf {name: "56789.d".to_string(),relative_path: "fake-gitignore/56789.d".to_string(),is_dir: false,last_modified: None,size: None,window: None,window_match: None,display: "56789.d".to_string(),is_sym: false,},
+                TreeLeaf {name: "README.md".to_string(),relative_path: "fake-gitignore/README.md".to_string(),is_dir: false,last_modified: None,size: None,window: None,window_match: None,display: "README.md".to_string(),is_sym: false,},
+                TreeLeaf {name: "secrets.txt".to_string(),relative_path: "fake-gitignore/secrets.txt".to_string(),is_dir: false,last_modified: None,size: None,window: None,window_match: None,display: "secrets.txt".to_string(),is_sym: false,},
+                TreeLeaf {name: "src".to_string(),relative_path: "fake-gitignore/src".to_string(),is_dir: true,last_modified: None,size: None,window: None,window_match: None,display: "src".to_string(),is_sym: false,},
+                TreeLeaf {name: "main.rs".to_string(),relative_path: "fake-gitignore/src/main.rs".to_string(),is_dir: false,last_modified: None,size: None,window: None,window_match: None,display: "main.rs".to_string(),is_sym: false,},
+                TreeLeaf {name: "target".to_string(),relative_path: "fake-gitignore/target".to_string(),is_dir: true,last_modified: None,size: None,window: None,window_match: None,display: "target".to_string(),is_sym: false,},
+                TreeLeaf {name: "t1".to_string(),relative_path: "fake-gitignore/target/t1".to_string(),is_dir: true,last_modified: None,size: None,window: None,window_match: None,display: "t1".to_string(),is_sym: false,},
+                TreeLeaf {name: "file.txt".to_string(),relative_path: "fake-gitignore/target/t1/file.txt".to_string(),is_dir: false,last_modified: None,size: None,window: None,window_match: None,display: "file.txt".to_string(),is_sym: false,},
             ],
             paths_searched: 6,
+            is_truncated: false,
+            paths_unreadable: 0,
+            paths_hidden: 1,
+            paths_ignored: 0,
+            mounts: Vec::new(),
         };
         let mut output_crawl_results = crawl::crawl_directory(&NO_GITIGNORE_ARGS)?;
         expected_crawl_results.paths.sort_by(SORT_RELATIVE);
@@ -371,7 +416,7 @@ mod tests {
     /// 
     /// Testing functionality of `tree::build_tree_from_paths` and structure of the generated `Tree`.
     pub fn test_tree_building() -> Result<(), DirError> {
-        const ROOT_TEST_DIR: &'static str = "fake-tree";
+        const ROOT_TEST_DIR: &str = "fake-tree";
         static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR]));
 
         let no_contents: Option<&str> = None;
@@ -386,8 +431,8 @@ mod tests {
         test_dir.create_directory("emptydir")?;
         let crawl_results = crawl::crawl_directory(&ARGS);
         let received_output = tree::build_tree_from_paths(crawl_results.unwrap().paths, &ARGS);
-        let expected_output = Tree { display: "fake-tree".to_string(), name: "fake-tree".to_string(), path: None, entry_type: EntryType::Directory, last_modified: None, size: None, window: None, fmt_width: None, children: generate_tree_map([("d1".to_string(), Tree { display: "d1".to_string(), name: "d1".to_string(), path: None, entry_type: EntryType::Directory, last_modified: None, size: None, window: None, fmt_width: None, children: generate_tree_map([("f1.txt".to_string(), Tree { display: "f1.txt".to_string(), name: "f1.txt".to_string(), path: Some(PathBuf::from("fake-tree/d1/f1.txt")), entry_type: EntryType::File, last_modified: None, size: None, window: None, fmt_width: None, children: TreeMap::default() }), ("f2.txt".to_string(), Tree { display: "f2.txt".to_string(), name: "f2.txt".to_string(), path: Some(PathBuf::from("fake-tree/d1/f2.txt")), entry_type: EntryType::File, last_modified: None, size: None, window: None, fmt_width: None, children: TreeMap::default() })]) }), ("d2".to_string(), Tree { display: "d2".to_string(), name: "d2".to_string(), path: None, entry_type: EntryType::Directory, last_modified: None, size: None, window: None, fmt_width: None, children: generate_tree_map([("f1.txt".to_string(), Tree 
-        { display: "f1.txt".to_string(), name: "f1.txt".to_string(), path: Some(PathBuf::from("fake-tree/d2/f1.txt")), entry_type: EntryType::File, last_modified: None, size: None, window: None, fmt_width: None, children: TreeMap::default() }), ("f2.txt".to_string(), Tree { display: "f2.txt".to_string(), name: "f2.txt".to_string(), path: Some(PathBuf::from("fake-tree/d2/f2.txt")), entry_type: EntryType::File, last_modified: None, size: None, window: None, fmt_width: None, children: TreeMap::default() })]) }), ("emptydir".to_string(), Tree { display: "emptydir".to_string(), name: "emptydir".to_string(), path: None, entry_type: EntryType::Directory, last_modified: None, size: None, window: None, fmt_width: None, children: TreeMap::default() }), ("f1.txt".to_string(), Tree { display: "f1.txt".to_string(), name: "f1.txt".to_string(), path: Some(PathBuf::from("fake-tree/f1.txt")), entry_type: EntryType::File, last_modified: None, size: None, window: None, fmt_width: None, children: TreeMap::default() }), ("f2.txt".to_string(), Tree { display: "f2.txt".to_string(), name: "f2.txt".to_string(), path: Some(PathBuf::from("fake-tree/f2.txt")), entry_type: EntryType::File, last_modified: None, size: None, window: None, fmt_width: None, children: TreeMap::default() })]) };
+        let expected_output = Tree { display: "fake-tree".to_string(), name: "fake-tree".to_string(), path: None, entry_type: EntryType::Directory, last_modified: None, size: None, window: None, window_match: None, truncated: None, diff: None, fuzzy_score: None, fmt_width: None, children: generate_tree_map([("d1".to_string(), Tree { display: "d1".to_string(), name: "d1".to_string(), path: None, entry_type: EntryType::Directory, last_modified: None, size: None, window: None, window_match: None, truncated: None, diff: None, fuzzy_score: None, fmt_width: None, children: generate_tree_map([("f1.txt".to_string(), Tree { display: "f1.txt".to_string(), name: "f1.txt".to_string(), path: Some(PathBuf::from("fake-tree/d1/f1.txt")), entry_type: EntryType::File, last_modified: None, size: None, window: None, window_match: None, truncated: None, diff: None, fuzzy_score: None, fmt_width: None, children: TreeMap::default() }), ("f2.txt".to_string(), Tree { display: "f2.txt".to_string(), name: "f2.txt".to_string(), path: Some(PathBuf::from("fake-tree/d1/f2.txt")), entry_type: EntryType::File, last_modified: None, size: None, window: None, window_match: None, truncated: None, diff: None, fuzzy_score: None, fmt_width: None, children: TreeMap::default() })]) }), ("d2".to_string(), Tree { display: "d2".to_string(), name: "d2".to_string(), path: None, entry_type: EntryType::Directory, last_modified: None, size: None, window: None, window_match: None, truncated: None, diff: None, fuzzy_score: None, fmt_width: None, children: generate_tree_map([("f1.txt".to_string(), Tree 
+        { display: "f1.txt".to_string(), name: "f1.txt".to_string(), path: Some(PathBuf::from("fake-tree/d2/f1.txt")), entry_type: EntryType::File, last_modified: None, size: None, window: None, window_match: None, truncated: None, diff: None, fuzzy_score: None, fmt_width: None, children: TreeMap::default() }), ("f2.txt".to_string(), Tree { display: "f2.txt".to_string(), name: "f2.txt".to_string(), path: Some(PathBuf::from("fake-tree/d2/f2.txt")), entry_type: EntryType::File, last_modified: None, size: None, window: None, window_match: None, truncated: None, diff: None, fuzzy_score: None, fmt_width: None, children: TreeMap::default() })]) }), ("emptydir".to_string(), Tree { display: "emptydir".to_string(), name: "emptydir".to_string(), path: None, entry_type: EntryType::Directory, last_modified: None, size: None, window: None, window_match: None, truncated: None, diff: None, fuzzy_score: None, fmt_width: None, children: TreeMap::default() }), ("f1.txt".to_string(), Tree { display: "f1.txt".to_string(), name: "f1.txt".to_string(), path: Some(PathBuf::from("fake-tree/f1.txt")), entry_type: EntryType::File, last_modified: None, size: None, window: None, window_match: None, truncated: None, diff: None, fuzzy_score: None, fmt_width: None, children: TreeMap::default() }), ("f2.txt".to_string(), Tree { display: "f2.txt".to_string(), name: "f2.txt".to_string(), path: Some(PathBuf::from("fake-tree/f2.txt")), entry_type: EntryType::File, last_modified: None, size: None, window: None, window_match: None, truncated: None, diff: None, fuzzy_score: None, fmt_width: None, children: TreeMap::default() })]) };
         assert_eq!(expected_output, received_output);
         test_dir.clean()
     }
@@ -418,7 +463,7 @@ mod tests {
     /// 
     /// Testing functionality of `[--sort | -B]` and `[--reverse | -Z]` sorting tree by name in ascending and descending order.
     pub fn test_tree_sort_by_name() -> Result<(), DirError> {
-        const ROOT_TEST_DIR: &'static str = "fake-sort-name";
+        const ROOT_TEST_DIR: &str = "fake-sort-name";
         static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--sort", "name", ROOT_TEST_DIR]));
 
         let no_contents: Option<&str> = None;
@@ -437,7 +482,7 @@ mod tests {
         let crawl_results = crawl::crawl_directory(&ARGS)?;
         let received_output = tree::build_tree_from_paths(crawl_results.paths, &ARGS);
         let mut received_output: Vec<&Tree> = received_output.iter().collect();
-        received_output.sort_by(|a, b| (&ARGS.sort_by)(a, b));  
+        received_output.sort_by(|a, b| (ARGS.sort_by)(a, b));  
         let received_output: Vec<_> = received_output.iter().map(|t| t.name.clone()).collect();
         let mut expected_output: Vec<_> = vec!["1.txt", "1.txt", "2.txt", "3.txt", "3.txt", "5.txt", "E", "a", "a.txt", "aa.txt", "ab.txt", "b", "fake-sort-name", "z", "z.txt"].iter().map(|s| s.to_owned()).collect();
         assert_eq!(received_output, expected_output);
@@ -447,7 +492,7 @@ mod tests {
         let crawl_results = crawl::crawl_directory(&ARGS_REVERSED)?;
         let received_output = tree::build_tree_from_paths(crawl_results.paths, &ARGS_REVERSED);
         let mut received_output: Vec<&Tree> = received_output.iter().collect();
-        received_output.sort_by(|a, b| (&ARGS_REVERSED.sort_by)(a, b));  
+        received_output.sort_by(|a, b| (ARGS_REVERSED.sort_by)(a, b));  
         let received_output: Vec<_> = received_output.iter().map(|t| t.name.clone()).collect();
         expected_output.reverse();
         assert_eq!(received_output, expected_output);
@@ -469,7 +514,7 @@ mod tests {
     /// 
     /// Testing functionality of `[--sort | -B]` and `[--reverse | -Z]` sorting tree by size in ascending and descending order.
     pub fn test_tree_sort_by_size() -> Result<(), DirError> {
-        const ROOT_TEST_DIR: &'static str = "fake-sort-size";
+        const ROOT_TEST_DIR: &str = "fake-sort-size";
         static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--sort", "size", "-s", ROOT_TEST_DIR]));
 
         let test_dir = RootDirectory::new(ROOT_TEST_DIR);
@@ -478,18 +523,18 @@ mod tests {
         test_dir.generate("large.txt", Some("11111"))?;
         let crawl_results = crawl::crawl_directory(&ARGS);
         let mut received_output = tree::build_tree_from_paths(crawl_results.unwrap().paths, &ARGS);
-        received_output.children.sort_by(|_, a, _, b| (&ARGS.sort_by)(a, b));     
+        received_output.children.sort_by(|_, a, _, b| (ARGS.sort_by)(a, b));     
         let order_received: Vec<_> = received_output.children.clone().into_iter().collect();
-        let order_expected = vec![("small.txt".to_string(), Tree { display: "small.txt".to_string(), name: "small.txt".to_string(), path: Some(PathBuf::from("fake-sort-size/small.txt")), entry_type: EntryType::File, last_modified: None, size: Some(1), window: None, fmt_width: None, children: TreeMap::default() }), ("medium.txt".to_string(), Tree { display: "medium.txt".to_string(), name: "medium.txt".to_string(), path: Some(PathBuf::from("fake-sort-size/medium.txt")), entry_type: EntryType::File, last_modified: None, size: Some(3), window: None, fmt_width: None, children: TreeMap::default() }), ("large.txt".to_string(), Tree { display: "large.txt".to_string(), name: "large.txt".to_string(), path: Some(PathBuf::from("fake-sort-size/large.txt")), entry_type: EntryType::File, last_modified: None, size: Some(5), window: None, fmt_width: None, children: TreeMap::default() })];
+        let order_expected = vec![("small.txt".to_string(), Tree { display: "small.txt".to_string(), name: "small.txt".to_string(), path: Some(PathBuf::from("fake-sort-size/small.txt")), entry_type: EntryType::File, last_modified: None, size: Some(1), window: None, window_match: None, truncated: None, diff: None, fuzzy_score: None, fmt_width: None, children: TreeMap::default() }), ("medium.txt".to_string(), Tree { display: "medium.txt".to_string(), name: "medium.txt".to_string(), path: Some(PathBuf::from("fake-sort-size/medium.txt")), entry_type: EntryType::File, last_modified: None, size: Some(3), window: None, window_match: None, truncated: None, diff: None, fuzzy_score: None, fmt_width: None, children: TreeMap::default() }), ("large.txt".to_string(), Tree { display: "large.txt".to_string(), name: "large.txt".to_string(), path: Some(PathBuf::from("fake-sort-size/large.txt")), entry_type: EntryType::File, last_modified: None, size: Some(5), window: None, window_match: None, truncated: None, diff: None, fuzzy_score: None, fmt_width: None, children: TreeMap::default() })];
         assert_eq!(order_expected, order_received);
         
         // Test `--reverse` sorting order
         static ARGS_REVERSED: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--sort", "size", "--reverse", "-s", ROOT_TEST_DIR]));
         let crawl_results = crawl::crawl_directory(&ARGS_REVERSED);
         let mut received_output = tree::build_tree_from_paths(crawl_results.unwrap().paths, &ARGS_REVERSED);
-        received_output.children.sort_by(|_, a, _, b| (&ARGS_REVERSED.sort_by)(a, b));        
+        received_output.children.sort_by(|_, a, _, b| (ARGS_REVERSED.sort_by)(a, b));        
         let order_received: Vec<_> = received_output.children.clone().into_iter().collect();
-        let order_expected = vec![("large.txt".to_string(), Tree { display: "large.txt".to_string(), name: "large.txt".to_string(), path: Some(PathBuf::from("fake-sort-size/large.txt")), entry_type: EntryType::File, last_modified: None, size: Some(5), window: None, fmt_width: None, children: TreeMap::default() }), ("medium.txt".to_string(), Tree { display: "medium.txt".to_string(), name: "medium.txt".to_string(), path: Some(PathBuf::from("fake-sort-size/medium.txt")), entry_type: EntryType::File, last_modified: None, size: Some(3), window: None, fmt_width: None, children: TreeMap::default() }), ("small.txt".to_string(), Tree { display: "small.txt".to_string(), name: "small.txt".to_string(), path: Some(PathBuf::from("fake-sort-size/small.txt")), entry_type: EntryType::File, last_modified: None, size: Some(1), window: None, fmt_width: None, children: TreeMap::default() })];
+        let order_expected = vec![("large.txt".to_string(), Tree { display: "large.txt".to_string(), name: "large.txt".to_string(), path: Some(PathBuf::from("fake-sort-size/large.txt")), entry_type: EntryType::File, last_modified: None, size: Some(5), window: None, window_match: None, truncated: None, diff: None, fuzzy_score: None, fmt_width: None, children: TreeMap::default() }), ("medium.txt".to_string(), Tree { display: "medium.txt".to_string(), name: "medium.txt".to_string(), path: Some(PathBuf::from("fake-sort-size/medium.txt")), entry_type: EntryType::File, last_modified: None, size: Some(3), window: None, window_match: None, truncated: None, diff: None, fuzzy_score: None, fmt_width: None, children: TreeMap::default() }), ("small.txt".to_string(), Tree { display: "small.txt".to_string(), name: "small.txt".to_string(), path: Some(PathBuf::from("fake-sort-size/small.txt")), entry_type: EntryType::File, last_modified: None, size: Some(1), window: None, window_match: None, truncated: None, diff: None, fuzzy_score: None, fmt_width: None, children: TreeMap::default() })];
         assert_eq!(order_received, order_expected);
         test_dir.clean()
     }    
@@ -509,7 +554,7 @@ mod tests {
     /// 
     /// Testing functionality of `[--sort | -B]` and `[--reverse | -Z]` sorting tree by type in ascending and descending order.
     pub fn test_tree_sort_by_type() -> Result<(), DirError> {
-        const ROOT_TEST_DIR: &'static str = "fake-sort-type";
+        const ROOT_TEST_DIR: &str = "fake-sort-type";
         static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--sort", "type", ROOT_TEST_DIR]));
 
         let no_contents: Option<&str> = None;
@@ -520,20 +565,24 @@ mod tests {
         test_dir.create_directory("d2")?;
         let crawl_results = crawl::crawl_directory(&ARGS);
         let mut received_output = tree::build_tree_from_paths(crawl_results.unwrap().paths, &ARGS);
-        received_output.children.sort_by(|_, a, _, b| (&ARGS.sort_by)(a, b));     
+        // Entries are listed by name first so entries of the same type keep the same order regardless of filesystem order
+        received_output.children.sort_keys();
+        received_output.children.sort_by(|_, a, _, b| (ARGS.sort_by)(a, b));     
         let order_received: Vec<_> = received_output.children.clone().into_iter().collect();
         
-        let order_expected = vec![("d1".to_string(), Tree { display: "d1".to_string(), name: "d1".to_string(), path: None, entry_type: EntryType::Directory, last_modified: None, size: None, window: None, fmt_width: None, children: TreeMap::default() }), ("d2".to_string(), Tree { display: "d2".to_string(), name: "d2".to_string(), path: None, entry_type: EntryType::Directory, last_modified: None, size: None, window: None, fmt_width: None, children: TreeMap::default() }), ("f1.txt".to_string(), Tree { display: "f1.txt".to_string(), name: "f1.txt".to_string(), path: Some(PathBuf::from("fake-sort-type/f1.txt")), entry_type: EntryType::File, last_modified: None, size: None, window: None, fmt_width: None, children: TreeMap::default() }), ("f2.txt".to_string(), Tree { display: "f2.txt".to_string(), name: "f2.txt".to_string(), path: Some(PathBuf::from("fake-sort-type/f2.txt")), entry_type: EntryType::File, last_modified: None, size: None, window: None, fmt_width: None, children: TreeMap::default() })];
+        let order_expected = vec![("d1".to_string(), Tree { display: "d1".to_string(), name: "d1".to_string(), path: None, entry_type: EntryType::Directory, last_modified: None, size: None, window: None, window_match: None, truncated: None, diff: None, fuzzy_score: None, fmt_width: None, children: TreeMap::default() }), ("d2".to_string(), Tree { display: "d2".to_string(), name: "d2".to_string(), path: None, entry_type: EntryType::Directory, last_modified: None, size: None, window: None, window_match: None, truncated: None, diff: None, fuzzy_score: None, fmt_width: None, children: TreeMap::default() }), ("f1.txt".to_string(), Tree { display: "f1.txt".to_string(), name: "f1.txt".to_string(), path: Some(PathBuf::from("fake-sort-type/f1.txt")), entry_type: EntryType::File, last_modified: None, size: None, window: None, window_match: None, truncated: None, diff: None, fuzzy_score: None, fmt_width: None, children: TreeMap::default() }), ("f2.txt".to_string(), Tree { display: "f2.txt".to_string(), name: "f2.txt".to_string(), path: Some(PathBuf::from("fake-sort-type/f2.txt")), entry_type: EntryType::File, last_modified: None, size: None, window: None, window_match: None, truncated: None, diff: None, fuzzy_score: None, fmt_width: None, children: TreeMap::default() })];
         assert_eq!(order_expected, order_received);
         
         // Test `--reverse` sorting order
         static ARGS_REVERSED: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--sort", "type", "--reverse", ROOT_TEST_DIR]));
         let crawl_results = crawl::crawl_directory(&ARGS_REVERSED);
         let mut received_output = tree::build_tree_from_paths(crawl_results.unwrap().paths, &ARGS_REVERSED);
-        received_output.children.sort_by(|_, a, _, b| (&ARGS_REVERSED.sort_by)(a, b));        
+        // Entries are listed by name first so entries of the same type keep the same order regardless of filesystem order
+        received_output.children.sort_keys();
+        received_output.children.sort_by(|_, a, _, b| (ARGS_REVERSED.sort_by)(a, b));        
         let order_received: Vec<_> = received_output.children.clone().into_iter().collect();
 
-        let order_expected = vec![("f1.txt".to_string(), Tree { display: "f1.txt".to_string(), name: "f1.txt".to_string(), path: Some(PathBuf::from("fake-sort-type/f1.txt")), entry_type: EntryType::File, last_modified: None, size: None, window: None, fmt_width: None, children: TreeMap::default() }), ("f2.txt".to_string(), Tree { display: "f2.txt".to_string(), name: "f2.txt".to_string(), path: Some(PathBuf::from("fake-sort-type/f2.txt")), entry_type: EntryType::File, last_modified: None, size: None, window: None, fmt_width: None, children: TreeMap::default() }), ("d1".to_string(), Tree { display: "d1".to_string(), name: "d1".to_string(), path: None, entry_type: EntryType::Directory, last_modified: None, size: None, window: None, fmt_width: None, children: TreeMap::default() }), ("d2".to_string(), Tree { display: "d2".to_string(), name: "d2".to_string(), path: None, entry_type: EntryType::Directory, last_modified: None, size: None, window: None, fmt_width: None, children: TreeMap::default() })];
+        let order_expected = vec![("f1.txt".to_string(), Tree { display: "f1.txt".to_string(), name: "f1.txt".to_string(), path: Some(PathBuf::from("fake-sort-type/f1.txt")), entry_type: EntryType::File, last_modified: None, size: None, window: None, window_match: None, truncated: None, diff: None, fuzzy_score: None, fmt_width: None, children: TreeMap::default() }), ("f2.txt".to_string(), Tree { display: "f2.txt".to_string(), name: "f2.txt".to_string(), path: Some(PathBuf::from("fake-sort-type/f2.txt")), entry_type: EntryType::File, last_modified: None, size: None, window: None, window_match: None, truncated: None, diff: None, fuzzy_score: None, fmt_width: None, children: TreeMap::default() }), ("d1".to_string(), Tree { display: "d1".to_string(), name: "d1".to_string(), path: None, entry_type: EntryType::Directory, last_modified: None, size: None, window: None, window_match: None, truncated: None, diff: None, fuzzy_score: None, fmt_width: None, children: TreeMap::default() }), ("d2".to_string(), Tree { display: "d2".to_string(), name: "d2".to_string(), path: None, entry_type: EntryType::Directory, last_modified: None, size: None, window: None, window_match: None, truncated: None, diff: None, fuzzy_score: None, fmt_width: None, children: TreeMap::default() })];
         assert_eq!(order_received, order_expected);
         test_dir.clean()
     }        
@@ -554,7 +603,7 @@ mod tests {
     /// 
     /// Testing functionality of `[--sort | -B]` and `[--reverse | -Z]` sorting tree by date in ascending and descending order.
     pub fn test_tree_sort_by_date() -> Result<(), DirError> {
-        const ROOT_TEST_DIR: &'static str = "fake-sort-date";
+        const ROOT_TEST_DIR: &str = "fake-sort-date";
         static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--sort", "date", "--date", ROOT_TEST_DIR]));
 
         let no_contents: Option<&str> = None;
@@ -568,7 +617,7 @@ mod tests {
         test_dir.create_file("time-3.txt", no_contents)?;
         let crawl_results = crawl::crawl_directory(&ARGS)?;
         let mut received_output = tree::build_tree_from_paths(crawl_results.paths, &ARGS);
-        received_output.children.sort_by(|_, a, _, b| (&ARGS.sort_by)(a, b));     
+        received_output.children.sort_by(|_, a, _, b| (ARGS.sort_by)(a, b));     
         let order_received: Vec<_> = received_output.children.keys().collect();
         
         let mut order_expected = vec!["time-0.txt", "time-1.txt", "time-2.txt", "time-3.txt"];
@@ -578,7 +627,7 @@ mod tests {
         static ARGS_REVERSED: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--sort", "date", "--date", "--reverse", ROOT_TEST_DIR]));
         let crawl_results = crawl::crawl_directory(&ARGS_REVERSED)?;
         let mut received_output = tree::build_tree_from_paths(crawl_results.paths, &ARGS_REVERSED);
-        received_output.children.sort_by(|_, a, _, b| (&ARGS_REVERSED.sort_by)(a, b));        
+        received_output.children.sort_by(|_, a, _, b| (ARGS_REVERSED.sort_by)(a, b));        
         let order_received: Vec<_> = received_output.children.keys().collect();
         order_expected.reverse();
         assert_eq!(order_received, order_expected);
@@ -604,7 +653,7 @@ mod tests {
     /// 
     /// Testing functionality of `[--relative-path | -P]` and `[--full-path | -K]` displaying paths for entries.
     pub fn test_tree_display_pathing() -> Result<(), DirError> {
-        const ROOT_TEST_DIR: &'static str = "fake-paths";
+        const ROOT_TEST_DIR: &str = "fake-paths";
         static ARGS_RELATIVE: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--relative-path", ROOT_TEST_DIR]));
 
         let no_contents: Option<&str> = None;
@@ -661,7 +710,7 @@ mod tests {
     /// 
     /// Testing calculations for matched snippet windows and their format widths from the entries.
     pub fn test_window_and_fmt_width() -> Result<(), DirError> {
-        const ROOT_TEST_DIR: &'static str = "fake-fmt-width";
+        const ROOT_TEST_DIR: &str = "fake-fmt-width";
         static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--window-radius", "10", ROOT_TEST_DIR, "X"]));
         let target_contents: Option<&str> = Some("5---------4---------3---------2---------1---------X---------1---------2---------3---------4---------5");
         let no_contents: Option<&str> = None;
@@ -707,10 +756,11 @@ mod tests {
     /// 7 directories, 15 files
     /// ```
     /// 
-    /// Testing functionality of `tree::count_tree` on imbalanced input and empty directories.
+    /// Testing functionality of `tree::count_tree` and `Tree::counts` on imbalanced input and empty directories, and `tree::count_paths` producing identical counts without building the tree.
     pub fn test_count_tree() -> Result<(), DirError> {
-        const ROOT_TEST_DIR: &'static str = "fake-count";
+        const ROOT_TEST_DIR: &str = "fake-count";
         static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "--just-counts"]));        
+        static ARGS_INCLUDE: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "--just-counts", "-x", "f1.txt"]));
         let no_contents: Option<&str> = None;
         let test_dir = RootDirectory::new(ROOT_TEST_DIR);
         test_dir.create_directory("e1")?;
@@ -736,6 +786,21 @@ mod tests {
         let mut counts_received = tree::TreeCounts::new();
         tree::count_tree(&tree_output, &mut counts_received, true);
         assert_eq!(counts_received, tree::TreeCounts{ dir_count: 7, file_count: 15});
+        // The root is the only difference when it is included
+        assert_eq!(tree_output.counts(false), counts_received);
+        assert_eq!(tree_output.counts(true), tree::TreeCounts{ dir_count: 8, file_count: 15});
+
+        // Counting directly from the crawl results must agree with counting the built tree, including directories only implied by included files
+        for args in [&ARGS, &ARGS_INCLUDE] {
+            for skip_root in [true, false] {
+                let paths = crawl::crawl_directory(args)?.paths;
+                let mut counts_from_paths = tree::TreeCounts::new();
+                tree::count_paths(&paths, args, &mut counts_from_paths, skip_root);
+                let mut counts_from_tree = tree::TreeCounts::new();
+                tree::count_tree(&tree::build_tree_from_paths(paths, args), &mut counts_from_tree, skip_root);
+                assert_eq!(counts_from_paths, counts_from_tree);
+            }
+        }
         test_dir.clean()
     }
     
@@ -757,9 +822,9 @@ mod tests {
     /// 2 directories, 7 files
     /// ```
     /// 
-    /// Testing functionality of `[--gray | -G]` and `[--reverse | -z]` and `tree::write_tree_to_buf` for tree rendering.
-    pub fn test_write_tree_to_buf() -> Result<(), DirError> {
-        const ROOT_TEST_DIR: &'static str = "fake-presorted-writer";
+    /// Testing functionality of `[--gray | -G]` and `[--reverse | -z]` and `tree::write_tree` for tree rendering.
+    pub fn test_write_tree() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-presorted-writer";
         static ARGS_COLORED: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--reverse", ROOT_TEST_DIR]));
         let no_contents: Option<&str> = None;
         let test_dir = RootDirectory::new(ROOT_TEST_DIR);
@@ -779,9 +844,9 @@ mod tests {
         let mut buf_output = Vec::new();
         {
             let mut writer = std::io::BufWriter::new(&mut buf_output);
-            tree::write_tree_to_buf(&mut tree_output, "", 0, "", true, &ARGS_COLORED, &mut counts, &mut writer)?;
+            tree::write_tree(&mut tree_output, &ARGS_COLORED, &mut counts, &mut writer)?;
         }
-        let output_expected = " \u{1b}[1m\u{1b}[38;5;220mfake-presorted-writer\u{1b}[0m\n \u{1b}[38;5;220m├── \u{1b}[0m\u{1b}[1m\u{1b}[38;5;80msrc\u{1b}[0m\n \u{1b}[38;5;220m│\u{1b}[0m\u{a0}\u{a0} \u{1b}[38;5;80m├── \u{1b}[0mprog.rs\n \u{1b}[38;5;220m│\u{1b}[0m\u{a0}\u{a0} \u{1b}[38;5;80m╰── \u{1b}[0mmod.rs\n \u{1b}[38;5;220m├── \u{1b}[0mREADME.md\n \u{1b}[38;5;220m├── \u{1b}[0mLICENSE\n \u{1b}[38;5;220m├── \u{1b}[0mCargo.toml\n \u{1b}[38;5;220m╰── \u{1b}[0mCargo.lock\n\n";
+        let output_expected = " \u{1b}[1m\u{1b}[38;5;220mfake-presorted-writer\u{1b}[0m\n \u{1b}[38;5;220m├── \u{1b}[0m\u{1b}[1m\u{1b}[38;5;80msrc\u{1b}[0m\n \u{1b}[38;5;220m│\u{1b}[0m\u{a0}\u{a0} \u{1b}[38;5;80m├── \u{1b}[0m\u{1b}[38;5;150mprog.rs\u{1b}[0m\n \u{1b}[38;5;220m│\u{1b}[0m\u{a0}\u{a0} \u{1b}[38;5;80m╰── \u{1b}[0m\u{1b}[38;5;150mmod.rs\u{1b}[0m\n \u{1b}[38;5;220m├── \u{1b}[0m\u{1b}[38;5;252mREADME.md\u{1b}[0m\n \u{1b}[38;5;220m├── \u{1b}[0mLICENSE\n \u{1b}[38;5;220m├── \u{1b}[0m\u{1b}[38;5;179mCargo.toml\u{1b}[0m\n \u{1b}[38;5;220m╰── \u{1b}[0m\u{1b}[38;5;179mCargo.lock\u{1b}[0m\n\n";
         let output_received = String::from_utf8(buf_output).unwrap();
         assert_eq!(output_received, output_expected);
         assert_eq!(counts, tree::TreeCounts{ dir_count: 1, file_count: 6});
@@ -795,7 +860,7 @@ mod tests {
         let mut buf_output = Vec::new();
         {
             let mut writer = std::io::BufWriter::new(&mut buf_output);
-            tree::write_tree_to_buf(&mut tree_output, "", 0, "", true, &ARGS_NO_COLOR, &mut counts, &mut writer)?;
+            tree::write_tree(&mut tree_output, &ARGS_NO_COLOR, &mut counts, &mut writer)?;
         }
         let output_expected = " fake-presorted-writer\n ├── Cargo.lock\n ├── Cargo.toml\n ├── LICENSE\n ├── README.md\n ├── build.rs\n ├── notes.txt\n ╰── src\n \u{a0}\u{a0}  ├── mod.rs\n \u{a0}\u{a0}  ╰── prog.rs\n\n";
         let output_received = String::from_utf8(buf_output).unwrap();
@@ -824,8 +889,8 @@ mod tests {
     /// 
     /// Testing functionality of `[--output <FILENAME>]` to validate JSON output of generated tree with arbitrary content for sizes.
     pub fn test_write_tree_to_json() -> Result<(), DirError> {
-        const ROOT_TEST_DIR: &'static str = "fake-json";
-        const JSON_FILE: &'static str = "fake-json/fake-output.json";
+        const ROOT_TEST_DIR: &str = "fake-json";
+        const JSON_FILE: &str = "fake-json/fake-output.json";
         static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--output", JSON_FILE, ROOT_TEST_DIR]));
         let test_dir = RootDirectory::new(ROOT_TEST_DIR);
         test_dir.generate("dist/prog.exe", Some("X".repeat(370)))?;
@@ -838,13 +903,13 @@ mod tests {
         let mut crawl_results = crawl::crawl_directory(&ARGS)?; 
         crawl_results.paths.sort_by(SORT_RELATIVE);
         let tree_output = tree::build_tree_from_paths(crawl_results.paths, &ARGS);     
-        tree_output.write_to_json_file(&ARGS)?;
+        tree_output.write_to_json_file(JSON_FILE, &ARGS)?;
 
         // Read the file back and deserialize
-        let file_content = std::fs::read_to_string(&ARGS.output).unwrap();
+        let file_content = std::fs::read_to_string(&ARGS.output[0]).unwrap();
         let json_received: serde_json::Value = serde_json::from_str(&file_content).unwrap();
     
-        assert_eq!(json_received, json!({
+        assert_eq!(json_received["tree"], json!({
             "name": "fake-json",
             "entry_type": "Directory",
             "last_modified": null,
@@ -930,4 +995,3432 @@ mod tests {
         test_dir.clean()
     }
 
-}
\ No newline at end of file
+    #[test]
+    /// Runs `rippy fake-table --table --gray` on test directory to generate:
+    /// 
+    /// ```shell
+    ///  fake-table
+    ///  file  5.0 B  2024-11-18 19:58:45  a.txt
+    ///  file  1.5 K  2024-11-18 19:58:45  b.txt
+    ///  file   12 K  2024-11-18 19:58:45  c.txt
+    ///  dir       -  -                    d1
+    ///  file   10 B  2024-11-18 19:58:45  d.txt
+    /// 
+    /// 1 directory, 4 files
+    /// ```
+    /// 
+    /// Testing functionality of `[--table]` to ensure columns remain aligned for entries with differing size magnitudes.
+    pub fn test_write_table_to_buf() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-table";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--table", "--gray", ROOT_TEST_DIR]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("a.txt", Some("X".repeat(5)))?;
+        test_dir.generate("b.txt", Some("X".repeat(1_500)))?;
+        test_dir.generate("c.txt", Some("X".repeat(12_345)))?;
+        test_dir.generate("d1/d.txt", Some("X".repeat(10)))?;
+        let crawl_results = crawl::crawl_directory(&ARGS)?;
+        let mut counts = tree::TreeCounts::new();
+        let mut tree_output = tree::build_tree_from_paths(crawl_results.paths, &ARGS);
+        let mut buf_output = Vec::new();
+        tree::write_table_to_buf(&mut tree_output, &ARGS, &mut counts, &mut buf_output)?;
+        let output_received = String::from_utf8(buf_output).unwrap();
+        let rows: Vec<&str> = output_received.lines().skip(1).filter(|line| !line.is_empty()).collect();
+        assert_eq!(rows.len(), 5);
+        assert!(rows[0].contains("5.0 B") && rows[1].contains("1.5 K") && rows[2].contains(" 12 K"));
+        // Every name should begin at the same visible column regardless of the size magnitude
+        let name_columns: Vec<usize> = rows.iter().zip(["a.txt", "b.txt", "c.txt", "d1", "d.txt"]).map(|(row, name)| row[..row.find(name).unwrap()].chars().count()).collect();
+        assert!(name_columns.iter().all(|column| *column == name_columns[0]), "misaligned name columns: {name_columns:?}");
+        assert_eq!(counts, tree::TreeCounts{ dir_count: 1, file_count: 4});
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-count-root --count-root` on test directory to generate:
+    /// 
+    /// ```shell
+    ///  fake-count-root
+    ///  ├── a
+    ///  │   ╰── f1.txt
+    ///  ╰── f2.txt
+    /// 
+    /// 2 directories, 2 files
+    /// ```
+    /// 
+    /// Testing functionality of `[--count-root]` to include the scan root in the directory count for both `tree::count_tree` and rendering.
+    pub fn test_count_root() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-count-root";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR]));
+        static ARGS_COUNT_ROOT: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--count-root", ROOT_TEST_DIR]));
+        let no_contents: Option<&str> = None;
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("a/f1.txt", no_contents)?;
+        test_dir.generate("f2.txt", no_contents)?;
+        for (args, expected_dirs) in [(&ARGS, 1), (&ARGS_COUNT_ROOT, 2)] {
+            let crawl_results = crawl::crawl_directory(args)?;
+            let mut tree_output = tree::build_tree_from_paths(crawl_results.paths, args);
+            let mut counts_counted = tree::TreeCounts::new();
+            tree::count_tree(&tree_output, &mut counts_counted, !args.is_count_root);
+            let mut counts_rendered = tree::TreeCounts::new();
+            tree::write_tree(&mut tree_output, args, &mut counts_rendered, &mut Vec::new())?;
+            assert_eq!(counts_counted, tree::TreeCounts{ dir_count: expected_dirs, file_count: 2});
+            assert_eq!(counts_rendered, counts_counted);
+        }
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-legend --legend` to generate the color legend appended after the summary:
+    /// 
+    /// ```shell
+    /// legend: directory  file  executable  symlink  match
+    /// ```
+    /// 
+    /// Testing functionality of `[--legend]` to include each label using the active color schema, and to be suppressed when grayscale.
+    pub fn test_format_legend() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-legend";
+        let no_contents: Option<&str> = None;
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("f1.txt", no_contents)?;
+        let args = generate_args_from(vec!["rippy", "--legend", ROOT_TEST_DIR]);
+        let args_gray = generate_args_from(vec!["rippy", "--legend", "--gray", ROOT_TEST_DIR]);
+        let args_off = generate_args_from(vec!["rippy", ROOT_TEST_DIR]);
+        let legend = rippy::args::format_legend(&args).expect("legend should be rendered when enabled");
+        assert!(legend.contains(args.colors.dir.unwrap()));
+        for label in ["directory", "file", "executable", "symlink", "match"] {
+            assert!(legend.contains(label), "legend missing label '{label}': {legend}");
+        }
+        // Labels are styled exactly as the entries they describe, executables without bold like in the tree
+        let exec_color = args.colors.exec.unwrap();
+        assert!(legend.contains(&format!("  {exec_color}executable")), "executable should not be bold in the legend: {legend:?}");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            test_dir.generate("run.sh", Some("#!/bin/sh"))?;
+            std::fs::set_permissions(format!("{ROOT_TEST_DIR}/run.sh"), std::fs::Permissions::from_mode(0o755))?;
+            static ARGS_TREE: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--legend", ROOT_TEST_DIR]));
+            let mut tree_output = tree::build_tree_from_paths(crawl::crawl_directory(&ARGS_TREE)?.paths, &ARGS_TREE);
+            let mut writer: Vec<u8> = Vec::new();
+            tree::write_tree(&mut tree_output, &ARGS_TREE, &mut tree::TreeCounts::new(), &mut writer)?;
+            let output = String::from_utf8_lossy(&writer);
+            assert!(output.contains(&format!("\u{1b}[0m{exec_color}run.sh")), "executable should be drawn like its legend label: {output:?}");
+        }
+        assert_eq!(rippy::args::format_legend(&args_gray), None);
+        assert_eq!(rippy::args::format_legend(&args_off), None);
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-fuzzy --fuzzy mnrs` on test directory to generate:
+    /// 
+    /// ```shell
+    ///  fake-fuzzy
+    ///  ╰── src
+    ///      ╰── main.rs
+    /// 
+    /// 1 directory, 1 file
+    /// ```
+    /// 
+    /// Testing functionality of `[--fuzzy]` to keep only files whose names fuzzy match the query, dropping directories left empty.
+    pub fn test_fuzzy_filter() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-fuzzy";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--fuzzy", "mnrs", "--gray", ROOT_TEST_DIR]));
+        let no_contents: Option<&str> = None;
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("src/main.rs", no_contents)?;
+        test_dir.generate("cargo.toml", no_contents)?;
+        test_dir.generate("docs/readme.md", no_contents)?;
+
+        assert!(tree::fuzzy_match("mnrs", "main.rs").is_some());
+        assert!(tree::fuzzy_match("mnrs", "cargo.toml").is_none());
+        assert_eq!(tree::fuzzy_match("MNRS", "main.rs").map(|(_, indices)| indices), Some(vec![0, 3, 5, 6]));
+        assert!(tree::fuzzy_match("main", "main.rs").unwrap().0 > tree::fuzzy_match("main", "my_admin.rs").unwrap().0);
+
+        let crawl_results = crawl::crawl_directory(&ARGS)?;
+        let mut tree_output = tree::build_tree_from_paths(crawl_results.paths, &ARGS);
+        tree_output.retain_fuzzy(ARGS.fuzzy.as_deref().unwrap(), &ARGS);
+        let names: Vec<&str> = tree_output.iter().skip(1).map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["src", "main.rs"]);
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-exec-ext --exec-ext ps1` on test directory to generate:
+    /// 
+    /// ```shell
+    ///  fake-exec-ext
+    ///  ├── build.ps1
+    ///  ╰── notes.txt
+    /// 
+    /// 0 directories, 2 files
+    /// ```
+    /// 
+    /// Testing functionality of `[--exec-ext]` to style files with the listed extensions using the executable color.
+    pub fn test_exec_extensions() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-exec-ext";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--exec-ext", ".PS1,bat", ROOT_TEST_DIR]));
+        let no_contents: Option<&str> = None;
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("build.ps1", no_contents)?;
+        test_dir.generate("notes.txt", no_contents)?;
+        assert_eq!(ARGS.exec_extensions, vec!["ps1".to_string(), "bat".to_string()]);
+
+        let crawl_results = crawl::crawl_directory(&ARGS)?;
+        let mut tree_output = tree::build_tree_from_paths(crawl_results.paths, &ARGS);
+        let mut counts = tree::TreeCounts::new();
+        let mut writer: Vec<u8> = Vec::new();
+        tree::write_tree(&mut tree_output, &ARGS, &mut counts, &mut writer)?;
+        let output = String::from_utf8_lossy(&writer);
+        let exec_color = ARGS.colors.exec.unwrap();
+        assert!(output.lines().any(|line| line.contains(exec_color) && line.contains("build.ps1")));
+        assert!(output.lines().any(|line| !line.contains(exec_color) && line.contains("notes.txt")));
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-opaque --all --opaque-dir node_modules,.git` on test directory to generate:
+    /// 
+    /// ```shell
+    ///  fake-opaque
+    ///  ├── .git/ (contents hidden)
+    ///  ├── index.js
+    ///  ╰── node_modules/ (contents hidden)
+    /// 
+    /// 2 directories, 1 file
+    /// ```
+    /// 
+    /// Testing functionality of `[--opaque-dir]` to display the named directories as leaves without descending into them, even with `[--all]`.
+    pub fn test_opaque_dirs() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-opaque";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--all", "--gray", "--opaque-dir", "node_modules,.git/", ROOT_TEST_DIR]));
+        let no_contents: Option<&str> = None;
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("index.js", no_contents)?;
+        test_dir.generate("node_modules/left-pad/index.js", no_contents)?;
+        test_dir.generate(".git/objects/pack.idx", no_contents)?;
+
+        let crawl_results = crawl::crawl_directory(&ARGS)?;
+        let names: Vec<&str> = crawl_results.paths.iter().map(|leaf| leaf.name.as_str()).collect();
+        assert!(names.contains(&"node_modules") && names.contains(&".git"));
+        assert!(!names.contains(&"left-pad") && !names.contains(&"objects") && !names.contains(&"pack.idx"));
+
+        let mut tree_output = tree::build_tree_from_paths(crawl_results.paths, &ARGS);
+        let mut counts = tree::TreeCounts::new();
+        let mut writer: Vec<u8> = Vec::new();
+        tree::write_tree(&mut tree_output, &ARGS, &mut counts, &mut writer)?;
+        let output = String::from_utf8_lossy(&writer);
+        assert!(output.contains("node_modules/ (contents hidden)"));
+        assert_eq!(counts, tree::TreeCounts{ dir_count: 2, file_count: 1});
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `crawl::search` for the pattern `needle` on test directory:
+    /// 
+    /// ```shell
+    ///  fake-search-api
+    ///  ├── a.txt
+    ///  ╰── b.txt
+    /// ```
+    /// 
+    /// Testing functionality of `crawl::search` to return the path, line number, line text, byte range and window of every match found.
+    pub fn test_search_matches() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-search-api";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--gray", "-R", "4", ROOT_TEST_DIR, "needle"]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("a.txt", Some("hay\nhay needle hay\nhay\nlast needle"))?;
+        test_dir.generate("b.txt", Some("nothing to see"))?;
+
+        let mut matches = crawl::search(&ARGS)?;
+        matches.sort_by(|a, b| (&a.path, a.line_number).cmp(&(&b.path, b.line_number)));
+        assert_eq!(matches.len(), 2);
+        assert!(matches.iter().all(|m| m.path.ends_with("a.txt")));
+        assert_eq!(matches[0].line_number, 2);
+        assert_eq!(matches[0].line, "hay needle hay");
+        assert_eq!(matches[0].byte_range, 8..14);
+        assert_eq!(matches[0].window, "hay needle hay");
+        assert_eq!(matches[1].line_number, 4);
+        assert_eq!(matches[1].line, "last needle");
+        assert_eq!(matches[1].window, "ast needle");
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-mark --gray -R 4 --ellipsis ~ --mark ">>>" "<<<" needle` on test directory to generate:
+    /// 
+    /// ```shell
+    ///  fake-mark
+    ///  ╰── a.txt ~hay >>>needle<<< hay~
+    /// 
+    /// 1 match, 1 searched
+    /// ```
+    /// 
+    /// Testing functionality of `[--ellipsis]` and `[--mark]` to customize the elision and match markers used by snippet windows in grayscale output.
+    pub fn test_snippet_ellipsis_and_mark() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-mark";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--gray", "-R", "4", "--ellipsis", "~", "--mark", ">>>", "<<<", ROOT_TEST_DIR, "needle"]));
+        static ARGS_DEFAULT: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--gray", "-R", "4", ROOT_TEST_DIR, "needle"]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("a.txt", Some("some hay needle hay here"))?;
+
+        let crawl_results = crawl::crawl_directory(&ARGS)?;
+        assert_eq!(crawl_results.paths[0].window.as_deref(), Some("~hay >>>needle<<< hay~"));
+        let crawl_results = crawl::crawl_directory(&ARGS_DEFAULT)?;
+        assert_eq!(crawl_results.paths[0].window.as_deref(), Some("...hay needle hay..."));
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-word-radius --gray --word-radius 2 needle` on test directory to generate:
+    /// 
+    /// ```shell
+    ///  fake-word-radius
+    ///  ├── a.txt ...three four needle five six...
+    ///  ╰── b.txt one needles two
+    /// 
+    /// 2 matches, 2 searched
+    /// ```
+    /// 
+    /// Testing functionality of `[--word-radius]` to bound snippet windows by whole words on each side of the match.
+    pub fn test_word_radius() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-word-radius";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--gray", "--word-radius", "2", ROOT_TEST_DIR, "needle"]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("a.txt", Some("one two three four needle five six seven"))?;
+        test_dir.generate("b.txt", Some("  one needles two  "))?;
+
+        let crawl_results = crawl::crawl_directory(&ARGS)?;
+        let windows: std::collections::HashMap<String, String> = crawl_results.paths.into_iter().map(|leaf| (leaf.name, leaf.window.unwrap_or_default())).collect();
+        assert_eq!(windows["a.txt"], "...three four needle five six...");
+        assert_eq!(windows["b.txt"], "one needles two");
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-multiline --gray --multiline "start.end"` on test directory to generate:
+    /// 
+    /// ```shell
+    ///  fake-multiline
+    ///  ╰── a.txt alpha start
+    ///        end omega
+    /// 
+    /// 1 match, 1 searched
+    /// ```
+    /// 
+    /// Testing functionality of `[--multiline]` to match patterns spanning lines and render the window lines indented beneath the file.
+    pub fn test_multiline_search() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-multiline";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--gray", "--multiline", ROOT_TEST_DIR, "start.end"]));
+        static ARGS_SINGLE: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--gray", ROOT_TEST_DIR, "start.end"]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("a.txt", Some("alpha start\nend omega"))?;
+
+        assert!(crawl::crawl_directory(&ARGS_SINGLE)?.paths.is_empty());
+        let crawl_results = crawl::crawl_directory(&ARGS)?;
+        assert_eq!(crawl_results.paths[0].window.as_deref(), Some("alpha start\nend omega"));
+
+        let mut tree_output = tree::build_tree_from_paths(crawl_results.paths, &ARGS);
+        tree_output.calculate_fmt_width();
+        let mut counts = tree::TreeCounts::new();
+        let mut writer: Vec<u8> = Vec::new();
+        tree::write_tree(&mut tree_output, &ARGS, &mut counts, &mut writer)?;
+        let output = String::from_utf8_lossy(&writer);
+        let lines: Vec<&str> = output.lines().collect();
+        assert!(lines[1].ends_with("a.txt alpha start"));
+        assert_eq!(lines[2].trim(), "end omega");
+        assert!(lines[2].starts_with("      "));
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-collapse --collapse` on test directory to generate:
+    /// 
+    /// ```shell
+    ///  fake-collapse
+    ///  ├── a/b/c
+    ///  │   ╰── file.txt
+    ///  ╰── d
+    ///      ├── e
+    ///      │   ╰── f2.txt
+    ///      ╰── f1.txt
+    /// 
+    /// 5 directories, 3 files
+    /// ```
+    /// 
+    /// Testing functionality of `[--collapse]` to merge single child directory chains onto one line while leaving directories with files intact.
+    pub fn test_collapse_chains() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-collapse";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--gray", "--collapse", ROOT_TEST_DIR]));
+        let no_contents: Option<&str> = None;
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("a/b/c/file.txt", no_contents)?;
+        test_dir.generate("d/f1.txt", no_contents)?;
+        test_dir.generate("d/e/f2.txt", no_contents)?;
+
+        let crawl_results = crawl::crawl_directory(&ARGS)?;
+        let mut tree_output = tree::build_tree_from_paths(crawl_results.paths, &ARGS);
+        let mut counts = tree::TreeCounts::new();
+        counts.dir_count += tree_output.collapse_chains(&ARGS);
+        let mut writer: Vec<u8> = Vec::new();
+        tree::write_tree(&mut tree_output, &ARGS, &mut counts, &mut writer)?;
+        let output = String::from_utf8_lossy(&writer);
+        let lines: Vec<&str> = output.lines().map(|line| line.trim_end()).collect();
+        assert_eq!(lines[1], " ├── a/b/c");
+        assert!(lines.iter().any(|line| line.ends_with("── d")));
+        assert!(lines.iter().any(|line| line.ends_with("── e")));
+        assert_eq!(counts, tree::TreeCounts{ dir_count: 5, file_count: 3});
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-max-entries --max-entries 10` on a test directory containing 10 directories of 10 files each.
+    /// 
+    /// Testing functionality of `[--max-entries]` to stop the crawl early once the entry budget is exhausted and report the results as truncated.
+    pub fn test_max_entries() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-max-entries";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--max-entries", "10", ROOT_TEST_DIR]));
+        static ARGS_UNBOUNDED: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR]));
+        let no_contents: Option<&str> = None;
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        for d in 0..10 {
+            for f in 0..10 {
+                test_dir.generate(format!("d{d}/f{f}.txt"), no_contents)?;
+            }
+        }
+        let crawl_results = crawl::crawl_directory(&ARGS_UNBOUNDED)?;
+        assert!(!crawl_results.is_truncated);
+        assert_eq!(crawl_results.paths.len(), 110);
+
+        let crawl_results = crawl::crawl_directory(&ARGS)?;
+        assert!(crawl_results.is_truncated);
+        assert!(crawl_results.paths.len() <= 10, "visited {} entries with a budget of 10", crawl_results.paths.len());
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-gitignore-negate` on test directory containing a `.gitignore` with `*.log` and `!keep.log` to generate:
+    /// 
+    /// ```shell
+    ///  fake-gitignore-negate
+    ///  ├── keep.log
+    ///  ├── notes.txt
+    ///  ╰── sub
+    ///      ├── keep.log
+    ///      ╰── notes.txt
+    /// 
+    /// 1 directory, 4 files
+    /// ```
+    /// 
+    /// Testing functionality of `.gitignore` negation patterns re-including otherwise ignored files, including beneath a nested `.gitignore` that must not replace the parent rules.
+    pub fn test_gitignore_negation() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-gitignore-negate";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR]));
+        let no_contents: Option<&str> = None;
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.create_file(".gitignore", Some("*.log\n!keep.log\n"))?;
+        test_dir.generate("debug.log", no_contents)?;
+        test_dir.generate("keep.log", no_contents)?;
+        test_dir.generate("notes.txt", no_contents)?;
+        test_dir.create_file("sub/.gitignore", Some("scratch.txt\n"))?;
+        test_dir.generate("sub/trace.log", no_contents)?;
+        test_dir.generate("sub/keep.log", no_contents)?;
+        test_dir.generate("sub/notes.txt", no_contents)?;
+        test_dir.generate("sub/scratch.txt", no_contents)?;
+
+        let crawl_results = crawl::crawl_directory(&ARGS)?;
+        let mut received: Vec<&str> = crawl_results.paths.iter().map(|leaf| leaf.relative_path.as_str()).collect();
+        received.sort();
+        let expected = vec![
+            "fake-gitignore-negate/keep.log",
+            "fake-gitignore-negate/notes.txt",
+            "fake-gitignore-negate/sub",
+            "fake-gitignore-negate/sub/keep.log",
+            "fake-gitignore-negate/sub/notes.txt",
+        ];
+        assert_eq!(received, expected);
+        test_dir.clean()
+    }
+
+    #[test]
+    #[cfg(unix)]
+    /// Runs `rippy fake-shallow-size --shallow-size` and `rippy fake-shallow-size -S -U` on test directory to generate:
+    /// 
+    /// ```shell
+    ///  fake-shallow-size
+    ///  ╰── (4.1 K) d1
+    ///      ├── (1.0 K) a.txt
+    ///      ╰── (2.0 K) b.txt
+    /// ```
+    /// 
+    /// Testing functionality of `[--shallow-size]` to use the size of the directory entry itself rather than the recursive total of its contents.
+    pub fn test_shallow_size() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-shallow-size";
+        static ARGS_SHALLOW: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--shallow-size", ROOT_TEST_DIR]));
+        static ARGS_RECURSIVE: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "-S", "-U", ROOT_TEST_DIR]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("d1/a.txt", Some("a".repeat(1000)))?;
+        test_dir.generate("d1/b.txt", Some("b".repeat(2000)))?;
+        assert!(ARGS_SHALLOW.show_size && ARGS_SHALLOW.is_dir_detail);
+        let inode_size = std::fs::metadata(test_dir.join("d1"))?.len();
+
+        let crawl_results = crawl::crawl_directory(&ARGS_SHALLOW)?;
+        let tree_output = tree::build_tree_from_paths(crawl_results.paths, &ARGS_SHALLOW);
+        assert_eq!(tree_output.children["d1"].size, Some(inode_size));
+
+        let crawl_results = crawl::crawl_directory(&ARGS_RECURSIVE)?;
+        let mut tree_output = tree::build_tree_from_paths(crawl_results.paths, &ARGS_RECURSIVE);
+        tree_output.calculate_sizes();
+        assert_eq!(tree_output.children["d1"].size, Some(3000));
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-ext-colors` on test directory to generate:
+    /// 
+    /// ```shell
+    ///  fake-ext-colors
+    ///  ├── README.md
+    ///  ├── data.unknownext
+    ///  ╰── main.rs
+    /// 
+    /// 0 directories, 3 files
+    /// ```
+    /// 
+    /// Testing functionality of the default per-extension file colors along with `[--ext-color]` overrides and `[--no-ext-colors]`.
+    pub fn test_ext_colors() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-ext-colors";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR]));
+        static ARGS_OVERRIDE: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--ext-color", ".RS=196", ROOT_TEST_DIR]));
+        static ARGS_DISABLED: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--no-ext-colors", ROOT_TEST_DIR]));
+        let no_contents: Option<&str> = None;
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("main.rs", no_contents)?;
+        test_dir.generate("README.md", no_contents)?;
+        test_dir.generate("data.unknownext", no_contents)?;
+
+        let rs_color = tcolor::default_ext_color("rs").unwrap();
+        let md_color = tcolor::default_ext_color("md").unwrap();
+        assert_ne!(rs_color, md_color);
+        assert_eq!(tcolor::default_ext_color("unknownext"), None);
+
+        let render = |args: &'static rippy::args::RippyArgs| -> Result<String, DirError> {
+            let crawl_results = crawl::crawl_directory(args)?;
+            let mut tree_output = tree::build_tree_from_paths(crawl_results.paths, args);
+            let mut writer: Vec<u8> = Vec::new();
+            tree::write_tree(&mut tree_output, args, &mut tree::TreeCounts::new(), &mut writer)?;
+            Ok(String::from_utf8_lossy(&writer).to_string())
+        };
+        let line_for = |output: &str, name: &str| output.lines().find(|line| line.contains(name)).unwrap_or_default().to_string();
+        let output = render(&ARGS)?;
+        assert!(line_for(&output, "main.rs").contains(&format!("{rs_color}main.rs")));
+        assert!(line_for(&output, "README.md").contains(&format!("{md_color}README.md")));
+        assert!(line_for(&output, "data.unknownext").ends_with("\u{1b}[0mdata.unknownext"));
+
+        let output = render(&ARGS_OVERRIDE)?;
+        assert!(line_for(&output, "main.rs").contains("\x1b[38;5;196mmain.rs"));
+        let output = render(&ARGS_DISABLED)?;
+        assert!(line_for(&output, "main.rs").ends_with("\u{1b}[0mmain.rs"));
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-ascii-space --ascii-space` on test directory to generate:
+    /// 
+    /// ```shell
+    ///  fake-ascii-space
+    ///  ├── d1
+    ///  │   ╰── f1.txt
+    ///  ╰── f2.txt
+    /// 
+    /// 1 directory, 2 files
+    /// ```
+    /// 
+    /// Testing functionality of `[--ascii-space]` to indent using regular spaces rather than non-breaking spaces.
+    pub fn test_ascii_space() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-ascii-space";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--ascii-space", ROOT_TEST_DIR]));
+        static ARGS_DEFAULT: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR]));
+        let no_contents: Option<&str> = None;
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("d1/f1.txt", no_contents)?;
+        test_dir.generate("f2.txt", no_contents)?;
+        for (args, has_nbsp) in [(&ARGS, false), (&ARGS_DEFAULT, true)] {
+            let crawl_results = crawl::crawl_directory(args)?;
+            let mut tree_output = tree::build_tree_from_paths(crawl_results.paths, args);
+            let mut writer: Vec<u8> = Vec::new();
+            tree::write_tree(&mut tree_output, args, &mut tree::TreeCounts::new(), &mut writer)?;
+            let output = String::from_utf8(writer).unwrap();
+            assert_eq!(output.contains('\u{00A0}'), has_nbsp);
+        }
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-select --select` on test directory without a terminal attached:
+    /// 
+    /// ```shell
+    ///  fake-select
+    ///  ├── d1
+    ///  │   ╰── f1.txt
+    ///  ╰── f2.txt
+    /// ```
+    /// 
+    /// Testing functionality of `[--select]` to leave tree building unchanged, flatten rows in render order and fail gracefully when not interactive.
+    pub fn test_select_fallback() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-select";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--select", ROOT_TEST_DIR]));
+        static ARGS_DEFAULT: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR]));
+        let no_contents: Option<&str> = None;
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("d1/f1.txt", no_contents)?;
+        test_dir.generate("f2.txt", no_contents)?;
+
+        let mut crawl_results = crawl::crawl_directory(&ARGS)?;
+        crawl_results.paths.sort_by(SORT_RELATIVE);
+        let tree_select = tree::build_tree_from_paths(crawl_results.paths, &ARGS);
+        let mut crawl_results = crawl::crawl_directory(&ARGS_DEFAULT)?;
+        crawl_results.paths.sort_by(SORT_RELATIVE);
+        let tree_default = tree::build_tree_from_paths(crawl_results.paths, &ARGS_DEFAULT);
+        assert_eq!(tree_select, tree_default);
+
+        let rows = rippy::select::collect_rows(&tree_select, &ARGS);
+        let paths: Vec<&str> = rows.iter().map(|row| row.path.as_str()).collect();
+        assert_eq!(paths, vec!["fake-select/d1", "fake-select/d1/f1.txt", "fake-select/f2.txt"]);
+        assert_eq!(rows[1].line, "│   ╰── f1.txt");
+
+        let output = std::process::Command::new(env!("CARGO_BIN_EXE_rippy"))
+            .args(["--select", ROOT_TEST_DIR])
+            .stdin(std::process::Stdio::null())
+            .output()?;
+        assert!(!output.status.success());
+        assert!(String::from_utf8_lossy(&output.stderr).contains("requires a terminal"));
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-multi-output --output fake-multi-output/tree.json --output fake-multi-output/tree.md` on test directory containing:
+    /// 
+    /// ```shell
+    ///  fake-multi-output
+    ///  ├── src
+    ///  │   ╰── main.rs
+    ///  ╰── README.md
+    /// ```
+    /// 
+    /// Testing functionality of a repeated `[--output <FILENAME>]` to export the same tree to each file using the format inferred from its extension.
+    pub fn test_write_multiple_outputs() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-multi-output";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "-I", "tree.*", "--output", "fake-multi-output/tree.json", "--output", "fake-multi-output/tree.md", "-O", "fake-multi-output/tree.yaml", "-O", "fake-multi-output/tree.dot", ROOT_TEST_DIR]));
+        let no_contents: Option<&str> = None;
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("src/main.rs", no_contents)?;
+        test_dir.generate("README.md", no_contents)?;
+        assert_eq!(ARGS.output.len(), 4);
+
+        let crawl_results = crawl::crawl_directory(&ARGS)?;
+        let tree_output = tree::build_tree_from_paths(crawl_results.paths, &ARGS);
+        for output in ARGS.output.iter() {
+            tree_output.write_to_file(output, &ARGS)?;
+        }
+
+        let json_received: serde_json::Value = serde_json::from_str(&std::fs::read_to_string("fake-multi-output/tree.json")?).unwrap();
+        assert_eq!(json_received["tree"]["name"], "fake-multi-output");
+        let child_names: Vec<&str> = json_received["tree"]["children"].as_array().unwrap().iter().map(|c| c["name"].as_str().unwrap()).collect();
+        assert!(child_names.contains(&"src") && child_names.contains(&"README.md"));
+
+        let markdown = std::fs::read_to_string("fake-multi-output/tree.md")?;
+        assert_eq!(markdown, "# fake-multi-output\n\n- README.md\n- src/\n  - main.rs\n");
+
+        let yaml = std::fs::read_to_string("fake-multi-output/tree.yaml")?;
+        assert!(yaml.starts_with("name: \"fake-multi-output\"\nentry_type: \"Directory\"\n"));
+        assert!(yaml.contains("\n    children:\n      - name: \"main.rs\"\n"));
+
+        let dot = std::fs::read_to_string("fake-multi-output/tree.dot")?;
+        assert!(dot.starts_with("digraph rippy {") && dot.contains("label=\"main.rs\"") && dot.contains("n0 -> n1;"));
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-rippyignore --no-gitignore` on test directory containing a `.rippyignore` with `generated/` to generate:
+    /// 
+    /// ```shell
+    ///  fake-rippyignore
+    ///  ├── main.rs
+    ///  ╰── notes.log
+    /// 
+    /// 0 directories, 2 files
+    /// ```
+    /// 
+    /// Testing functionality of `.rippyignore` files filtering results independently of `[--no-gitignore]`, and `[--no-rippyignore]` to disable them.
+    pub fn test_rippyignore() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-rippyignore";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--no-gitignore", ROOT_TEST_DIR]));
+        static ARGS_DISABLED: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--no-gitignore", "--no-rippyignore", ROOT_TEST_DIR]));
+        let no_contents: Option<&str> = None;
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.create_file(".rippyignore", Some("generated/\n"))?;
+        test_dir.create_file(".gitignore", Some("*.log\n"))?;
+        test_dir.generate("main.rs", no_contents)?;
+        test_dir.generate("notes.log", no_contents)?;
+        test_dir.generate("generated/bindings.rs", no_contents)?;
+
+        let names = |args: &'static rippy::args::RippyArgs| -> Result<Vec<String>, DirError> {
+            let mut names: Vec<String> = crawl::crawl_directory(args)?.paths.into_iter().map(|leaf| leaf.name).collect();
+            names.sort();
+            Ok(names)
+        };
+        assert_eq!(names(&ARGS)?, vec!["main.rs", "notes.log"]);
+        assert_eq!(names(&ARGS_DISABLED)?, vec!["bindings.rs", "generated", "main.rs", "notes.log"]);
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-long-line --gray -R 6 needle` on a test directory containing an 8 MB single line file with the match near its end, and a multibyte file with the radius landing mid-character.
+    /// 
+    /// Testing functionality of snippet extraction to only scan a bounded neighborhood of the match on very long lines while producing the same snippets.
+    pub fn test_snippet_long_line() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-long-line";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--gray", "-R", "6", ROOT_TEST_DIR, "needle"]));
+        static ARGS_WORDS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--gray", "--word-radius", "1", ROOT_TEST_DIR, "needle"]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        let huge_line = format!("{} abcdefneedleghijkl {}", "x".repeat(8_000_000), "y".repeat(100_000));
+        test_dir.generate("huge.min.js", Some(huge_line))?;
+        test_dir.generate("multibyte.txt", Some("\u{e9}\u{e9}\u{e9}\u{e9} needle \u{e9}\u{e9}\u{e9}\u{e9}\nnext line"))?;
+        test_dir.generate("short.txt", Some("ab needle cd\nnext line"))?;
+
+        let start = std::time::Instant::now();
+        let windows: std::collections::HashMap<String, String> = crawl::crawl_directory(&ARGS)?.paths.into_iter().map(|leaf| (leaf.name, leaf.window.unwrap_or_default())).collect();
+        let elapsed = start.elapsed();
+        assert_eq!(windows["huge.min.js"], "...abcdefneedleghijkl...");
+        assert_eq!(windows["multibyte.txt"], "...\u{e9}\u{e9}\u{e9} needle \u{e9}\u{e9}\u{e9}...");
+        assert_eq!(windows["short.txt"], "ab needle cd");
+
+        let windows: std::collections::HashMap<String, String> = crawl::crawl_directory(&ARGS_WORDS)?.paths.into_iter().map(|leaf| (leaf.name, leaf.window.unwrap_or_default())).collect();
+        assert!(windows["huge.min.js"].starts_with("...x") && windows["huge.min.js"].ends_with("y..."));
+        assert!(windows["huge.min.js"].contains(" abcdefneedleghijkl ") && windows["huge.min.js"].len() < 10_000);
+        assert!(elapsed < Duration::from_secs(10), "snippet extraction took {elapsed:?}");
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy`, `rippy "fake-pattern-only-TODO"` and `rippy fake-default-dir "TODO"` to check how the positional arguments are resolved.
+    /// 
+    /// Testing functionality of the optional `[DIRECTORY]` positional defaulting to the current directory, including when only a pattern is provided.
+    pub fn test_default_directory() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-default-dir";
+        let no_contents: Option<&str> = None;
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("f1.txt", no_contents)?;
+
+        let args = generate_args_from(vec!["rippy"]);
+        assert_eq!(args.directory, PathBuf::from("."));
+        assert!(!args.is_search);
+
+        let args = generate_args_from(vec!["rippy", "fake-pattern-only-TODO"]);
+        assert_eq!(args.directory, PathBuf::from("."));
+        assert!(args.is_search);
+        assert_eq!(args.pattern.as_ref().map(|re| re.as_str()), Some("fake-pattern-only-TODO"));
+
+        let args = generate_args_from(vec!["rippy", "-", "TODO"]);
+        assert_eq!(args.directory, PathBuf::from("."));
+        assert_eq!(args.pattern.as_ref().map(|re| re.as_str()), Some("TODO"));
+
+        let args = generate_args_from(vec!["rippy", ROOT_TEST_DIR, "TODO"]);
+        assert_eq!(args.directory, PathBuf::from(ROOT_TEST_DIR));
+        assert_eq!(args.pattern.as_ref().map(|re| re.as_str()), Some("TODO"));
+
+        let args = generate_args_from(vec!["rippy", ROOT_TEST_DIR]);
+        assert_eq!(args.directory, PathBuf::from(ROOT_TEST_DIR));
+        assert!(!args.is_search);
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-unreadable needle` with and without `[--show-unreadable]` on a test directory containing a matching file and a binary file that cannot be read as text.
+    /// 
+    /// Testing functionality of files that fail to be read while searching being counted and optionally shown rather than silently treated as having no match.
+    pub fn test_unreadable_files() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-unreadable";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--gray", ROOT_TEST_DIR, "needle"]));
+        static ARGS_SHOWN: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--gray", "--show-unreadable", ROOT_TEST_DIR, "needle"]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("found.txt", Some("the needle is here"))?;
+        test_dir.generate("missing.txt", Some("nothing to see"))?;
+        std::fs::write(test_dir.root().join("image.bin"), [0x89, 0x50, 0x4e, 0x47, 0xff, 0xfe, 0x00, 0xc3])?;
+
+        let result = crawl::crawl_directory(&ARGS)?;
+        assert_eq!(result.paths_unreadable, 1);
+        assert_eq!(result.paths.iter().map(|leaf| leaf.name.as_str()).collect::<Vec<_>>(), vec!["found.txt"]);
+
+        let mut result = crawl::crawl_directory(&ARGS_SHOWN)?;
+        result.paths.sort_by(SORT_RELATIVE);
+        assert_eq!(result.paths_unreadable, 1);
+        assert_eq!(result.paths.iter().map(|leaf| leaf.display.as_str()).collect::<Vec<_>>(), vec!["found.txt", "image.bin [unreadable]"]);
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-relative-to/project/src --relative-to fake-relative-to/project` and with an unrelated base on a test directory containing a nested source directory.
+    /// 
+    /// Testing functionality of `[--relative-to]` displaying paths relative to a parent base rather than the scan root, and falling back to absolute paths when the base is not an ancestor.
+    pub fn test_relative_to() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-relative-to";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--gray", "fake-relative-to/project/src", "--relative-to", "fake-relative-to/project"]));
+        static ARGS_UNRELATED: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--gray", "fake-relative-to/project/src", "--relative-to", "fake-relative-to/other"]));
+        let no_contents: Option<&str> = None;
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("project/src/util/mod.rs", no_contents)?;
+        test_dir.generate("project/src/main.rs", no_contents)?;
+        test_dir.create_directory("other")?;
+
+        let displays = |args: &'static rippy::args::RippyArgs| -> Result<Vec<String>, DirError> {
+            let mut displays: Vec<String> = crawl::crawl_directory(args)?.paths.into_iter().map(|leaf| leaf.display).collect();
+            displays.sort();
+            Ok(displays)
+        };
+        assert_eq!(displays(&ARGS)?, vec!["src/main.rs", "src/util", "src/util/mod.rs"]);
+
+        let base = std::path::absolute("fake-relative-to/project/src")?.to_string_lossy().replace("\\", "/");
+        assert_eq!(displays(&ARGS_UNRELATED)?, vec![format!("{base}/main.rs"), format!("{base}/util"), format!("{base}/util/mod.rs")]);
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Formats the result summary for `rippy fake-summary-parity` with and without a pattern across colored, `[--gray]` and `[--just-counts]` output, for both match and no match cases.
+    /// 
+    /// Testing functionality of the summary line producing identical text and spacing regardless of styling or whether the tree was rendered, once ANSI sequences are removed.
+    pub fn test_summary_parity() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-summary-parity";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR]));
+        static ARGS_GRAY: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--gray", ROOT_TEST_DIR]));
+        static ARGS_COUNTS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--just-counts", ROOT_TEST_DIR]));
+        static ARGS_COUNTS_GRAY: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--just-counts", "--gray", ROOT_TEST_DIR]));
+        static SEARCH: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "needle"]));
+        static SEARCH_GRAY: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--gray", ROOT_TEST_DIR, "needle"]));
+        static SEARCH_COUNTS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--just-counts", ROOT_TEST_DIR, "needle"]));
+        static SEARCH_COUNTS_GRAY: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--just-counts", "--gray", ROOT_TEST_DIR, "needle"]));
+        let no_contents: Option<&str> = None;
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("f1.txt", no_contents)?;
+
+        let ansi = Regex::new("\\x1b\\[[0-9;]*m").unwrap();
+        let summarize = |args: &'static rippy::args::RippyArgs, num_matched: usize, counts: &tree::TreeCounts| ansi.replace_all(&rippy::args::format_result_summary(args, num_matched, 4, counts), "").to_string();
+        let rendered = tree::TreeCounts { dir_count: 1, file_count: 1 };
+        let empty = tree::TreeCounts::new();
+
+        // The blank line before the summary is always left to the tree writer, even when nothing was rendered
+        for (args, just_counts) in [(&ARGS, &ARGS_COUNTS), (&ARGS_GRAY, &ARGS_COUNTS_GRAY)] {
+            assert_eq!(summarize(args, 2, &rendered), "1 directory, 1 file");
+            assert_eq!(summarize(just_counts, 2, &rendered), "1 directory, 1 file");
+            assert_eq!(summarize(args, 0, &empty), "0 directories, 0 files");
+            assert_eq!(summarize(just_counts, 0, &empty), "0 directories, 0 files");
+        }
+        for (args, just_counts) in [(&SEARCH, &SEARCH_COUNTS), (&SEARCH_GRAY, &SEARCH_COUNTS_GRAY)] {
+            assert_eq!(summarize(args, 1, &rendered), "1 match for needle, 4 searched");
+            assert_eq!(summarize(just_counts, 1, &rendered), "1 match for needle, 4 searched");
+            assert_eq!(summarize(args, 0, &empty), "0 matches for needle, 4 searched");
+            assert_eq!(summarize(just_counts, 0, &empty), "0 matches for needle, 4 searched");
+        }
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-depth-summary --max-depth 3 --depth-summary --gray` on the same layout as `fake-depth` to generate:
+    /// 
+    /// ```shell
+    /// fake-depth-summary
+    /// ├── d1
+    /// │   ╰── d2
+    /// │       ├── d3
+    /// │       ╰── depth-3.txt
+    /// ╰── depth-1.txt
+    ///
+    /// 3 directories, 2 files
+    /// L1: 2, L2: 1, L3: 2
+    /// ```
+    /// 
+    /// Testing functionality of `[--depth-summary]` counting the entries found at each depth.
+    pub fn test_depth_summary() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-depth-summary";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--max-depth", "3", "--depth-summary", "--gray", ROOT_TEST_DIR]));
+        let no_contents: Option<&str> = None;
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("depth-1.txt", no_contents)?;
+        test_dir.generate("d1/d2/depth-3.txt", no_contents)?;
+        test_dir.generate("d1/d2/d3/d4/d5/d6/depth-7.txt", no_contents)?;
+
+        let tree = tree::build_tree_from_paths(crawl::crawl_directory(&ARGS)?.paths, &ARGS);
+        let depths = tree.depth_counts();
+        assert_eq!(depths.into_iter().collect::<Vec<_>>(), vec![(1, 2), (2, 1), (3, 2)]);
+        assert_eq!(rippy::args::format_depth_summary(&ARGS, &tree.depth_counts()).as_deref(), Some("L1: 2, L2: 1, L3: 2"));
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-match-color needle --match-color 199` and with `[--match-color #ff8800]` on a test directory containing a single matching file.
+    /// 
+    /// Testing functionality of `[--match-color]` overriding the highlight color used in snippets and the summary, and rejecting invalid colors.
+    pub fn test_match_color() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-match-color";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "needle", "--match-color", "199"]));
+        static ARGS_HEX: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "needle", "--match-color", "#ff8800"]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("f1.txt", Some("find the needle here"))?;
+
+        let snippet = |args: &'static rippy::args::RippyArgs| -> Result<String, DirError> {
+            Ok(crawl::crawl_directory(args)?.paths.into_iter().next().and_then(|leaf| leaf.window).unwrap_or_default())
+        };
+        assert!(snippet(&ARGS)?.contains("\u{1b}[1m\u{1b}[38;5;199mneedle\u{1b}[0m"));
+        assert!(snippet(&ARGS_HEX)?.contains("\u{1b}[1m\u{1b}[38;2;255;136;0mneedle\u{1b}[0m"));
+        let summary = rippy::args::format_result_summary(&ARGS, 1, 1, &tree::TreeCounts { dir_count: 0, file_count: 1 });
+        assert!(summary.starts_with("\u{1b}[1m\u{1b}[38;5;199m1 match"));
+
+        let invalid = std::process::Command::new(env!("CARGO_BIN_EXE_rippy")).args([ROOT_TEST_DIR, "needle", "--match-color", "#ff88"]).stdout(std::process::Stdio::null()).stderr(std::process::Stdio::null()).status()?;
+        assert!(!invalid.success());
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-deterministic --deterministic --date --full-path --time` twice, modifying a file in between.
+    /// 
+    /// Testing functionality of `[--deterministic]` producing byte identical output by fixing timestamps, omitting the elapsed time and avoiding absolute paths.
+    pub fn test_deterministic() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-deterministic";
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("a/f1.txt", Some("first"))?;
+        test_dir.generate("f2.txt", Some("second"))?;
+
+        let run = || std::process::Command::new(env!("CARGO_BIN_EXE_rippy")).args([ROOT_TEST_DIR, "--deterministic", "--date", "--full-path", "--time"]).output();
+        let first = run()?;
+        thread::sleep(Duration::from_millis(1100));
+        std::fs::write(test_dir.root().join("f2.txt"), "modified")?;
+        let second = run()?;
+        assert!(first.status.success());
+        assert_eq!(first.stdout, second.stdout);
+
+        let output = String::from_utf8_lossy(&first.stdout);
+        let cwd = std::env::current_dir()?.to_string_lossy().replace("\\", "/");
+        assert!(output.contains("1970-01-01"));
+        assert!(output.contains("fake-deterministic/a/f1.txt") && !output.contains(&cwd));
+        assert!(output.trim_end().ends_with("1 directory, 2 files"));
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-deep-size --size --dir-detail --max-depth 2` and with `[--deep-size]` on a test directory whose largest file is below the depth limit:
+    /// 
+    /// ```shell
+    /// fake-deep-size
+    /// ╰── (1.0 K) a
+    ///     ├── (1.0 K) b
+    ///     ╰── ( 10 B) small.txt
+    /// ```
+    /// 
+    /// Testing functionality of directory sizes at the depth limit only counting crawled content by default, and including the content beyond it with `[--deep-size]`.
+    pub fn test_deep_size() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-deep-size";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--size", "--dir-detail", "--max-depth", "2", ROOT_TEST_DIR]));
+        static ARGS_DEEP: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--deep-size", "--max-depth", "2", ROOT_TEST_DIR]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("a/small.txt", Some("x".repeat(10)))?;
+        test_dir.generate("a/b/c/big.txt", Some("x".repeat(1000)))?;
+
+        let mut tree_output = tree::build_tree_from_paths(crawl::crawl_directory(&ARGS)?.paths, &ARGS);
+        tree_output.calculate_sizes();
+        assert_eq!(tree_output.children["a"].children["b"].size, Some(0));
+        assert_eq!(tree_output.children["a"].size, Some(10));
+
+        let mut tree_output = tree::build_tree_from_paths(crawl::crawl_directory(&ARGS_DEEP)?.paths, &ARGS_DEEP);
+        tree_output.calculate_sizes_to_depth(ARGS_DEEP.max_depth, 0);
+        assert_eq!(tree_output.children["a"].children["b"].size, Some(1000));
+        assert_eq!(tree_output.children["a"].size, Some(1010));
+        assert_eq!(tree_output.size, Some(1010));
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Formats the result summary for `rippy fake-summary-pattern "TODO|FIXME"` with and without `[--quote]`.
+    /// 
+    /// Testing functionality of the summary including the pattern text as provided when searching, wrapped in double quotes when `[--quote]` is present.
+    pub fn test_summary_pattern() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-summary-pattern";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--gray", "-c", ROOT_TEST_DIR, "TODO|FIXME"]));
+        static ARGS_QUOTE: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--gray", "--quote", ROOT_TEST_DIR, "TODO|FIXME"]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("f1.txt", Some("TODO: one"))?;
+        test_dir.generate("f2.txt", Some("FIXME: two"))?;
+        test_dir.generate("f3.txt", Some("todo: three"))?;
+
+        let result = crawl::crawl_directory(&ARGS)?;
+        let counts = tree::TreeCounts { dir_count: 0, file_count: result.paths.len() };
+        assert_eq!(ARGS.pattern_text.as_deref(), Some("TODO|FIXME"));
+        assert_eq!(rippy::args::format_result_summary(&ARGS, result.paths.len(), result.paths_searched, &counts), "3 matches for TODO|FIXME, 3 searched");
+
+        let result = crawl::crawl_directory(&ARGS_QUOTE)?;
+        let counts = tree::TreeCounts { dir_count: 0, file_count: result.paths.len() };
+        assert_eq!(rippy::args::format_result_summary(&ARGS_QUOTE, result.paths.len(), result.paths_searched, &counts), "2 matches for \"TODO|FIXME\", 3 searched");
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Compares the file system identified for paths within a test directory, and on Linux against `/proc`, then runs `rippy fake-one-fs --one-file-system`.
+    /// 
+    /// Testing functionality of `crawl::file_system_id` used by `[--one-file-system]` to avoid descending into directories on other file systems.
+    pub fn test_one_file_system() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-one-fs";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--one-file-system", ROOT_TEST_DIR]));
+        let no_contents: Option<&str> = None;
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("a/b/f1.txt", no_contents)?;
+
+        let root_id = crawl::file_system_id(test_dir.root());
+        assert!(root_id.is_some());
+        assert_eq!(crawl::file_system_id(&test_dir.root().join("a/b")), root_id);
+        if cfg!(target_os = "linux") && std::path::Path::new("/proc/self").exists() {
+            assert_ne!(crawl::file_system_id(std::path::Path::new("/proc")), root_id);
+        }
+
+        // Directories on the same file system are still descended into
+        let mut names: Vec<String> = crawl::crawl_directory(&ARGS)?.paths.into_iter().map(|leaf| leaf.name).collect();
+        names.sort();
+        assert_eq!(names, vec!["a", "b", "f1.txt"]);
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-min-children --min-children 2` on test directory to generate:
+    /// 
+    /// ```shell
+    /// fake-min-children
+    /// ├── chain
+    /// │   ╰── inner
+    /// │       ├── x1.txt
+    /// │       ╰── x2.txt
+    /// ├── two
+    /// │   ├── a.txt
+    /// │   ╰── b.txt
+    /// ╰── root.txt
+    /// ```
+    /// 
+    /// Testing functionality of `[--min-children]` hiding directories with only one file while keeping the ancestors of directories that are shown.
+    pub fn test_min_children() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-min-children";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--min-children", "2", ROOT_TEST_DIR]));
+        let no_contents: Option<&str> = None;
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("one/only.txt", no_contents)?;
+        test_dir.generate("two/a.txt", no_contents)?;
+        test_dir.generate("two/b.txt", no_contents)?;
+        test_dir.generate("chain/inner/x1.txt", no_contents)?;
+        test_dir.generate("chain/inner/x2.txt", no_contents)?;
+        test_dir.generate("root.txt", no_contents)?;
+
+        let mut tree_output = tree::build_tree_from_paths(crawl::crawl_directory(&ARGS)?.paths, &ARGS);
+        tree_output.retain_min_children(ARGS.min_children);
+        let mut names: Vec<&String> = tree_output.children.keys().collect();
+        names.sort();
+        assert_eq!(names, vec!["chain", "root.txt", "two"]);
+        assert_eq!(tree_output.children["chain"].children["inner"].children.len(), 2);
+        assert_eq!(tree_output.children["two"].children.len(), 2);
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-json-header -x "*.rs" --output fake-json-header/out.json` and with `[--bare-json]` on a test directory containing a nested source file.
+    /// 
+    /// Testing functionality of the JSON export header fields and the nested tree round-tripping unchanged, with `[--bare-json]` writing only the tree.
+    pub fn test_json_header() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-json-header";
+        const JSON_FILE: &str = "fake-json-header/out.json";
+        const BARE_JSON_FILE: &str = "fake-json-header/bare.json";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "-x", "*.rs", "--output", JSON_FILE, ROOT_TEST_DIR]));
+        static ARGS_BARE: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "-x", "*.rs", "--bare-json", "--output", BARE_JSON_FILE, ROOT_TEST_DIR]));
+        let no_contents: Option<&str> = None;
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("src/main.rs", no_contents)?;
+
+        let tree_output = tree::build_tree_from_paths(crawl::crawl_directory(&ARGS)?.paths, &ARGS);
+        tree_output.write_to_file(JSON_FILE, &ARGS)?;
+        let json_received: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(JSON_FILE)?).unwrap();
+        assert_eq!(json_received["schema"], json!(tree::JSON_SCHEMA_VERSION));
+        assert_eq!(json_received["root"], json!(ROOT_TEST_DIR));
+        assert!(json_received["generated"].as_str().is_some_and(|generated| chrono::DateTime::parse_from_rfc3339(generated).is_ok()));
+        assert_eq!(json_received["args"]["include"], json!(["^.*\\.rs$"]));
+        assert_eq!(json_received["args"]["pattern"], json!(null));
+        assert_eq!(json_received["tree"], tree_output.to_json(&ARGS));
+
+        tree_output.write_to_file(BARE_JSON_FILE, &ARGS_BARE)?;
+        let json_received: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(BARE_JSON_FILE)?).unwrap();
+        assert_eq!(json_received, tree_output.to_json(&ARGS_BARE));
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-pattern-file --pattern-file fake-pattern-file/patterns.txt` with `[--case-insensitive]` and `[--fixed-strings]` on a test directory with a two pattern file.
+    /// 
+    /// Testing functionality of `[--pattern-file]` matching files containing any of the patterns while skipping empty lines and comments.
+    pub fn test_pattern_file() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-pattern-file";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "-I", "patterns.txt", "--pattern-file", "fake-pattern-file/patterns.txt", ROOT_TEST_DIR]));
+        static ARGS_CASE: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "-I", "patterns.txt", "-c", "--pattern-file", "fake-pattern-file/patterns.txt", ROOT_TEST_DIR]));
+        static ARGS_FIXED: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "-I", "patterns.txt", "--fixed-strings", "--pattern-file", "fake-pattern-file/patterns.txt", ROOT_TEST_DIR]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("patterns.txt", Some("TODO\n\n# comment\nFIX.*ME\n"))?;
+        test_dir.generate("todo.txt", Some("TODO: first"))?;
+        test_dir.generate("fixme.txt", Some("FIXME: second"))?;
+        test_dir.generate("literal.txt", Some("FIX.*ME: third"))?;
+        test_dir.generate("lowercase.txt", Some("todo: fourth"))?;
+        test_dir.generate("comment.txt", Some("# comment"))?;
+
+        let names = |args: &'static rippy::args::RippyArgs| -> Result<Vec<String>, DirError> {
+            let mut names: Vec<String> = crawl::crawl_directory(args)?.paths.into_iter().map(|leaf| leaf.name).collect();
+            names.sort();
+            Ok(names)
+        };
+        assert!(ARGS.is_search);
+        assert_eq!(ARGS.pattern_text.as_deref(), Some("TODO|FIX.*ME"));
+        assert_eq!(names(&ARGS)?, vec!["fixme.txt", "literal.txt", "todo.txt"]);
+        assert_eq!(names(&ARGS_CASE)?, vec!["fixme.txt", "literal.txt", "lowercase.txt", "todo.txt"]);
+        assert_eq!(names(&ARGS_FIXED)?, vec!["literal.txt", "todo.txt"]);
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-plain --plain --size` on test directory to generate:
+    /// 
+    /// ```shell
+    ///  fake-plain
+    ///  |-- a
+    ///  |   |-- b
+    ///  |   |   `-- (0.0 B) f2.txt
+    ///  |   `-- (0.0 B) f1.txt
+    ///  `-- (0.0 B) root.txt
+    /// ```
+    /// 
+    /// Testing functionality of `[--plain]` producing output containing only ASCII bytes without any escape sequences.
+    pub fn test_plain_output() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-plain";
+        let no_contents: Option<&str> = None;
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("a/b/f2.txt", no_contents)?;
+        test_dir.generate("a/f1.txt", no_contents)?;
+        test_dir.generate("root.txt", no_contents)?;
+
+        let output = std::process::Command::new(env!("CARGO_BIN_EXE_rippy")).args([ROOT_TEST_DIR, "--plain", "--size"]).output()?;
+        assert!(output.status.success());
+        assert!(output.stdout.is_ascii());
+        assert!(!output.stdout.contains(&0x1b));
+        let output = String::from_utf8_lossy(&output.stdout);
+        assert!(output.contains(" |-- a\n") && output.contains(" |   |-- b\n") && output.contains(" `-- (0.0 B) root.txt\n"));
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-ext-count --ext-count` on a test directory mixing extensions, casing and extensionless files.
+    /// 
+    /// Testing functionality of `[--ext-count]` counting distinct extensions case insensitively with extensionless files grouped into a single `(none)` bucket.
+    pub fn test_ext_count() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-ext-count";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--ext-count", ROOT_TEST_DIR]));
+        let no_contents: Option<&str> = None;
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("src/main.rs", no_contents)?;
+        test_dir.generate("src/lib.RS", no_contents)?;
+        test_dir.generate("README.md", no_contents)?;
+        test_dir.create_file("Makefile", no_contents)?;
+        test_dir.create_file("LICENSE", no_contents)?;
+        test_dir.generate("docs/archive.tar.gz", no_contents)?;
+        test_dir.generate("docs/notes.txt", no_contents)?;
+
+        let tree_output = tree::build_tree_from_paths(crawl::crawl_directory(&ARGS)?.paths, &ARGS);
+        let mut extensions: Vec<String> = tree_output.distinct_extensions().into_iter().collect();
+        extensions.sort();
+        assert_eq!(extensions, vec!["(none)", "gz", "md", "rs", "txt"]);
+        assert_eq!(rippy::args::format_ext_count(&ARGS, extensions.len()).as_deref(), Some(" across 5 extensions"));
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-flat-paths --gray --flat-paths` on a nested test directory repeating the same file name at different depths:
+    /// 
+    /// ```text
+    ///  fake-flat-paths
+    ///  fake-flat-paths/a
+    ///  fake-flat-paths/a/b
+    ///  fake-flat-paths/a/b/notes.txt
+    ///  fake-flat-paths/a/notes.txt
+    /// 
+    /// 2 directories, 2 files
+    /// ```
+    /// 
+    /// Testing functionality of `[--flat-paths]` to force the flat layout with each entry displayed by its full relative path.
+    pub fn test_flat_paths() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-flat-paths";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--gray", "--flat-paths", ROOT_TEST_DIR]));
+        let no_contents: Option<&str> = None;
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("a/notes.txt", no_contents)?;
+        test_dir.generate("a/b/notes.txt", no_contents)?;
+        assert!(ARGS.is_flat && ARGS.show_relative_path);
+
+        let crawl_results = crawl::crawl_directory(&ARGS)?;
+        let mut tree_output = tree::build_tree_from_paths(crawl_results.paths, &ARGS);
+        let mut counts = tree::TreeCounts::new();
+        let mut writer: Vec<u8> = Vec::new();
+        tree::write_tree(&mut tree_output, &ARGS, &mut counts, &mut writer)?;
+        let output = String::from_utf8_lossy(&writer);
+        let lines: Vec<&str> = output.lines().map(|line| line.trim()).filter(|line| !line.is_empty()).collect();
+        assert_eq!(lines, vec![ROOT_TEST_DIR, "fake-flat-paths/a", "fake-flat-paths/a/b", "fake-flat-paths/a/b/notes.txt", "fake-flat-paths/a/notes.txt"]);
+        assert_eq!(counts, tree::TreeCounts{ dir_count: 2, file_count: 2});
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-sort-recursive --gray --sort size --reverse` on a nested test directory with files of varying sizes.
+    /// 
+    /// Testing functionality of `tree::Tree::sort_recursive` to apply the ordering once so the JSON export lists children in the same order the tree renders them.
+    pub fn test_sort_recursive() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-sort-recursive";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--gray", "--sort", "size", "--reverse", ROOT_TEST_DIR]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("a.txt", Some("X".repeat(10)))?;
+        test_dir.generate("b.txt", Some("X".repeat(300)))?;
+        test_dir.generate("c.txt", Some("X".repeat(120)))?;
+        test_dir.generate("sub/d.txt", Some("X".repeat(5)))?;
+        test_dir.generate("sub/e.txt", Some("X".repeat(50)))?;
+
+        let crawl_results = crawl::crawl_directory(&ARGS)?;
+        let mut tree_output = tree::build_tree_from_paths(crawl_results.paths, &ARGS);
+        tree_output.sort_recursive(ARGS.sort_by);
+
+        // Names in depth-first order as exported
+        fn collect_json_names(value: &serde_json::Value, names: &mut Vec<String>) {
+            for child in value["children"].as_array().into_iter().flatten() {
+                names.push(child["name"].as_str().unwrap_or_default().to_string());
+                collect_json_names(child, names);
+            }
+        }
+        let mut json_names = Vec::new();
+        collect_json_names(&tree_output.to_json(&ARGS), &mut json_names);
+
+        let mut writer: Vec<u8> = Vec::new();
+        tree::write_tree(&mut tree_output, &ARGS, &mut tree::TreeCounts::new(), &mut writer)?;
+        let output = String::from_utf8_lossy(&writer);
+        let rendered_names: Vec<String> = output.lines().skip(1).filter_map(|line| line.rsplit("── ").next().filter(|_| line.contains("── "))).map(|name| name.trim().to_string()).collect();
+        assert_eq!(json_names, rendered_names);
+        assert_eq!(json_names.first().map(String::as_str), Some("b.txt"));
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-empty-files --gray --flag-empty` and `rippy fake-empty-files --no-empty-files` on a test directory containing zero-byte files:
+    /// 
+    /// ```text
+    ///  fake-empty-files
+    ///  ├── docs
+    ///  │   ╰── placeholder.md [empty]
+    ///  ├── empty.txt [empty]
+    ///  ╰── full.txt
+    /// 
+    /// 1 directory, 3 files
+    /// ```
+    /// 
+    /// Testing functionality of `[--flag-empty]` marking zero-byte files and `[--no-empty-files]` pruning them while keeping their directories.
+    pub fn test_empty_files() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-empty-files";
+        static ARGS_FLAG: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--gray", "--flag-empty", ROOT_TEST_DIR]));
+        static ARGS_PRUNE: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--no-empty-files", ROOT_TEST_DIR]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("empty.txt", Some(""))?;
+        test_dir.generate("full.txt", Some("X".repeat(12)))?;
+        test_dir.generate("docs/placeholder.md", Some(""))?;
+
+        let mut tree_flagged = tree::build_tree_from_paths(crawl::crawl_directory(&ARGS_FLAG)?.paths, &ARGS_FLAG);
+        let mut writer: Vec<u8> = Vec::new();
+        tree::write_tree(&mut tree_flagged, &ARGS_FLAG, &mut tree::TreeCounts::new(), &mut writer)?;
+        let output = String::from_utf8_lossy(&writer);
+        assert!(output.lines().any(|line| line.trim_end().ends_with("empty.txt [empty]")));
+        assert!(output.lines().any(|line| line.trim_end().ends_with("placeholder.md [empty]")));
+        assert!(output.lines().any(|line| line.trim_end().ends_with("full.txt")));
+
+        let mut tree_pruned = tree::build_tree_from_paths(crawl::crawl_directory(&ARGS_PRUNE)?.paths, &ARGS_PRUNE);
+        tree_pruned.retain_non_empty_files();
+        let mut counts = tree::TreeCounts::new();
+        tree::count_tree(&tree_pruned, &mut counts, true);
+        assert_eq!(counts, tree::TreeCounts{ dir_count: 1, file_count: 1});
+        assert!(tree_pruned.iter().all(|entry| entry.name != "empty.txt" && entry.name != "placeholder.md"));
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-line-numbers --gray --line-numbers 'TODO'` on a test directory with matches on the third line using both `\n` and `\r\n` line endings.
+    /// 
+    /// Testing functionality of `[--line-numbers]` to prefix the window with the 1-based line number of the match in the tree and JSON export.
+    pub fn test_line_numbers() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-line-numbers";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--gray", "--line-numbers", ROOT_TEST_DIR, "TODO"]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("unix.txt", Some("first\nsecond\nTODO: third\nfourth\n"))?;
+        test_dir.generate("windows.txt", Some("first\r\nsecond\r\nTODO: third\r\nfourth\r\n"))?;
+
+        let tree_output = tree::build_tree_from_paths(crawl::crawl_directory(&ARGS)?.paths, &ARGS);
+        let windows: Vec<&str> = tree_output.iter().filter_map(|entry| entry.window.as_deref()).collect();
+        assert_eq!(windows.len(), 2);
+        assert!(windows.iter().all(|window| window.starts_with("L3: TODO")));
+        let json = tree_output.to_json(&ARGS);
+        assert!(json["children"].as_array().is_some_and(|children| children.iter().all(|child| child["window"].as_str().is_some_and(|window| window.starts_with("L3:")))));
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-no-bold --no-bold 'needle'` on a test directory with a match nested in a subdirectory.
+    /// 
+    /// Testing functionality of `[--no-bold]` to remove every bold sequence from the rendered tree and summary while keeping the colors.
+    pub fn test_no_bold() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-no-bold";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--no-bold", ROOT_TEST_DIR, "needle"]));
+        static ARGS_BOLD: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "needle"]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("src/found.txt", Some("a needle in a haystack"))?;
+        test_dir.generate("src/other.txt", Some("only hay"))?;
+
+        let render = |args: &'static rippy::args::RippyArgs| -> Result<String, DirError> {
+            let crawl_results = crawl::crawl_directory(args)?;
+            let (num_matched, num_searched) = (crawl_results.paths.len(), crawl_results.paths_searched);
+            let mut tree_output = tree::build_tree_from_paths(crawl_results.paths, args);
+            let mut counts = tree::TreeCounts::new();
+            let mut writer: Vec<u8> = Vec::new();
+            tree::write_tree(&mut tree_output, args, &mut counts, &mut writer)?;
+            Ok(String::from_utf8_lossy(&writer).to_string() + &rippy::args::format_result_summary(args, num_matched, num_searched, &counts))
+        };
+        // Only meaningful when colors are enabled, which requires a terminal
+        if !ARGS.is_grayscale {
+            assert!(render(&ARGS_BOLD)?.contains("\x1b[1m"));
+            let output = render(&ARGS)?;
+            assert!(!output.contains("\x1b[1m"));
+            assert!(output.contains("\x1b[38;5;"));
+        }
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-guides --gray --guide-tee + --guide-elbow \ --guide-pipe : --guide-dash =` on a nested test directory:
+    /// 
+    /// ```text
+    ///  fake-guides
+    ///  +== a
+    ///  :   \== inner.txt
+    ///  \== b.txt
+    /// 
+    /// 1 directory, 2 files
+    /// ```
+    /// 
+    /// Testing functionality of `[--guide-tee]`, `[--guide-elbow]`, `[--guide-pipe]` and `[--guide-dash]` overriding the connectors, including over the `[--plain]` preset.
+    pub fn test_custom_guides() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-guides";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--gray", "--guide-tee", "+", "--guide-elbow", "\\", "--guide-pipe", ":", "--guide-dash", "=", ROOT_TEST_DIR]));
+        static ARGS_PLAIN: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--plain", "--guide-elbow", "*", ROOT_TEST_DIR]));
+        let no_contents: Option<&str> = None;
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("a/inner.txt", no_contents)?;
+        test_dir.generate("b.txt", no_contents)?;
+
+        let mut tree_output = tree::build_tree_from_paths(crawl::crawl_directory(&ARGS)?.paths, &ARGS);
+        let mut writer: Vec<u8> = Vec::new();
+        tree::write_tree(&mut tree_output, &ARGS, &mut tree::TreeCounts::new(), &mut writer)?;
+        let output = String::from_utf8_lossy(&writer);
+        let lines: Vec<&str> = output.lines().map(|line| line.trim_end()).collect();
+        assert_eq!(lines[1], " +== a");
+        assert!(lines[2].starts_with(" :") && lines[2].ends_with("\\== inner.txt"));
+        assert_eq!(lines[3], " \\== b.txt");
+
+        assert_eq!(ARGS_PLAIN.glyphs, tree::TreeGlyphs { elbow: "*", ..tree::ASCII_GLYPHS });
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-time-phases --time-phases` on a small test directory.
+    /// 
+    /// Testing functionality of `[--time-phases]` to report the crawl, build and render durations after the results, omitted when output is deterministic.
+    pub fn test_time_phases() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-time-phases";
+        let no_contents: Option<&str> = None;
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("src/main.rs", no_contents)?;
+        test_dir.generate("README.md", no_contents)?;
+
+        let output = std::process::Command::new(env!("CARGO_BIN_EXE_rippy")).args([ROOT_TEST_DIR, "--time-phases"]).output()?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let phases_line = stdout.lines().find(|line| line.starts_with("crawl: ")).expect("phase breakdown should be printed");
+        let phases: Vec<(&str, f64)> = phases_line.split(", ")
+            .filter_map(|phase| phase.split_once(": "))
+            .map(|(label, duration)| (label, duration.trim_end_matches('s').parse::<f64>().unwrap_or(-1.0)))
+            .collect();
+        assert_eq!(phases.iter().map(|(label, _)| *label).collect::<Vec<&str>>(), vec!["crawl", "build", "render"]);
+        assert!(phases.iter().all(|(_, duration)| *duration >= 0.0));
+
+        let output = std::process::Command::new(env!("CARGO_BIN_EXE_rippy")).args([ROOT_TEST_DIR, "--time-phases", "--deterministic"]).output()?;
+        assert!(!String::from_utf8_lossy(&output.stdout).contains("crawl: "));
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-breadth-first --gray --breadth-first` on a nested test directory:
+    /// 
+    /// ```text
+    ///  fake-breadth-first
+    ///  a
+    ///  b
+    ///  root.txt
+    ///  a1.txt
+    ///  deep
+    ///  b1.txt
+    ///  deep.txt
+    /// 
+    /// 3 directories, 4 files
+    /// ```
+    /// 
+    /// Testing functionality of `[--breadth-first]` to list every entry at one depth before any at the next while keeping each directory's children sorted.
+    pub fn test_breadth_first() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-breadth-first";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--gray", "--breadth-first", ROOT_TEST_DIR]));
+        let no_contents: Option<&str> = None;
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("root.txt", no_contents)?;
+        test_dir.generate("a/a1.txt", no_contents)?;
+        test_dir.generate("a/deep/deep.txt", no_contents)?;
+        test_dir.generate("b/b1.txt", no_contents)?;
+        assert!(ARGS.is_flat);
+
+        let mut tree_output = tree::build_tree_from_paths(crawl::crawl_directory(&ARGS)?.paths, &ARGS);
+        let mut counts = tree::TreeCounts::new();
+        let mut writer: Vec<u8> = Vec::new();
+        tree::write_breadth_first_to_buf(&mut tree_output, &ARGS, &mut counts, &mut writer)?;
+        let output = String::from_utf8_lossy(&writer);
+        let lines: Vec<&str> = output.lines().map(|line| line.trim()).filter(|line| !line.is_empty()).collect();
+        assert_eq!(lines, vec![ROOT_TEST_DIR, "a", "b", "root.txt", "a1.txt", "deep", "b1.txt", "deep.txt"]);
+        assert_eq!(counts, tree::TreeCounts{ dir_count: 3, file_count: 4});
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-summary-top --summary-top` on a small nested test directory:
+    /// 
+    /// ```text
+    /// 1 directory, 2 files
+    ///  fake-summary-top
+    ///  ├── a.txt
+    ///  ╰── sub
+    ///      ╰── b.txt
+    /// ```
+    /// 
+    /// Testing functionality of `[--summary-top]` to print the summary with accurate counts before the buffered tree.
+    pub fn test_summary_top() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-summary-top";
+        let no_contents: Option<&str> = None;
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("a.txt", no_contents)?;
+        test_dir.generate("sub/b.txt", no_contents)?;
+
+        let run = |flags: &[&str]| std::process::Command::new(env!("CARGO_BIN_EXE_rippy")).arg(ROOT_TEST_DIR).args(flags).output();
+        let output = run(&["--summary-top"])?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let lines: Vec<&str> = stdout.lines().collect();
+        assert_eq!(lines[0], "1 directory, 2 files");
+        assert_eq!(lines[1].trim(), ROOT_TEST_DIR);
+        assert!(lines.iter().skip(1).all(|line| !line.contains("2 files")));
+
+        // Same lines as the default order with only the summary moved
+        let output_default = run(&[])?;
+        let stdout_default = String::from_utf8_lossy(&output_default.stdout);
+        let mut lines_default: Vec<&str> = stdout_default.lines().filter(|line| !line.is_empty()).collect();
+        assert_eq!(lines_default.pop(), Some("1 directory, 2 files"));
+        assert_eq!(lines[1..].iter().filter(|line| !line.is_empty()).copied().collect::<Vec<&str>>(), lines_default);
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-summary-colors 'needle'` and `rippy fake-summary-colors 'needle' --searched-color 33` on a test directory with a single match.
+    /// 
+    /// Testing functionality of the summary using distinct colors for the match count and searched count, with or without matches, and `[--searched-color]` overriding the latter.
+    pub fn test_summary_colors() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-summary-colors";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "needle"]));
+        static ARGS_SEARCHED: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "needle", "--searched-color", "33"]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("found.txt", Some("needle"))?;
+
+        // Only meaningful when colors are enabled, which requires a terminal
+        if !ARGS.is_grayscale {
+            let counts = tree::TreeCounts { dir_count: 0, file_count: 1 };
+            let color_codes = |summary: &str| -> Vec<String> {
+                summary.split("\u{1b}[38;5;").skip(1).filter_map(|code| code.split_once('m')).map(|(code, _)| code.to_string()).collect()
+            };
+            for num_matched in [0, 1] {
+                let codes = color_codes(&rippy::args::format_result_summary(&ARGS, num_matched, 1, &counts));
+                assert_eq!(codes.len(), 2);
+                assert_ne!(codes[0], codes[1]);
+            }
+            let codes = color_codes(&rippy::args::format_result_summary(&ARGS_SEARCHED, 1, 1, &counts));
+            assert_eq!(codes.last().map(String::as_str), Some("33"));
+        }
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-include-file --include-file fake-include-file/include.txt --ignore-file-list fake-include-file/ignore.txt` on a test directory with pattern files holding globs, blank lines and comments.
+    /// 
+    /// Testing functionality of `[--include-file]` and `[--ignore-file-list]` merging the globs read from files with those provided to `[--include]` and `[--ignore]`.
+    pub fn test_include_file() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-include-file";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--include-file", "fake-include-file/include.txt", "--ignore-file-list", "fake-include-file/ignore.txt", ROOT_TEST_DIR]));
+        static ARGS_MERGED: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--include-file", "fake-include-file/include.txt", "-X", "*.md", "--ignore-file-list", "fake-include-file/ignore.txt", ROOT_TEST_DIR]));
+        let no_contents: Option<&str> = None;
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("include.txt", Some("# sources\n*.rs\n\n*.toml \n"))?;
+        test_dir.generate("ignore.txt", Some("generated\n# build output\n"))?;
+        test_dir.generate("src/main.rs", no_contents)?;
+        test_dir.generate("Cargo.toml", no_contents)?;
+        test_dir.generate("README.md", no_contents)?;
+        test_dir.generate("generated/out.rs", no_contents)?;
+
+        let file_names = |args: &'static rippy::args::RippyArgs| -> Result<Vec<String>, DirError> {
+            let mut names: Vec<String> = crawl::crawl_directory(args)?.paths.into_iter().filter(|leaf| !leaf.is_dir).map(|leaf| leaf.name).collect();
+            names.sort();
+            Ok(names)
+        };
+        assert_eq!(file_names(&ARGS)?, vec!["Cargo.toml", "main.rs"]);
+        assert_eq!(file_names(&ARGS_MERGED)?, vec!["Cargo.toml", "README.md", "main.rs"]);
+        test_dir.clean()
+    }
+
+    #[test]
+    #[cfg(unix)]
+    /// Runs `rippy fake-dereference --size --dereference` on a test directory with a symbolic link to a 100 byte file:
+    /// 
+    /// ```shell
+    ///  fake-dereference
+    ///  ├── (100 B) link.bin -> target.bin
+    ///  ╰── (100 B) target.bin
+    /// ```
+    /// 
+    /// Testing functionality of `[--dereference]` to display the size of the link target while the size of the link itself is kept by default.
+    pub fn test_dereference_size() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-dereference";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--gray", "--size", "--dereference", ROOT_TEST_DIR]));
+        static ARGS_LINK: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--gray", "--size", ROOT_TEST_DIR]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("target.bin", Some("X".repeat(100)))?;
+        let link_path = test_dir.join("link.bin");
+        if !link_path.exists() {
+            std::os::unix::fs::symlink("target.bin", &link_path)?;
+        }
+
+        let mut tree_output = tree::build_tree_from_paths(crawl::crawl_directory(&ARGS)?.paths, &ARGS);
+        assert_eq!(tree_output.children["link.bin"].size, Some(100));
+        let mut writer: Vec<u8> = Vec::new();
+        tree::write_tree(&mut tree_output, &ARGS, &mut tree::TreeCounts::new(), &mut writer)?;
+        assert!(String::from_utf8_lossy(&writer).contains("(100 B) link.bin -> target.bin"));
+
+        let tree_output = tree::build_tree_from_paths(crawl::crawl_directory(&ARGS_LINK)?.paths, &ARGS_LINK);
+        assert_eq!(tree_output.children["link.bin"].size, Some(std::fs::symlink_metadata(&link_path)?.len()));
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-empty-result 'absent'` and `rippy fake-empty-result 'absent' --no-summary-on-empty` on a test directory without any matches:
+    /// 
+    /// ```shell
+    ///  fake-empty-result
+    /// 
+    /// 0 matches for absent, 2 searched
+    /// ```
+    /// 
+    /// Testing functionality of the zero match output keeping a single blank line between the root and summary, and `[--no-summary-on-empty]` leaving the summary out.
+    pub fn test_empty_result() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-empty-result";
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("a.txt", Some("present"))?;
+        test_dir.generate("b.txt", Some("also present"))?;
+
+        let run = |flags: &[&str]| std::process::Command::new(env!("CARGO_BIN_EXE_rippy")).args([ROOT_TEST_DIR, "absent"]).args(flags).output();
+        assert_eq!(String::from_utf8_lossy(&run(&[])?.stdout), " fake-empty-result\n\n0 matches for absent, 2 searched\n");
+        assert_eq!(String::from_utf8_lossy(&run(&["--table"])?.stdout), " fake-empty-result\n\n0 matches for absent, 2 searched\n");
+        assert_eq!(String::from_utf8_lossy(&run(&["--just-counts"])?.stdout), "0 matches for absent, 2 searched\n");
+        assert!(!String::from_utf8_lossy(&run(&["--no-summary-on-empty"])?.stdout).contains("matches"));
+        let with_matches = std::process::Command::new(env!("CARGO_BIN_EXE_rippy")).args([ROOT_TEST_DIR, "present", "--no-summary-on-empty"]).output()?;
+        assert!(String::from_utf8_lossy(&with_matches.stdout).ends_with("\n\n2 matches for present, 2 searched\n"));
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-bom --bom --output ...` with JSON and YAML exports on a small test directory.
+    /// 
+    /// Testing functionality of `[--bom]` to start every exported file with the UTF-8 byte order mark `EF BB BF` while leaving it out by default.
+    pub fn test_bom() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-bom";
+        const JSON_FILE: &str = "fake-bom/out.json";
+        const YAML_FILE: &str = "fake-bom/out.yaml";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--bom", "-I", "out.*", ROOT_TEST_DIR]));
+        static ARGS_NO_BOM: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "-I", "out.*", ROOT_TEST_DIR]));
+        const BOM: [u8; 3] = [0xEF, 0xBB, 0xBF];
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("notes.txt", Some("some notes"))?;
+
+        let tree_output = tree::build_tree_from_paths(crawl::crawl_directory(&ARGS)?.paths, &ARGS);
+        for output in [JSON_FILE, YAML_FILE] {
+            tree_output.write_to_file(output, &ARGS)?;
+            assert!(std::fs::read(output)?.starts_with(&BOM));
+            tree_output.write_to_file(output, &ARGS_NO_BOM)?;
+            assert!(!std::fs::read(output)?.starts_with(&BOM));
+        }
+        tree_output.write_to_file(JSON_FILE, &ARGS)?;
+        let json_bytes = std::fs::read(JSON_FILE)?;
+        let json_received: serde_json::Value = serde_json::from_slice(&json_bytes[BOM.len()..]).unwrap();
+        assert_eq!(json_received["tree"], tree_output.to_json(&ARGS));
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-glob '**/main.rs' --glob` on a nested test directory to generate:
+    /// 
+    /// ```shell
+    ///  fake-glob
+    ///  ╰── src
+    ///      ╰── main.rs
+    /// ```
+    /// 
+    /// Testing functionality of `[--glob]` to match the pattern as a path glob relative to the root, pruning everything else rather than searching contents.
+    pub fn test_glob() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-glob";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "**/main.rs", "--glob"]));
+        static ARGS_SHALLOW: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "*.md", "--glob"]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("src/main.rs", Some("fn main() {}"))?;
+        test_dir.generate("src/lib.rs", Some("main.rs"))?;
+        test_dir.generate("tests/main_test.rs", Some("main.rs"))?;
+        test_dir.generate("README.md", Some("main.rs"))?;
+        test_dir.generate("docs/guide.md", Some("main.rs"))?;
+        assert!(!ARGS.is_search && ARGS.pattern.is_none());
+
+        let relative_paths = |args: &'static rippy::args::RippyArgs| -> Result<Vec<String>, DirError> {
+            let mut paths: Vec<String> = crawl::crawl_directory(args)?.paths.into_iter().map(|leaf| leaf.relative_path).collect();
+            paths.sort();
+            Ok(paths)
+        };
+        assert_eq!(relative_paths(&ARGS)?, vec!["fake-glob/src/main.rs"]);
+        // A single `*` doesn't cross directories
+        assert_eq!(relative_paths(&ARGS_SHALLOW)?, vec!["fake-glob/README.md"]);
+
+        let tree_output = tree::build_tree_from_paths(crawl::crawl_directory(&ARGS)?.paths, &ARGS);
+        let mut counts = tree::TreeCounts::new();
+        tree::count_tree(&tree_output, &mut counts, true);
+        assert_eq!(counts, tree::TreeCounts{ dir_count: 1, file_count: 1});
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-config --config fake-config/custom.toml` with and without `--sort name` on a test directory with files of varying sizes.
+    /// 
+    /// Testing functionality of `[--config]` supplying default options from a TOML file, with those given explicitly taking precedence and replacing list options and flags from the file.
+    pub fn test_config_file() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-config";
+        const CONFIG_FILE: &str = "fake-config/custom.toml";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--config", CONFIG_FILE, ROOT_TEST_DIR]));
+        static ARGS_OVERRIDE: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--config", CONFIG_FILE, "--sort", "name", "-I", "skip.log", ROOT_TEST_DIR]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("custom.toml", Some("# defaults\nsort = \"size\"\nsize = true\nmax_depth = 3\nignore = [\"*.toml\"]\n"))?;
+        test_dir.generate("a.txt", Some("X".repeat(300)))?;
+        test_dir.generate("b.txt", Some("X".repeat(10)))?;
+        test_dir.generate("c.txt", Some("X".repeat(120)))?;
+        test_dir.generate("skip.log", Some("X".repeat(50)))?;
+
+        let sorted_names = |args: &'static rippy::args::RippyArgs| -> Result<Vec<String>, DirError> {
+            let mut tree_output = tree::build_tree_from_paths(crawl::crawl_directory(args)?.paths, args);
+            tree_output.sort_recursive(args.sort_by);
+            Ok(tree_output.children.values().map(|child| child.name.clone()).collect())
+        };
+        assert!(ARGS.show_size);
+        assert_eq!(ARGS.max_depth, 3);
+        assert_eq!(sorted_names(&ARGS)?, vec!["b.txt", "skip.log", "c.txt", "a.txt"]);
+        // Ignoring `skip.log` on the command line replaces the `*.toml` ignored by the config rather than adding to it
+        assert_eq!(sorted_names(&ARGS_OVERRIDE)?, vec!["a.txt", "b.txt", "c.txt", "custom.toml"]);
+        let args_flag_off = generate_args_from(vec!["rippy", "--config", CONFIG_FILE, "--size=false", "--max-depth=5", ROOT_TEST_DIR]);
+        assert!(!args_flag_off.show_size);
+        assert_eq!(args_flag_off.max_depth, 5);
+        assert!(args_flag_off.is_size_sort);
+
+        let entries = rippy::config::parse_config("sort = 'size' # comment\nignore = [\"a#b\", 'c',]\nall = false\n").unwrap();
+        assert_eq!(entries, vec![
+            ("sort".to_string(), vec![rippy::config::ConfigValue::Text("size".to_string())]),
+            ("ignore".to_string(), vec![rippy::config::ConfigValue::Text("a#b".to_string()), rippy::config::ConfigValue::Text("c".to_string())]),
+            ("all".to_string(), vec![rippy::config::ConfigValue::Bool(false)]),
+        ]);
+        assert!(rippy::config::parse_config("[section]\nsort = \"size\"").is_err());
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-native-separators --relative-path --native-separators` on a test directory with nested files.
+    /// 
+    /// Testing functionality of `[--native-separators]` displaying paths with the separator of the platform, backslashes on Windows, while traversal is unaffected.
+    pub fn test_native_separators() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-native-separators";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "--relative-path", "--native-separators", "--sort", "name"]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("sub/inner/a.txt", Some("a"))?;
+
+        let mut tree_output = tree::build_tree_from_paths(crawl::crawl_directory(&ARGS)?.paths, &ARGS);
+        let mut buffer = Vec::new();
+        tree::write_results_to_buf(&mut tree_output, &ARGS, &mut rippy::tree::TreeCounts::new(), &mut buffer)?;
+        let output = String::from_utf8_lossy(&buffer);
+        let separator = std::path::MAIN_SEPARATOR_STR;
+        let expected = ["sub", "inner", "a.txt"].join(separator);
+        assert!(output.contains(&format!("{ROOT_TEST_DIR}{separator}{expected}")), "{output}");
+        #[cfg(windows)]
+        assert!(output.contains(r"fake-native-separators\sub\inner\a.txt"), "{output}");
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-window-below --gray --window-below 'needle'` on a test directory with a match nested in a subdirectory.
+    /// 
+    /// Testing functionality of `[--window-below]` placing the window on its own line indented beneath the file name rather than beside it.
+    pub fn test_window_below() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-window-below";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--gray", "--window-below", ROOT_TEST_DIR, "needle"]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("src/found.txt", Some("a needle in a haystack"))?;
+
+        let mut tree_output = tree::build_tree_from_paths(crawl::crawl_directory(&ARGS)?.paths, &ARGS);
+        let mut writer: Vec<u8> = Vec::new();
+        tree::write_tree(&mut tree_output, &ARGS, &mut tree::TreeCounts::new(), &mut writer)?;
+        let output = String::from_utf8_lossy(&writer);
+        let lines: Vec<&str> = output.lines().collect();
+        let entry_index = lines.iter().position(|line| line.contains("found.txt")).expect("entry should be listed");
+        assert!(lines[entry_index].ends_with("found.txt"), "{output}");
+        let window_line = lines[entry_index + 1];
+        assert_eq!(window_line.trim_start(), "a needle in a haystack");
+        // The window is indented past the connector of the entry above it
+        let entry_indent = lines[entry_index].chars().take_while(|c| *c != 'f').count();
+        let window_indent = window_line.chars().take_while(|c| !c.is_alphanumeric()).count();
+        assert!(window_indent > entry_indent, "{output}");
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-du --du` and `rippy fake-du --du -I skip.log` on a test directory with files of known sizes.
+    /// 
+    /// Testing functionality of `[--du]` printing only the total size of the root, respecting ignore filters.
+    pub fn test_du() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-du";
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("a.txt", Some("X".repeat(100)))?;
+        test_dir.generate("nested/b.txt", Some("X".repeat(250)))?;
+        test_dir.generate("nested/deeper/c.txt", Some("X".repeat(40)))?;
+        test_dir.generate("skip.log", Some("X".repeat(300)))?;
+
+        let output = std::process::Command::new(env!("CARGO_BIN_EXE_rippy")).args([ROOT_TEST_DIR, "--du"]).output()?;
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "690 B\tfake-du\n");
+        let output = std::process::Command::new(env!("CARGO_BIN_EXE_rippy")).args([ROOT_TEST_DIR, "--du", "-I", "skip.log"]).output()?;
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "390 B\tfake-du\n");
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-number-format --gray -s --number-format de` on a test directory with a file of 1,500 bytes.
+    /// 
+    /// Testing functionality of `[--number-format]` rendering sizes and other numbers with the thousands and decimal separators requested.
+    pub fn test_number_format() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-number-format";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--gray", "-s", "--number-format", "de", ROOT_TEST_DIR]));
+        static ARGS_SPEC: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--number-format", " ,", ROOT_TEST_DIR]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("file.txt", Some("X".repeat(1_500)))?;
+
+        let us_format = rippy::tree::US_NUMBER_FORMAT;
+        assert_eq!(us_format.format(1_234_567.0, 0), "1,234,567");
+        assert_eq!(ARGS.number_format.format(1_234_567.0, 0), "1.234.567");
+        assert_eq!(ARGS.number_format.format(1_234.5, 1), "1.234,5");
+        assert_eq!(ARGS_SPEC.number_format.format(1_234_567.25, 2), "1 234 567,25");
+        assert_eq!(ARGS.number_format.format(999.0, 0), "999");
+
+        let mut tree_output = tree::build_tree_from_paths(crawl::crawl_directory(&ARGS)?.paths, &ARGS);
+        let mut writer: Vec<u8> = Vec::new();
+        tree::write_tree(&mut tree_output, &ARGS, &mut tree::TreeCounts::new(), &mut writer)?;
+        assert!(String::from_utf8_lossy(&writer).contains("(1,5 K) file.txt"));
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-output-exclude -O fake-output-exclude/result.json` on a test directory that already holds the output of a previous run.
+    /// 
+    /// Testing functionality of `[--output | -O]` excluding output files within the scanned directory from the results.
+    pub fn test_output_excluded() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-output-exclude";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "-O", "fake-output-exclude/./result.json", "-O", "fake-output-exclude/nested/result.json"]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("result.json", Some("{}"))?;
+        test_dir.generate("nested/result.json", Some("{}"))?;
+        test_dir.generate("nested/other.json", Some("{}"))?;
+        test_dir.generate("other.txt", Some("kept"))?;
+
+        let tree_output = tree::build_tree_from_paths(crawl::crawl_directory(&ARGS)?.paths, &ARGS);
+        tree_output.write_to_file(&ARGS.output[0], &ARGS)?;
+        let tree_output = tree::build_tree_from_paths(crawl::crawl_directory(&ARGS)?.paths, &ARGS);
+        let mut names: Vec<&str> = tree_output.iter().skip(1).map(|entry| entry.name.as_str()).collect();
+        names.sort();
+        assert_eq!(names, vec!["nested", "other.json", "other.txt"]);
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Builds the tree of a test directory without searching, marks a single file as a match and prunes the tree to the matches.
+    /// 
+    /// Testing functionality of `Tree::prune_to_matches` removing files without a window and directories without matching descendants.
+    pub fn test_prune_to_matches() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-prune-matches";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("found/deeper/match.txt", Some("needle"))?;
+        test_dir.generate("found/sibling.txt", Some("hay"))?;
+        test_dir.generate("missed/other.txt", Some("hay"))?;
+        test_dir.generate("top.txt", Some("hay"))?;
+
+        let mut tree_output = tree::build_tree_from_paths(crawl::crawl_directory(&ARGS)?.paths, &ARGS);
+        let matched = tree_output.children.get_mut("found")
+            .and_then(|dir| dir.children.get_mut("deeper"))
+            .and_then(|dir| dir.children.get_mut("match.txt"))
+            .expect("file should be in the tree");
+        matched.window = Some("needle".to_string());
+        tree_output.prune_to_matches();
+        let names: Vec<&str> = tree_output.iter().skip(1).map(|entry| entry.name.as_str()).collect();
+        assert_eq!(names, vec!["found", "deeper", "match.txt"]);
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-exclude-dir --exclude-dir node_modules,target/` on a test directory with nested excluded directories and a file named after one of them.
+    /// 
+    /// Testing functionality of `[--exclude-dir]` pruning matching directories and their contents while keeping files of the same name.
+    pub fn test_exclude_dir() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-exclude-dir";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "--exclude-dir", "node_modules,target/"]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("node_modules/pkg/index.js", Some("module"))?;
+        test_dir.generate("app/target/debug/app.bin", Some("binary"))?;
+        test_dir.generate("app/main.rs", Some("fn main() {}"))?;
+        test_dir.create_file("app/node_modules", Some("a file, not a directory"))?;
+
+        let crawl_results = crawl::crawl_directory(&ARGS)?;
+        let mut paths: Vec<&str> = crawl_results.paths.iter().map(|leaf| leaf.relative_path.as_str()).collect();
+        paths.sort();
+        assert_eq!(paths, vec!["fake-exclude-dir/app", "fake-exclude-dir/app/main.rs", "fake-exclude-dir/app/node_modules"]);
+        assert_eq!(crawl_results.paths_ignored, 2);
+        test_dir.clean()
+    }
+
+    #[test]
+    #[cfg(unix)]
+    /// Runs `rippy fake-normalize-unix --relative-path` on a test directory with a file whose name contains a backslash.
+    /// 
+    /// Testing functionality of `normalize_path` leaving backslashes in names untouched where they are not separators.
+    pub fn test_normalize_path_unix() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-normalize-unix";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "--relative-path"]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("dir/back\\slash.txt", Some("odd name"))?;
+
+        assert_eq!(tree::normalize_path(std::path::Path::new("dir/back\\slash.txt")), "dir/back\\slash.txt");
+        let crawl_results = crawl::crawl_directory(&ARGS)?;
+        assert!(crawl_results.paths.iter().any(|leaf| leaf.relative_path == "fake-normalize-unix/dir/back\\slash.txt" && leaf.name == "back\\slash.txt"));
+        test_dir.clean()
+    }
+
+    #[test]
+    #[cfg(windows)]
+    /// Normalizes paths using backslash separators.
+    /// 
+    /// Testing functionality of `normalize_path` converting backslash separators to forward slashes on Windows.
+    pub fn test_normalize_path_windows() {
+        assert_eq!(tree::normalize_path(std::path::Path::new(r"dir\sub\file.txt")), "dir/sub/file.txt");
+        assert_eq!(tree::normalize_path(std::path::Path::new(r"C:\dir/mixed\file.txt")), "C:/dir/mixed/file.txt");
+    }
+
+    #[test]
+    /// Runs `rippy fake-top-dirs --top-dirs 2` on a test directory with directories of differing total content.
+    /// 
+    /// Testing functionality of `[--top-dirs]` listing the largest directories by recursive size, largest first.
+    pub fn test_top_dirs() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-top-dirs";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--top-dirs", "2", ROOT_TEST_DIR]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("small/a.txt", Some("X".repeat(50)))?;
+        test_dir.generate("medium/a.txt", Some("X".repeat(100)))?;
+        test_dir.generate("large/a.txt", Some("X".repeat(120)))?;
+        test_dir.generate("large/nested/b.txt", Some("X".repeat(200)))?;
+        test_dir.generate("top.txt", Some("X".repeat(900)))?;
+
+        let mut tree_output = tree::build_tree_from_paths(crawl::crawl_directory(&ARGS)?.paths, &ARGS);
+        tree_output.calculate_sizes();
+        assert_eq!(tree_output.largest_dirs(ARGS.top_dirs.unwrap_or_default()), vec![
+            ("fake-top-dirs/large".to_string(), 320),
+            ("fake-top-dirs/large/nested".to_string(), 200),
+        ]);
+        assert_eq!(tree_output.largest_dirs(10).iter().map(|(dir, _)| dir.as_str()).collect::<Vec<&str>>(), vec![
+            "fake-top-dirs/large", "fake-top-dirs/large/nested", "fake-top-dirs/medium", "fake-top-dirs/small",
+        ]);
+
+        let output = std::process::Command::new(env!("CARGO_BIN_EXE_rippy")).args([ROOT_TEST_DIR, "--top-dirs", "2"]).output()?;
+        assert_eq!(String::from_utf8_lossy(&output.stdout), " 320 B  fake-top-dirs/large\n 200 B  fake-top-dirs/large/nested\n");
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-include-dirs --include '*.rs'` on a test directory with matching files in directories whose names don't match.
+    /// 
+    /// Testing functionality of `[--include | -X]` keeping directories that contain included files and dropping those without any, whatever their names.
+    pub fn test_include_keeps_dirs() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-include-dirs";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "--include", "*.rs"]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("utils/lib.rs", Some("pub fn util() {}"))?;
+        test_dir.generate("src/nested/deep/mod.rs", Some("mod deep;"))?;
+        test_dir.generate("docs/guide.md", Some("# Guide"))?;
+        test_dir.generate("empty.rs/notes.txt", Some("not rust"))?;
+
+        let crawl_results = crawl::crawl_directory(&ARGS)?;
+        let mut paths: Vec<&str> = crawl_results.paths.iter().map(|leaf| leaf.relative_path.as_str()).collect();
+        paths.sort();
+        assert_eq!(paths, vec![
+            "fake-include-dirs/src",
+            "fake-include-dirs/src/nested",
+            "fake-include-dirs/src/nested/deep",
+            "fake-include-dirs/src/nested/deep/mod.rs",
+            "fake-include-dirs/utils",
+            "fake-include-dirs/utils/lib.rs",
+        ]);
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-dotfiles` with `--include '.*'`, `--include .env` and `--ignore '.*'` on a test directory with dotfiles, with and without `--all`.
+    /// 
+    /// Testing functionality of `[--include | -X]` and `[--ignore | -I]` matching dotfiles explicitly independent of `[--all | -a]`.
+    pub fn test_dotfile_patterns() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-dotfiles";
+        static ARGS_INCLUDE_ALL: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "--all", "--include", ".*"]));
+        static ARGS_IGNORE_ALL: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "--all", "--ignore", ".*"]));
+        static ARGS_INCLUDE: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "--include", ".env,*.txt"]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.create_file(".env", Some("KEY=value"))?;
+        test_dir.create_file(".profile", Some("export PATH"))?;
+        test_dir.generate("config/.hidden.txt", Some("hidden"))?;
+        test_dir.generate("config/settings.txt", Some("visible"))?;
+
+        let crawled_names = |args: &'static rippy::args::RippyArgs| -> Result<Vec<String>, DirError> {
+            let mut names: Vec<String> = crawl::crawl_directory(args)?.paths.into_iter().map(|leaf| leaf.name).collect();
+            names.sort();
+            Ok(names)
+        };
+        assert_eq!(crawled_names(&ARGS_INCLUDE_ALL)?, vec![".env", ".hidden.txt", ".profile", "config"]);
+        assert_eq!(crawled_names(&ARGS_IGNORE_ALL)?, vec!["config", "settings.txt"]);
+        // Only patterns naming dotfiles explicitly list them without `--all`
+        assert_eq!(crawled_names(&ARGS_INCLUDE)?, vec![".env", "config", "settings.txt"]);
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-missing-dir TODO --error-format json` and `rippy fake-error-format -O fake-error-format/missing/out.json --error-format json` where neither the directory nor the output location exist.
+    /// 
+    /// Testing functionality of `[--error-format]` reporting errors on stderr as parseable JSON objects.
+    pub fn test_error_format_json() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-error-format";
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("file.txt", Some("contents"))?;
+
+        let output = std::process::Command::new(env!("CARGO_BIN_EXE_rippy")).args(["fake-missing-dir", "TODO", "--error-format", "json"]).output()?;
+        assert!(!output.status.success());
+        let error: serde_json::Value = serde_json::from_slice(&output.stderr).expect("error should be valid JSON");
+        assert_eq!(error["kind"], "invalid_directory");
+        assert_eq!(error["path"], "fake-missing-dir");
+        assert!(error["error"].as_str().is_some_and(|message| message.contains("does not exist")));
+
+        let output = std::process::Command::new(env!("CARGO_BIN_EXE_rippy")).args([ROOT_TEST_DIR, "-O", "fake-error-format/missing/out.json", "--error-format", "json"]).output()?;
+        let error: serde_json::Value = serde_json::from_slice(&output.stderr).expect("error should be valid JSON");
+        assert_eq!(error["kind"], "output");
+        assert_eq!(error["path"], "fake-error-format/missing/out.json");
+
+        // Text errors remain the default
+        let output = std::process::Command::new(env!("CARGO_BIN_EXE_rippy")).args(["fake-missing-dir", "TODO"]).output()?;
+        assert!(String::from_utf8_lossy(&output.stderr).contains("does not exist or is not a valid directory"));
+        assert!(serde_json::from_slice::<serde_json::Value>(&output.stderr).is_err());
+        test_dir.clean()
+    }
+
+    #[test]
+    #[cfg(unix)]
+    /// Runs `rippy fake-follow-links --follow-links` on a test directory with a broken link to a directory and a link back to an ancestor.
+    /// 
+    /// Testing functionality of `[--follow-links]` completing the crawl by listing links that can't be followed with a marker instead of walking or aborting on them.
+    pub fn test_follow_links_unfollowable() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-follow-links";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "--follow-links"]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("a/b/file.txt", Some("contents"))?;
+        std::os::unix::fs::symlink("../..", test_dir.join("a/b/loop"))?;
+        std::os::unix::fs::symlink("missing/dir", test_dir.join("broken"))?;
+
+        let crawl_results = crawl::crawl_directory(&ARGS)?;
+        let mut paths: Vec<&str> = crawl_results.paths.iter().map(|leaf| leaf.relative_path.as_str()).collect();
+        paths.sort();
+        assert_eq!(paths, vec![
+            "fake-follow-links/a",
+            "fake-follow-links/a/b",
+            "fake-follow-links/a/b/file.txt",
+            "fake-follow-links/a/b/loop",
+            "fake-follow-links/broken",
+        ]);
+        let display = |name: &str| crawl_results.paths.iter().find(|leaf| leaf.name == name).map(|leaf| leaf.display.clone()).unwrap_or_default();
+        assert!(display("broken").contains("missing/dir") && display("broken").contains("[broken link]"));
+        assert!(display("loop").contains("[cycle, not followed]"));
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-filename-regex --filename-regex '^test_.*\.rs$'`, with and without the content pattern `assert`, on a test directory of test and source files.
+    /// 
+    /// Testing functionality of `[--filename-regex]` keeping only files with matching names, combined with any content search.
+    pub fn test_filename_regex() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-filename-regex";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "--filename-regex", r"^test_.*\.rs$"]));
+        static ARGS_SEARCH: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "assert", "--filename-regex", r"^test_.*\.rs$"]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("tests/test_args.rs", Some("assert!(true);"))?;
+        test_dir.generate("tests/test_tree.rs", Some("fn helper() {}"))?;
+        test_dir.generate("tests/common.rs", Some("assert!(true);"))?;
+        test_dir.generate("src/test_data.txt", Some("assert"))?;
+        test_dir.generate("src/main.rs", Some("assert!(true);"))?;
+
+        let crawled_names = |args: &'static rippy::args::RippyArgs| -> Result<Vec<String>, DirError> {
+            let mut names: Vec<String> = crawl::crawl_directory(args)?.paths.into_iter().map(|leaf| leaf.name).collect();
+            names.sort();
+            Ok(names)
+        };
+        assert_eq!(crawled_names(&ARGS)?, vec!["test_args.rs", "test_tree.rs", "tests"]);
+        assert_eq!(crawled_names(&ARGS_SEARCH)?, vec!["test_args.rs"]);
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-max-name-length --max-name-length 10`
+    /// Testing functionality of truncating long displayed names while exports keep the full name.
+    pub fn test_max_name_length() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-max-name-length";
+        const LONG_NAME: &str = "abcdefghijklmnopqrstuvwxyz0123456789.txt";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "--max-name-length", "10"]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate(LONG_NAME, Some("contents"))?;
+        test_dir.generate("short.rs", Some("contents"))?;
+        assert_eq!(LONG_NAME.chars().count(), 40);
+
+        let crawl_results = crawl::crawl_directory(&ARGS)?;
+        let tree = tree::build_tree_from_paths(crawl_results.paths, &ARGS);
+        let mut displays: Vec<String> = tree.iter().skip(1).map(|node| node.display.clone()).collect();
+        displays.sort();
+        assert_eq!(displays, vec!["abcdefghij…", "short.rs"]);
+
+        let json = tree.to_json(&ARGS);
+        let mut names: Vec<&str> = json["children"].as_array().unwrap().iter().map(|child| child["name"].as_str().unwrap()).collect();
+        names.sort();
+        assert_eq!(names, vec![LONG_NAME, "short.rs"]);
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-notify --notify`
+    /// Testing functionality of the notification emitted once finished and its absence from output that isn't a terminal.
+    pub fn test_notify() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-notify";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "--notify"]));
+        static ARGS_BARE: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("file.txt", Some("contents"))?;
+
+        let notification = rippy::args::format_notification(&ARGS).expect("notification requested");
+        assert!(notification.starts_with("\x1b]9;rippy finished in fake-notify"));
+        assert!(notification.ends_with('\x07'));
+        assert_eq!(rippy::args::format_notification(&ARGS_BARE), None);
+
+        // Piped output is not a terminal so nothing is emitted
+        let output = std::process::Command::new(env!("CARGO_BIN_EXE_rippy")).args([ROOT_TEST_DIR, "--notify"]).output()?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(!stdout.contains('\x07') && !stdout.contains("\x1b]9;"));
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-group-similar --group-similar`
+    /// Testing functionality of `Tree::group_similar` collapsing a numbered sequence into one summary entry.
+    pub fn test_group_similar() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-group-similar";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "--group-similar", "-s"]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        for i in 1..=12 {
+            test_dir.generate(format!("frames/frame{i:04}.png"), Some("png"))?;
+        }
+        test_dir.generate("frames/notes1.txt", Some("a"))?;
+        test_dir.generate("frames/notes2.txt", Some("b"))?;
+        test_dir.generate("frames/cover.png", Some("png"))?;
+
+        let crawl_results = crawl::crawl_directory(&ARGS)?;
+        let mut tree = tree::build_tree_from_paths(crawl_results.paths, &ARGS);
+        assert_eq!(tree.group_similar(), 11);
+        let mut displays: Vec<String> = tree.iter().skip(2).map(|node| node.display.clone()).collect();
+        displays.sort();
+        assert_eq!(displays, vec!["cover.png", "frame####.png (12 files)", "notes1.txt", "notes2.txt"]);
+        let summary = tree.iter().find(|node| node.name == "frame####.png").expect("summary entry");
+        assert_eq!(summary.size, Some(36));
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-ascii --ascii`
+    /// Testing functionality of rendering the tree with ASCII characters only.
+    pub fn test_ascii() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-ascii";
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("src/main.rs", Some("fn main() {}"))?;
+        test_dir.generate("src/lib.rs", Some("pub mod args;"))?;
+        test_dir.generate("a_very_long_file_name.txt", Some("contents"))?;
+
+        let output = std::process::Command::new(env!("CARGO_BIN_EXE_rippy")).args([ROOT_TEST_DIR, "--ascii", "--max-name-length", "12"]).output()?;
+        assert!(output.stdout.is_ascii());
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains("|-- a_very_long_..."));
+        assert!(stdout.contains("`-- src"));
+        assert!(stdout.contains("    |-- lib.rs"));
+        assert!(stdout.contains("    `-- main.rs"));
+        assert!(stdout.contains("1 directory, 3 files"));
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-no-root-bold --no-root-bold`
+    /// Testing functionality of `[--no-root-bold]` to remove the bold styling of the root line only.
+    pub fn test_no_root_bold() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-no-root-bold";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "--no-root-bold"]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("src/main.rs", Some("fn main() {}"))?;
+
+        let crawl_results = crawl::crawl_directory(&ARGS)?;
+        let mut tree_output = tree::build_tree_from_paths(crawl_results.paths, &ARGS);
+        let mut counts = tree::TreeCounts::new();
+        let mut writer: Vec<u8> = Vec::new();
+        tree::write_tree(&mut tree_output, &ARGS, &mut counts, &mut writer)?;
+        let output = String::from_utf8_lossy(&writer).to_string();
+        // Only meaningful when colors are enabled, which requires a terminal
+        if !ARGS.is_grayscale {
+            let (root_line, children) = output.split_once('\n').expect("root line followed by children");
+            assert!(root_line.contains(ROOT_TEST_DIR) && !root_line.contains("\x1b[1m"));
+            assert!(children.contains("\x1b[1m"));
+        }
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-tracked --tracked` within a freshly initialized git repository.
+    /// Testing functionality of listing only the files git tracks along with their directories.
+    pub fn test_tracked() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-tracked";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "--tracked"]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("src/main.rs", Some("fn main() {}"))?;
+        test_dir.generate("Cargo.toml", Some("[package]"))?;
+        test_dir.generate("notes.txt", Some("untracked"))?;
+        test_dir.generate("target/debug/build.log", Some("artifact"))?;
+        let git = |args: &[&str]| std::process::Command::new("git").arg("-C").arg(ROOT_TEST_DIR).args(args).output();
+        git(&["init", "-q"])?;
+        git(&["add", "src/main.rs", "Cargo.toml"])?;
+
+        let mut names: Vec<String> = crawl::crawl_directory(&ARGS)?.paths.into_iter().map(|leaf| leaf.relative_path).collect();
+        names.sort();
+        assert_eq!(names, vec!["fake-tracked/Cargo.toml", "fake-tracked/src", "fake-tracked/src/main.rs"]);
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-subtree --subtree src/parser/ --ascii-space`
+    /// Testing functionality of `Tree::find_by_path` and rendering only the named subtree.
+    pub fn test_subtree() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-subtree";
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("src/parser/lexer.rs", Some("lex"))?;
+        test_dir.generate("src/parser/ast/node.rs", Some("node"))?;
+        test_dir.generate("src/main.rs", Some("fn main() {}"))?;
+        test_dir.generate("README.md", Some("readme"))?;
+
+        let output = std::process::Command::new(env!("CARGO_BIN_EXE_rippy")).args([ROOT_TEST_DIR, "--subtree", "src/parser/", "--ascii-space"]).output()?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let lines: Vec<&str> = stdout.lines().map(str::trim).collect();
+        assert_eq!(lines, vec!["parser", "├── ast", "│   ╰── node.rs", "╰── lexer.rs", "", "1 directory, 2 files"]);
+
+        let missing = std::process::Command::new(env!("CARGO_BIN_EXE_rippy")).args([ROOT_TEST_DIR, "--subtree", "src/missing"]).output()?;
+        assert!(!missing.status.success());
+        assert!(String::from_utf8_lossy(&missing.stderr).contains("src/missing"));
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Testing functionality of `tcolor::format_ansi_warning` describing a failure to enable ANSI support, with `tcolor::try_enable_ansi_support` succeeding where nothing needs enabling.
+    pub fn test_ansi_warning() {
+        let error = std::io::Error::other("The handle is invalid.");
+        assert_eq!(rippy::tcolor::format_ansi_warning(&error), "Warning: colors were disabled because the Windows console could not enable virtual terminal processing (The handle is invalid.)");
+        #[cfg(not(windows))]
+        assert!(rippy::tcolor::try_enable_ansi_support().is_ok());
+    }
+
+    #[test]
+    /// Runs `rippy fake-type-dates --dir-date` and `rippy fake-type-dates --file-date` with a literal date format.
+    /// Testing functionality of `[--dir-date]` and `[--file-date]` to display dates for a single type of entry.
+    pub fn test_type_dates() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-type-dates";
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("src/main.rs", Some("fn main() {}"))?;
+        test_dir.generate("README.md", Some("readme"))?;
+
+        let dated_lines = |flag: &str| -> Result<Vec<String>, DirError> {
+            let output = std::process::Command::new(env!("CARGO_BIN_EXE_rippy")).args([ROOT_TEST_DIR, flag, "--date-format", "STAMP"]).output()?;
+            Ok(String::from_utf8_lossy(&output.stdout).lines()
+                .filter_map(|line| line.split_once("(STAMP) ").map(|(_, name)| name.to_string()))
+                .collect())
+        };
+        assert_eq!(dated_lines("--dir-date")?, vec!["src"]);
+        assert_eq!(dated_lines("--file-date")?, vec!["README.md", "main.rs"]);
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-date-range 'needle' --since 2024-01-01 --until 2024-06-01` with files modified before, within and after the range.
+    /// Testing functionality of `[--since]` and `[--until]` to skip searching files modified outside of the range.
+    pub fn test_date_range() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-date-range";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "needle", "--since", "2024-01-01", "--until", "2024-06-01"]));
+        static ARGS_LISTING: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "--since", "2024-03-01 12:00:00"]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        let set_modified = |file: &str, secs: u64| -> Result<(), DirError> {
+            test_dir.generate(file, Some("a needle in a haystack"))?;
+            let path = std::path::Path::new(ROOT_TEST_DIR).join(file);
+            std::fs::File::options().write(true).open(path)?.set_modified(std::time::UNIX_EPOCH + std::time::Duration::from_secs(secs))?;
+            Ok(())
+        };
+        set_modified("old/before.txt", 1_672_531_200)?; // 2023-01-01
+        set_modified("within.txt", 1_709_294_400)?; // 2024-03-01 12:00:00
+        set_modified("after.txt", 1_735_689_600)?; // 2025-01-01
+
+        let names = |args: &'static rippy::args::RippyArgs| -> Result<Vec<String>, DirError> {
+            let mut names: Vec<String> = crawl::crawl_directory(args)?.paths.into_iter().map(|leaf| leaf.name).collect();
+            names.sort();
+            Ok(names)
+        };
+        assert_eq!(names(&ARGS)?, vec!["within.txt"]);
+        assert_eq!(names(&ARGS_LISTING)?, vec!["after.txt", "within.txt"]);
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy --config fake-show-config/custom.toml fake-show-config --max-depth 5 --show-config`
+    /// Testing functionality of `[--show-config]` to print the resolved settings in place of the tree.
+    pub fn test_show_config() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-show-config";
+        const CONFIG_FILE: &str = "fake-show-config/custom.toml";
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("custom.toml", Some("size = true\nmax_depth = 3\n"))?;
+        test_dir.generate("a.txt", Some("contents"))?;
+
+        let output = std::process::Command::new(env!("CARGO_BIN_EXE_rippy")).args(["--config", CONFIG_FILE, ROOT_TEST_DIR, "--max-depth", "5", "--show-config", "--no-pipe", "--match-color", "#ff8800"]).output()?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.starts_with("RippyArgs {"));
+        assert!(stdout.contains("    max_depth: 5,\n"));
+        assert!(stdout.contains("    show_size: true,\n"));
+        assert!(stdout.contains("    sort_by: name ascending,\n"));
+        assert!(stdout.contains("    max_files: unlimited,\n"));
+        assert!(stdout.contains("        dir: Some(\n            \"color 80\",\n        ),\n") && stdout.contains("\"#ff8800\""));
+        assert!(!stdout.contains("0x") && !stdout.contains("\\u{1b}"));
+        assert!(!stdout.contains("a.txt"));
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-size-heat -s --size-heat`
+    /// Testing functionality of `[--size-heat]` to color sizes by magnitude.
+    pub fn test_size_heat() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-size-heat";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "-s", "--size-heat"]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("small.txt", Some("tiny"))?;
+        test_dir.generate("large.txt", Some("X".repeat(2_000_000)))?;
+
+        assert_ne!(rippy::tcolor::size_heat_color(4), rippy::tcolor::size_heat_color(2_000_000));
+        let crawl_results = crawl::crawl_directory(&ARGS)?;
+        let mut tree_output = tree::build_tree_from_paths(crawl_results.paths, &ARGS);
+        let mut counts = tree::TreeCounts::new();
+        let mut writer: Vec<u8> = Vec::new();
+        tree::write_tree(&mut tree_output, &ARGS, &mut counts, &mut writer)?;
+        let output = String::from_utf8_lossy(&writer).to_string();
+        // Only meaningful when colors are enabled, which requires a terminal
+        if !ARGS.is_grayscale {
+            let line_of = |name: &str| output.lines().find(|line| line.contains(name)).unwrap_or_default().to_string();
+            assert!(line_of("small.txt").contains(rippy::tcolor::size_heat_color(4).unwrap()));
+            assert!(line_of("large.txt").contains(rippy::tcolor::size_heat_color(2_000_000).unwrap()));
+            assert!(!line_of("large.txt").contains(rippy::tcolor::size_heat_color(4).unwrap()));
+        }
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-json-matches-only 'needle' --json-matches-only --bare-json -O fake-json-matches-only.json`
+    /// Testing functionality of `[--json-matches-only]` to export only matched files and their ancestors.
+    pub fn test_json_matches_only() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-json-matches-only";
+        const OUTPUT_FILE: &str = "fake-json-matches-only.json";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "needle", "--json-matches-only", "--bare-json"]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("src/found.rs", Some("let needle = 1;"))?;
+        test_dir.generate("src/other.rs", Some("let hay = 1;"))?;
+        test_dir.generate("docs/readme.md", Some("only hay"))?;
+        test_dir.generate("notes.txt", Some("needle"))?;
+
+        let crawl_results = crawl::crawl_directory(&ARGS)?;
+        let tree_output = tree::build_tree_from_paths(crawl_results.paths, &ARGS);
+        tree_output.write_to_file(OUTPUT_FILE, &ARGS)?;
+        let json: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(OUTPUT_FILE)?).unwrap();
+        std::fs::remove_file(OUTPUT_FILE)?;
+
+        fn collect(value: &serde_json::Value, names: &mut Vec<String>) {
+            for child in value["children"].as_array().unwrap() {
+                names.push(child["name"].as_str().unwrap().to_string());
+                if child["entry_type"] == "File" {
+                    assert!(!child["window"].is_null());
+                }
+                collect(child, names);
+            }
+        }
+        let mut names = Vec::new();
+        collect(&json, &mut names);
+        names.sort();
+        assert_eq!(names, vec!["found.rs", "notes.txt", "src"]);
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-elide-middle --elide-middle 3` on a directory with 20 children.
+    /// Testing functionality of `[--elide-middle]` to keep the first and last children with a marker in between.
+    pub fn test_elide_middle() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-elide-middle";
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        for i in 1..=20 {
+            test_dir.generate(format!("file{i:02}.txt"), Some("contents"))?;
+        }
+
+        let output = std::process::Command::new(env!("CARGO_BIN_EXE_rippy")).args([ROOT_TEST_DIR, "--elide-middle", "3"]).output()?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let lines: Vec<&str> = stdout.lines().map(str::trim).collect();
+        assert_eq!(lines, vec![
+            ROOT_TEST_DIR,
+            "├── file01.txt", "├── file02.txt", "├── file03.txt",
+            "├── ... (14 hidden) ...",
+            "├── file18.txt", "├── file19.txt", "╰── file20.txt",
+            "",
+            "0 directories, 20 files",
+        ]);
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-encoding 'café' --encoding latin1` on a Latin-1 encoded file.
+    /// Testing functionality of `[--encoding]` to decode file contents before searching.
+    pub fn test_encoding() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-encoding";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "café", "--encoding", "latin1"]));
+        static ARGS_UTF16: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "café", "--encoding", "utf16le"]));
+        static ARGS_UTF8: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "café"]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("utf8.txt", Some("a café in utf-8"))?;
+        std::fs::write(std::path::Path::new(ROOT_TEST_DIR).join("latin1.txt"), b"un caf\xe9 cr\xe8me")?;
+
+        let matched = |args: &'static rippy::args::RippyArgs| -> Result<Vec<String>, DirError> {
+            let mut names: Vec<String> = crawl::crawl_directory(args)?.paths.into_iter().map(|leaf| leaf.name).collect();
+            names.sort();
+            Ok(names)
+        };
+        assert_eq!(matched(&ARGS)?, vec!["latin1.txt"]);
+        assert_eq!(matched(&ARGS_UTF8)?, vec!["utf8.txt"]);
+        assert_eq!(ARGS_UTF16.encoding, Some(encoding_rs::UTF_16LE));
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-count-total 'needle' --count-matches-total` with and without `--count`.
+    /// Testing functionality of `[--count-matches-total]` to print only the number of matching files or matches.
+    pub fn test_count_matches_total() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-count-total";
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("a.txt", Some("needle and another needle"))?;
+        test_dir.generate("src/b.txt", Some("one needle"))?;
+        test_dir.generate("src/c.txt", Some("only hay"))?;
+
+        let run = |extra: &[&str]| -> Result<String, DirError> {
+            let output = std::process::Command::new(env!("CARGO_BIN_EXE_rippy")).args([ROOT_TEST_DIR, "needle", "--count-matches-total"]).args(extra).output()?;
+            assert!(output.stderr.is_empty());
+            Ok(String::from_utf8_lossy(&output.stdout).to_string())
+        };
+        assert_eq!(run(&[])?, "2\n");
+        assert_eq!(run(&["--count"])?, "3\n");
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-json-indent --json-indent 4 -O fake-json-indent.json` and again with `--json-indent 0`.
+    /// Testing functionality of `[--json-indent]` to control the indentation of exported JSON.
+    pub fn test_json_indent() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-json-indent";
+        const OUTPUT_FILE: &str = "fake-json-indent.json";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "--json-indent", "4"]));
+        static ARGS_COMPACT: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "--json-indent", "0"]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("a.txt", Some("contents"))?;
+
+        let export = |args: &'static rippy::args::RippyArgs| -> Result<String, DirError> {
+            let tree_output = tree::build_tree_from_paths(crawl::crawl_directory(args)?.paths, args);
+            tree_output.write_to_file(OUTPUT_FILE, args)?;
+            let contents = std::fs::read_to_string(OUTPUT_FILE)?;
+            std::fs::remove_file(OUTPUT_FILE)?;
+            Ok(contents)
+        };
+        let indented = export(&ARGS)?;
+        assert!(indented.starts_with("{\n    \"schema\": "));
+        assert!(indented.contains("\n        \"name\": \"fake-json-indent\""));
+        let compact = export(&ARGS_COMPACT)?;
+        assert!(!compact.contains('\n'));
+        let parse = |json: &str| serde_json::from_str::<serde_json::Value>(json).unwrap()["tree"].clone();
+        assert_eq!(parse(&indented), parse(&compact));
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-trailing-slash --relative-path` and `rippy fake-trailing-slash/ --relative-path`
+    /// Testing functionality of normalizing trailing slashes of the directory so both forms render identically.
+    pub fn test_trailing_slash() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-trailing-slash";
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("src/main.rs", Some("fn main() {}"))?;
+        test_dir.generate("README.md", Some("readme"))?;
+
+        let render = |directory: &str| -> Result<Vec<u8>, DirError> {
+            Ok(std::process::Command::new(env!("CARGO_BIN_EXE_rippy")).args([directory, "--relative-path", "-s"]).output()?.stdout)
+        };
+        let expected = render(ROOT_TEST_DIR)?;
+        assert!(String::from_utf8_lossy(&expected).contains("fake-trailing-slash/src/main.rs"));
+        assert_eq!(render("fake-trailing-slash/")?, expected);
+        assert_eq!(render("fake-trailing-slash//")?, expected);
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-no-report --no-report`
+    /// Testing functionality of `[--no-report]` to omit the report line while leaving the tree unchanged.
+    pub fn test_no_report() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-no-report";
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("src/main.rs", Some("fn main() {}"))?;
+        test_dir.generate("README.md", Some("readme"))?;
+
+        let render = |extra: &[&str]| -> Result<String, DirError> {
+            let output = std::process::Command::new(env!("CARGO_BIN_EXE_rippy")).arg(ROOT_TEST_DIR).args(extra).output()?;
+            Ok(String::from_utf8_lossy(&output.stdout).to_string())
+        };
+        let with_report = render(&[])?;
+        let without_report = render(&["--no-report"])?;
+        assert!(with_report.ends_with("1 directory, 2 files\n"));
+        assert!(!without_report.contains("director"));
+        assert_eq!(with_report, without_report + "1 directory, 2 files\n");
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-prune '(?m)^' -W --no-empty-files --prune` and `rippy fake-prune --prune`
+    /// Testing functionality of `[--prune]` to drop directories left without matches or files.
+    pub fn test_prune() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-prune";
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("src/main.rs", Some("fn main() {}"))?;
+        test_dir.generate("blank/empty.txt", Some(""))?;
+        test_dir.create_directory("nested/empty")?;
+
+        let render = |extra: &[&str]| -> Result<Vec<String>, DirError> {
+            let output = std::process::Command::new(env!("CARGO_BIN_EXE_rippy")).arg(ROOT_TEST_DIR).args(extra).args(["--ascii-space", "--no-report"]).output()?;
+            Ok(String::from_utf8_lossy(&output.stdout).lines().map(|line| line.trim().to_string()).filter(|line| !line.is_empty()).collect())
+        };
+        // The only file in `blank` matches but is removed as empty, leaving the directory behind unless pruned
+        assert_eq!(render(&["(?m)^", "-W", "--no-empty-files"])?, vec!["fake-prune", "├── blank", "╰── src", "╰── main.rs"]);
+        assert_eq!(render(&["(?m)^", "-W", "--no-empty-files", "--prune"])?, vec!["fake-prune", "╰── src", "╰── main.rs"]);
+        assert_eq!(render(&["--prune"])?, vec!["fake-prune", "├── blank", "│   ╰── empty.txt", "╰── src", "╰── main.rs"]);
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-read-threads 'needle' --read-threads 1` and without `--read-threads`.
+    /// Testing functionality of `[--read-threads]` to read files on a separate pool without changing the search results.
+    pub fn test_read_threads() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-read-threads";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "needle", "--read-threads", "1"]));
+        static ARGS_DEFAULT: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "needle"]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        for i in 0..20 {
+            let content = if i % 3 == 0 { format!("file {i} has a needle inside") } else { format!("file {i} has only hay") };
+            test_dir.generate(format!("dir{}/file{i}.txt", i % 4), Some(&content))?;
+        }
+
+        let matched = |args: &'static rippy::args::RippyArgs| -> Result<Vec<(String, Option<String>)>, DirError> {
+            let mut leaves: Vec<(String, Option<String>)> = crawl::crawl_directory(args)?.paths.into_iter().map(|leaf| (leaf.relative_path, leaf.window)).collect();
+            leaves.sort();
+            Ok(leaves)
+        };
+        assert_eq!(ARGS.read_threads, Some(1));
+        assert_eq!(ARGS_DEFAULT.read_threads, None);
+        let leaves = matched(&ARGS)?;
+        assert_eq!(leaves.iter().filter(|(path, window)| path.ends_with(".txt") && window.as_ref().is_some_and(|window| window.contains("needle"))).count(), 7);
+        assert_eq!(leaves, matched(&ARGS_DEFAULT)?);
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-show-root-abs --show-root-abs` with and without `--relative-path`.
+    /// Testing functionality of `[--show-root-abs]` to display the root as an absolute path while entries stay relative.
+    pub fn test_show_root_abs() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-show-root-abs";
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("src/main.rs", Some("fn main() {}"))?;
+
+        let render = |extra: &[&str]| -> Result<Vec<String>, DirError> {
+            let output = std::process::Command::new(env!("CARGO_BIN_EXE_rippy")).args([ROOT_TEST_DIR, "--show-root-abs", "--ascii-space"]).args(extra).output()?;
+            Ok(String::from_utf8_lossy(&output.stdout).lines().map(|line| line.trim().to_string()).collect())
+        };
+        let absolute_root = std::path::absolute(ROOT_TEST_DIR)?.to_string_lossy().to_string();
+        let lines = render(&[])?;
+        assert_eq!(lines[0], absolute_root);
+        assert!(lines.iter().any(|line| line.ends_with(" src")));
+        let lines = render(&["--relative-path"])?;
+        assert_eq!(lines[0], absolute_root);
+        assert!(lines.iter().any(|line| line.ends_with(" fake-show-root-abs/src/main.rs")));
+        assert!(!lines.iter().skip(1).any(|line| line.contains(&absolute_root)));
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-depth-limit --max-depth` at 1, 2 and 3 with an empty directory sitting exactly at each limit.
+    /// Testing functionality of `[--max-depth | -L]` to list directories at the limit without descending into them.
+    pub fn test_max_depth_boundary() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-depth-limit";
+        static ARGS_1: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "--max-depth", "1"]));
+        static ARGS_2: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "--max-depth", "2"]));
+        static ARGS_3: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "--max-depth", "3"]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("top.txt", Some("0"))?;
+        test_dir.create_directory("empty")?;
+        test_dir.generate("a/a.txt", Some("1"))?;
+        test_dir.create_directory("a/empty")?;
+        test_dir.generate("a/b/b.txt", Some("2"))?;
+        test_dir.create_directory("a/b/empty")?;
+        test_dir.generate("a/b/c/c.txt", Some("3"))?;
+
+        let crawled = |args: &'static rippy::args::RippyArgs| -> Result<Vec<(String, bool)>, DirError> {
+            let mut leaves: Vec<(String, bool)> = crawl::crawl_directory(args)?.paths.into_iter()
+                .map(|leaf| (leaf.relative_path.trim_start_matches("fake-depth-limit/").to_string(), leaf.is_dir))
+                .collect();
+            leaves.sort();
+            Ok(leaves)
+        };
+        let entry = |path: &str, is_dir: bool| (path.to_string(), is_dir);
+        assert_eq!(crawled(&ARGS_1)?, vec![entry("a", true), entry("empty", true), entry("top.txt", false)]);
+        assert_eq!(crawled(&ARGS_2)?, vec![
+            entry("a", true), entry("a/a.txt", false), entry("a/b", true), entry("a/empty", true),
+            entry("empty", true), entry("top.txt", false),
+        ]);
+        assert_eq!(crawled(&ARGS_3)?, vec![
+            entry("a", true), entry("a/a.txt", false), entry("a/b", true), entry("a/b/b.txt", false), entry("a/b/c", true), entry("a/b/empty", true), entry("a/empty", true),
+            entry("empty", true), entry("top.txt", false),
+        ]);
+        test_dir.clean()
+    }
+
+    #[test]
+    #[cfg(unix)]
+    /// Aggregates synthetic entries across three devices, only two of which have known mount points.
+    /// Testing functionality of `[--mount-summary]` to count the entries and sum the sizes found on each file system.
+    pub fn test_mount_summary() -> Result<(), DirError> {
+        let entries = vec![(7, 100), (3, 10), (7, 0), (9, 5), (7, 50), (3, 20)];
+        let mount_points = std::collections::HashMap::from([(7, "/".to_string()), (3, "/mnt/data".to_string())]);
+        let mounts = crawl::aggregate_mounts(&entries, &mount_points);
+        assert_eq!(mounts, vec![
+            crawl::MountUsage { device: 7, mount_point: Some("/".to_string()), entries: 3, size: 150 },
+            crawl::MountUsage { device: 3, mount_point: Some("/mnt/data".to_string()), entries: 2, size: 30 },
+            crawl::MountUsage { device: 9, mount_point: None, entries: 1, size: 5 },
+        ]);
+        assert!(crawl::aggregate_mounts(&[], &mount_points).is_empty());
+
+        const ROOT_TEST_DIR: &str = "fake-mount-summary";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "--mount-summary"]));
+        static ARGS_DEFAULT: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("src/main.rs", Some("fn main() {}"))?;
+        test_dir.generate("README.md", Some("readme"))?;
+        let mounts = crawl::crawl_directory(&ARGS)?.mounts;
+        assert_eq!(mounts.len(), 1);
+        assert_eq!((mounts[0].entries, mounts[0].size), (3, 18));
+        assert!(crawl::crawl_directory(&ARGS_DEFAULT)?.mounts.is_empty());
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-export-highlight 'needle' -O fake-export-highlight.html -O fake-export-highlight.md`
+    /// Testing functionality of `[--output]` to keep the match highlighted within windows exported as HTML or Markdown.
+    pub fn test_export_highlight() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-export-highlight";
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("src/lib.rs", Some("a <haystack> with a needle & more"))?;
+        test_dir.generate("src/other.rs", Some("only hay"))?;
+
+        let html_path = "fake-export-highlight.html";
+        let markdown_path = "fake-export-highlight.md";
+        let output = std::process::Command::new(env!("CARGO_BIN_EXE_rippy")).args([ROOT_TEST_DIR, "needle", "-O", html_path, "-O", markdown_path]).output()?;
+        assert!(output.status.success());
+        let html = std::fs::read_to_string(html_path)?;
+        let markdown = std::fs::read_to_string(markdown_path)?;
+        std::fs::remove_file(html_path)?;
+        std::fs::remove_file(markdown_path)?;
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<li>lib.rs: <code>a &lt;haystack&gt; with a <mark>needle</mark> &amp; more</code></li>"));
+        assert!(!html.contains("\x1b["));
+        assert!(markdown.contains("- lib.rs: `a <haystack> with a `**`needle`**` & more`"));
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-flatten-export --flatten-single-child -O fake-flatten-export.json` and without `--flatten-single-child`.
+    /// Testing functionality of `[--flatten-single-child]` to export a chain of single child directories as one node.
+    pub fn test_flatten_single_child() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-flatten-export";
+        const OUTPUT_FILE: &str = "fake-flatten-export.json";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "--flatten-single-child", "--bare-json"]));
+        static ARGS_DEFAULT: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "--bare-json"]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("a/b/c/file.txt", Some("contents"))?;
+
+        let export = |args: &'static rippy::args::RippyArgs| -> Result<serde_json::Value, DirError> {
+            let tree_output = tree::build_tree_from_paths(crawl::crawl_directory(args)?.paths, args);
+            tree_output.write_to_file(OUTPUT_FILE, args)?;
+            let contents = std::fs::read_to_string(OUTPUT_FILE)?;
+            std::fs::remove_file(OUTPUT_FILE)?;
+            Ok(serde_json::from_str(&contents).unwrap())
+        };
+        let flattened = export(&ARGS)?;
+        let children = flattened["children"].as_array().unwrap();
+        assert_eq!(children.len(), 1);
+        assert_eq!(children[0]["name"], "a/b/c");
+        assert_eq!(children[0]["children"][0]["name"], "file.txt");
+        assert_eq!(children[0]["children"][0]["children"], json!([]));
+        let faithful = export(&ARGS_DEFAULT)?;
+        assert_eq!(faithful["children"][0]["name"], "a");
+        assert_eq!(faithful["children"][0]["children"][0]["name"], "b");
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-args-default` and crawls with `RippyArgs::new("fake-args-default")`.
+    /// Testing functionality of `RippyArgs::default` and `RippyArgs::new` to provide the settings yielded without any options, and to return an error for a missing directory.
+    pub fn test_args_default() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-args-default";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| rippy::args::RippyArgs::new(ROOT_TEST_DIR).expect("test directory exists"));
+        static CLI_ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("src/main.rs", Some("fn main() {}"))?;
+        test_dir.generate("README.md", Some("readme"))?;
+        test_dir.generate(".hidden", Some("hidden"))?;
+
+        let default_args = rippy::args::RippyArgs::default();
+        assert_eq!(default_args.directory, PathBuf::from("."));
+        assert_eq!(default_args.max_depth, usize::MAX);
+        assert_eq!(format!("{:?}", *ARGS), format!("{:?}", *CLI_ARGS));
+        // A missing directory is reported rather than searched for in the current directory
+        let missing = rippy::args::RippyArgs::new("fake-args-default/missing").unwrap_err();
+        assert_eq!(missing.kind, rippy::error::ErrorKind::InvalidDirectory);
+        let mut crawled = crawl::crawl_directory(&ARGS)?;
+        let mut expected = crawl::crawl_directory(&CLI_ARGS)?;
+        crawled.paths.sort_by(SORT_RELATIVE);
+        expected.paths.sort_by(SORT_RELATIVE);
+        assert_eq!(crawled, expected);
+        assert_eq!(crawled.paths.len(), 3);
+        test_dir.clean()
+    }
+
+    #[test]
+    #[cfg(unix)]
+    /// Runs `rippy fake-dereference-all --dereference-all -s` with a link to an executable script.
+    /// Testing functionality of `[--dereference-all]` to display and count links as their targets.
+    pub fn test_dereference_all() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-dereference-all";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "--dereference-all", "-s"]));
+        static ARGS_DEFAULT: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "-s"]));
+        use std::os::unix::fs::PermissionsExt;
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("run.sh", Some("#!/bin/sh\necho run\n"))?;
+        std::fs::set_permissions("fake-dereference-all/run.sh", std::fs::Permissions::from_mode(0o755))?;
+        std::os::unix::fs::symlink("run.sh", "fake-dereference-all/link.sh")?;
+
+        assert!(ARGS.is_follow_links && ARGS.is_dereference);
+        let leaf = |args: &'static rippy::args::RippyArgs| -> Result<TreeLeaf, DirError> {
+            Ok(crawl::crawl_directory(args)?.paths.into_iter().find(|leaf| leaf.name == "link.sh").unwrap())
+        };
+        let linked = leaf(&ARGS)?;
+        assert_eq!((linked.is_sym, linked.is_dir, linked.size), (false, false, Some(19)));
+        assert_eq!(linked.display, "link.sh");
+        let unlinked = leaf(&ARGS_DEFAULT)?;
+        assert!(unlinked.is_sym && unlinked.display.contains(" -> "));
+        assert_eq!(unlinked.size, Some(6));
+
+        let mut tree_output = tree::build_tree_from_paths(crawl::crawl_directory(&ARGS)?.paths, &ARGS);
+        let mut writer: Vec<u8> = Vec::new();
+        tree::write_tree(&mut tree_output, &ARGS, &mut tree::TreeCounts::new(), &mut writer)?;
+        let rendered = String::from_utf8_lossy(&writer).to_string();
+        assert!(!rendered.contains(" -> "));
+        if !ARGS.is_grayscale {
+            assert!(rendered.contains(&format!("{}link.sh\x1b[0m", ARGS.colors.exec.unwrap())));
+        }
+        test_dir.clean()
+    }
+
+    #[test]
+    /// Runs `rippy fake-summary-sort --summary-by-ext` with each `--summary-sort` key.
+    /// Testing functionality of `[--summary-sort]` to order the breakdown by extension by size, count or name.
+    pub fn test_summary_sort() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-summary-sort";
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("notes.md", Some(&"m".repeat(500)))?;
+        for name in ["a.rs", "b.rs", "c.rs"] {
+            test_dir.generate(format!("src/{name}"), Some(&"r".repeat(10)))?;
+        }
+        test_dir.generate("docs/p.txt", Some(&"t".repeat(100)))?;
+        test_dir.generate("docs/q.txt", Some(&"t".repeat(100)))?;
+
+        let breakdown = |extra: &[&str]| -> Result<Vec<String>, DirError> {
+            let output = std::process::Command::new(env!("CARGO_BIN_EXE_rippy")).args([ROOT_TEST_DIR, "--summary-by-ext"]).args(extra).output()?;
+            Ok(String::from_utf8_lossy(&output.stdout).lines()
+                .filter(|line| line.trim_start().starts_with('.'))
+                .map(|line| line.split_whitespace().take(2).collect::<Vec<&str>>().join(" "))
+                .collect())
+        };
+        let by_size = breakdown(&[])?;
+        assert_eq!(by_size, vec![".md 1", ".txt 2", ".rs 3"]);
+        assert_eq!(breakdown(&["--summary-sort", "size"])?, by_size);
+        assert_eq!(breakdown(&["--summary-sort", "count"])?, vec![".rs 3", ".txt 2", ".md 1"]);
+        assert_eq!(breakdown(&["--summary-sort", "name"])?, vec![".md 1", ".rs 3", ".txt 2"]);
+        let output = std::process::Command::new(env!("CARGO_BIN_EXE_rippy")).args([ROOT_TEST_DIR, "--summary-by-ext"]).output()?;
+        assert!(String::from_utf8_lossy(&output.stdout).contains(".txt  2 files  200 B"));
+        test_dir.clean()
+    }
+    #[test]
+    /// Runs `rippy fake-file-root/notes.txt -s` with and without a pattern against a single file rather than a directory.
+    /// Testing functionality of `[DIRECTORY]` to render a file given as the root as a single entry with its details and match.
+    pub fn test_file_root() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-file-root";
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("notes.txt", Some("first line\nfind the needle here\n"))?;
+        let file_path = format!("{ROOT_TEST_DIR}/notes.txt");
+
+        let output = std::process::Command::new(env!("CARGO_BIN_EXE_rippy")).args([file_path.as_str(), "-s", "--ascii-space", "needle"]).output()?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(output.status.success(), "rippy on a file should succeed: {stdout}");
+        let entry = stdout.lines().find(|line| line.contains("notes.txt")).unwrap_or_default();
+        assert!(entry.contains("32 B)"), "file entry should include its size: {entry}");
+        assert!(entry.contains("needle"), "file entry should include its match: {entry}");
+        assert!(stdout.contains("1 match for needle, 1 searched"), "summary should count the file alone: {stdout}");
+
+        let output = std::process::Command::new(env!("CARGO_BIN_EXE_rippy")).args([file_path.as_str(), "-s"]).output()?;
+        assert!(String::from_utf8_lossy(&output.stdout).contains("0 directories, 1 file"), "counts should include just the file");
+
+        let output = std::process::Command::new(env!("CARGO_BIN_EXE_rippy")).args([file_path.as_str(), "-s", "absent"]).output()?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains("0 matches"), "a file without a match should be searched and not listed: {stdout}");
+        test_dir.clean()
+    }
+    #[test]
+    /// Runs `rippy fake-color-scale --color-scale date` with one file modified now and another over a year ago.
+    /// Testing functionality of `[--color-scale]` to color files by how long ago they were modified.
+    pub fn test_color_scale() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-color-scale";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "--color-scale", "date"]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("new.txt", Some("fresh"))?;
+        test_dir.generate("old.txt", Some("stale"))?;
+        let two_years_ago = std::time::SystemTime::now() - std::time::Duration::from_secs(2 * 365 * 86_400);
+        std::fs::File::options().write(true).open(format!("{ROOT_TEST_DIR}/old.txt"))?.set_modified(two_years_ago)?;
+
+        assert!(ARGS.show_date, "aging by date should fetch and show the modified dates");
+        assert_ne!(rippy::tcolor::age_color(60.0), rippy::tcolor::age_color(2.0 * 365.0 * 86_400.0));
+        let crawl_results = crawl::crawl_directory(&ARGS)?;
+        let mut tree_output = tree::build_tree_from_paths(crawl_results.paths, &ARGS);
+        let mut counts = tree::TreeCounts::new();
+        let mut writer: Vec<u8> = Vec::new();
+        tree::write_tree(&mut tree_output, &ARGS, &mut counts, &mut writer)?;
+        let output = String::from_utf8_lossy(&writer).to_string();
+        // Only meaningful when colors are enabled, which requires a terminal
+        if !ARGS.is_grayscale {
+            let line_of = |name: &str| output.lines().find(|line| line.contains(name)).unwrap_or_default().to_string();
+            assert!(line_of("new.txt").contains(rippy::tcolor::age_color(60.0).unwrap()));
+            assert!(line_of("old.txt").contains(rippy::tcolor::age_color(2.0 * 365.0 * 86_400.0).unwrap()));
+            assert!(!line_of("old.txt").contains(rippy::tcolor::age_color(60.0).unwrap()));
+        }
+        test_dir.clean()
+    }
+    #[test]
+    /// Runs `rippy fake-flat-record -s -D 'needle' -O fake-flat-record.csv` and flattens the crawled and built entries to records.
+    /// Testing functionality of `TreeLeaf::to_record` and `Tree::to_record` to list the same fields for the flat exports.
+    pub fn test_flat_record() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-flat-record";
+        const OUTPUT_FILE: &str = "fake-flat-record.csv";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "-s", "-D", "needle", "--deterministic"]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("docs/notes.txt", Some("a needle, here"))?;
+
+        let crawl_results = crawl::crawl_directory(&ARGS)?;
+        let leaf = crawl_results.paths.iter().find(|leaf| leaf.name == "notes.txt").expect("file should be crawled");
+        let record = leaf.to_record(&ARGS);
+        assert_eq!(record.path, "fake-flat-record/docs/notes.txt");
+        assert_eq!(record.entry_type, "File");
+        assert_eq!(record.size, "14");
+        assert!(!record.date.is_empty(), "date should be set when shown: {record:?}");
+        assert_eq!(record.matched, "a needle, here");
+
+        let tree_output = tree::build_tree_from_paths(crawl_results.paths, &ARGS);
+        let records = tree_output.to_records(&ARGS);
+        assert_eq!(records.iter().map(|record| record.path.as_str()).collect::<Vec<&str>>(), vec!["fake-flat-record/docs", "fake-flat-record/docs/notes.txt"]);
+        assert_eq!(records[0].entry_type, "Directory");
+        assert_eq!(records[1], record, "built and crawled entries should flatten alike");
+
+        tree_output.write_to_file(OUTPUT_FILE, &ARGS)?;
+        let csv = std::fs::read_to_string(OUTPUT_FILE)?;
+        std::fs::remove_file(OUTPUT_FILE)?;
+        assert_eq!(csv.lines().next(), Some(tree::FlatRecord::CSV_HEADER));
+        assert!(csv.contains("File,14,"), "file row should include its size: {csv}");
+        assert!(csv.contains(",\"a needle, here\"\n"), "fields with commas should be quoted: {csv}");
+        test_dir.clean()
+    }
+    #[test]
+    /// Runs `rippy fake-truncated --max-files 1 --bare-json -O fake-truncated.json` with three files in a subdirectory.
+    /// Testing functionality of `[--max-files | -M]` to record the files truncated on the directory rather than as an entry.
+    pub fn test_truncated_metadata() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-truncated";
+        const OUTPUT_FILE: &str = "fake-truncated.json";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "--max-files", "1", "--bare-json"]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("docs/a.txt", Some("a"))?;
+        test_dir.generate("docs/b.txt", Some("b"))?;
+        test_dir.generate("docs/c.txt", Some("c"))?;
+
+        let crawl_results = crawl::crawl_directory(&ARGS)?;
+        let mut tree_output = tree::build_tree_from_paths(crawl_results.paths, &ARGS);
+        tree_output.write_to_file(OUTPUT_FILE, &ARGS)?;
+        let json: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(OUTPUT_FILE)?).unwrap();
+        std::fs::remove_file(OUTPUT_FILE)?;
+        let docs = &json["children"][0];
+        assert_eq!(docs["name"], "docs");
+        assert_eq!(docs["truncated"], 2);
+        assert_eq!(docs["children"].as_array().map(Vec::len), Some(1), "only the kept file should be exported: {docs}");
+
+        let mut counts = tree::TreeCounts::new();
+        let mut writer: Vec<u8> = Vec::new();
+        tree::write_tree(&mut tree_output, &ARGS, &mut counts, &mut writer)?;
+        let docs = tree_output.children.get("docs").expect("directory should be kept");
+        assert_eq!(docs.truncated, Some(2));
+        assert_eq!(docs.children.len(), 1, "no entry should stand in for the truncated files");
+        assert!(String::from_utf8_lossy(&writer).contains("2 more ..."));
+        assert_eq!(counts.file_count, 3, "truncated files should still be counted");
+        test_dir.clean()
+    }
+    #[test]
+    /// Runs `rippy fake-reverse-levels --reverse-levels files` with directories each holding only files.
+    /// Testing functionality of `[--reverse-levels]` to reverse the files within directories while the directories stay ascending.
+    pub fn test_reverse_levels() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-reverse-levels";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "--reverse-levels", "files"]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        for dir in ["beta", "alpha", "gamma"] {
+            for file in ["one.txt", "three.txt", "two.txt"] {
+                test_dir.generate(format!("{dir}/{file}"), Some(file))?;
+            }
+        }
+
+        let crawl_results = crawl::crawl_directory(&ARGS)?;
+        let mut tree_output = tree::build_tree_from_paths(crawl_results.paths, &ARGS);
+        let mut counts = tree::TreeCounts::new();
+        let mut writer: Vec<u8> = Vec::new();
+        tree::write_tree(&mut tree_output, &ARGS, &mut counts, &mut writer)?;
+        // Children are left in the order they were written
+        let names: Vec<&str> = tree_output.iter().skip(1).map(|entry| entry.name.as_str()).collect();
+        let files_reversed = ["two.txt", "three.txt", "one.txt"];
+        let expected: Vec<&str> = ["alpha", "beta", "gamma"].into_iter().flat_map(|dir| std::iter::once(dir).chain(files_reversed)).collect();
+        assert_eq!(names, expected, "directories should be ascending with their files reversed");
+        test_dir.clean()
+    }
+    #[test]
+    /// Runs `rippy fake-utf8-lossy 'needle'` with and without `--utf8-lossy` over a file containing an invalid UTF-8 byte.
+    /// Testing functionality of `[--utf8-lossy]` to search files with invalid bytes instead of skipping them.
+    pub fn test_utf8_lossy() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-utf8-lossy";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "needle", "--utf8-lossy"]));
+        static ARGS_STRICT: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "needle"]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        std::fs::create_dir_all(ROOT_TEST_DIR)?;
+        std::fs::write(std::path::Path::new(ROOT_TEST_DIR).join("mixed.txt"), b"broken \xff byte then a needle")?;
+
+        let strict = crawl::crawl_directory(&ARGS_STRICT)?;
+        assert!(strict.paths.is_empty(), "invalid UTF-8 should be skipped by default");
+        assert_eq!(strict.paths_unreadable, 1);
+
+        let lossy = crawl::crawl_directory(&ARGS)?;
+        assert_eq!(lossy.paths.iter().map(|leaf| leaf.name.as_str()).collect::<Vec<&str>>(), vec!["mixed.txt"]);
+        assert_eq!(lossy.paths_unreadable, 0);
+        assert!(lossy.paths[0].window_match.as_ref().is_some_and(|window| window.text.contains('\u{FFFD}')), "invalid byte should be replaced in the window");
+        test_dir.clean()
+    }
+    #[test]
+    /// Runs `rippy fake-size-progress -s --progress` and calculates the directory sizes with and without progress.
+    /// Testing functionality of `[--progress]` to leave the directory sizes unchanged while reporting progress.
+    pub fn test_size_progress() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-size-progress";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "-s", "--progress"]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("a.txt", Some("12345"))?;
+        test_dir.generate("src/b.txt", Some("123"))?;
+        test_dir.generate("src/nested/c.txt", Some("1234567"))?;
+        assert!(ARGS.is_progress);
+
+        let crawl_results = crawl::crawl_directory(&ARGS)?;
+        let total_entries = crawl_results.paths.len();
+        let mut plain = tree::build_tree_from_paths(crawl_results.paths, &ARGS);
+        let mut with_progress = plain.clone();
+        plain.calculate_sizes();
+        let mut progress = tree::SizeProgress::new(total_entries);
+        with_progress.calculate_sizes_with_progress(usize::MAX, &mut progress);
+
+        let sizes = |tree: &tree::Tree| tree.iter().map(|entry| (entry.name.clone(), entry.size)).collect::<Vec<(String, Option<u64>)>>();
+        assert_eq!(sizes(&with_progress), sizes(&plain));
+        assert_eq!(with_progress.size, Some(15));
+        assert_eq!(progress.processed, total_entries, "every crawled entry should be processed once");
+        test_dir.clean()
+    }
+    #[test]
+    /// Runs `rippy fake-json-paths --json-paths base=fake-json-paths/src --bare-json -O fake-json-paths.json` along with `relative`.
+    /// Testing functionality of `[--json-paths]` to export the path of each entry relative to the base requested.
+    pub fn test_json_paths() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-json-paths";
+        const OUTPUT_FILE: &str = "fake-json-paths.json";
+        static ARGS_BASE: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "--json-paths", "base=fake-json-paths/src", "--bare-json"]));
+        static ARGS_RELATIVE: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "--json-paths", "relative", "--bare-json"]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("src/lib/mod.rs", Some("mod"))?;
+        test_dir.generate("notes.txt", Some("notes"))?;
+
+        let exported = |args: &'static rippy::args::RippyArgs| -> Result<serde_json::Value, DirError> {
+            let tree_output = tree::build_tree_from_paths(crawl::crawl_directory(args)?.paths, args);
+            tree_output.write_to_file(OUTPUT_FILE, args)?;
+            let json = serde_json::from_str(&std::fs::read_to_string(OUTPUT_FILE)?).unwrap();
+            std::fs::remove_file(OUTPUT_FILE)?;
+            Ok(json)
+        };
+        let json = exported(&ARGS_BASE)?;
+        let src = json["children"].as_array().unwrap().iter().find(|child| child["name"] == "src").unwrap();
+        assert_eq!(src["path"], ".", "the base itself should be written as '.'");
+        assert_eq!(src["children"][0]["path"], "lib");
+        assert_eq!(src["children"][0]["children"][0]["path"], "lib/mod.rs");
+        let notes = json["children"].as_array().unwrap().iter().find(|child| child["name"] == "notes.txt").unwrap();
+        assert!(notes["path"].as_str().is_some_and(|path| std::path::Path::new(path).is_absolute()), "paths outside the base should be absolute: {notes}");
+
+        let json = exported(&ARGS_RELATIVE)?;
+        assert_eq!(json["path"], ".");
+        let src = json["children"].as_array().unwrap().iter().find(|child| child["name"] == "src").unwrap();
+        assert_eq!(src["children"][0]["children"][0]["path"], "src/lib/mod.rs");
+        test_dir.clean()
+    }
+    #[test]
+    /// Runs `rippy fake-name-style --name-style snake` with and without `--invert` over a mix of naming conventions.
+    /// Testing functionality of `[--name-style]` and `[--invert]` to keep the files following or breaking the convention.
+    pub fn test_name_style() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-name-style";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "--name-style", "snake"]));
+        static ARGS_INVERT: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "--name-style", "snake", "--invert"]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("src/snake_case.rs", Some("ok"))?;
+        test_dir.generate("src/camelCase.rs", Some("flagged"))?;
+        test_dir.generate("docs/kebab-case.md", Some("flagged"))?;
+        test_dir.generate("docs/readme.md", Some("ok"))?;
+
+        let files = |args: &'static rippy::args::RippyArgs| -> Result<Vec<String>, DirError> {
+            let mut names: Vec<String> = crawl::crawl_directory(args)?.paths.into_iter().filter(|leaf| !leaf.is_dir).map(|leaf| leaf.name).collect();
+            names.sort();
+            Ok(names)
+        };
+        assert_eq!(files(&ARGS)?, vec!["readme.md", "snake_case.rs"]);
+        assert_eq!(files(&ARGS_INVERT)?, vec!["camelCase.rs", "kebab-case.md"], "only the files breaking snake_case should be flagged");
+        test_dir.clean()
+    }
+    #[test]
+    /// Runs `rippy fake-max-line-length 'needle' -r 2000 --max-line-length 200` over a file with a single 10,000 character line.
+    /// Testing functionality of `[--max-line-length]` to cap the window on long lines whatever the window radius.
+    pub fn test_max_line_length() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-max-line-length";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "needle", "-r", "2000", "--max-line-length", "200"]));
+        static ARGS_UNCAPPED: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "needle", "-r", "2000"]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("minified.js", Some(format!("{} needle {}", "a".repeat(4_996), "b".repeat(4_996))))?;
+
+        let window = |args: &'static rippy::args::RippyArgs| -> Result<String, DirError> {
+            let crawl_results = crawl::crawl_directory(args)?;
+            Ok(crawl_results.paths.into_iter().find_map(|leaf| leaf.window_match).map(|window| window.text).unwrap_or_default())
+        };
+        let capped = window(&ARGS)?;
+        assert!(capped.contains("needle"));
+        assert!(capped.chars().count() <= 64, "window should be bounded on the long line: {capped}");
+        assert!(capped.starts_with("...") && capped.ends_with("..."), "elided content should be marked: {capped}");
+        assert!(window(&ARGS_UNCAPPED)?.len() > 4_000, "window radius should apply without the maximum");
+        test_dir.clean()
+    }
+    #[test]
+    /// Runs `rippy fake-json-elapsed -T -O fake-json-elapsed.json` and again without `-T`.
+    /// Testing functionality of `[--time]` to record the elapsed seconds and entry counts in the header of the JSON export.
+    pub fn test_json_elapsed() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-json-elapsed";
+        const OUTPUT_FILE: &str = "fake-json-elapsed.json";
+        static ARGS_TIMED: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "-T"]));
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("src/main.rs", Some("fn main() {}"))?;
+        test_dir.generate("notes.txt", Some("notes"))?;
+
+        let exported = |args: &'static rippy::args::RippyArgs| -> Result<serde_json::Value, DirError> {
+            let start = std::time::Instant::now();
+            let tree_output = tree::build_tree_from_paths(crawl::crawl_directory(args)?.paths, args);
+            let elapsed = args.show_elapsed.then(|| start.elapsed());
+            tree_output.write_to_file_with_elapsed(OUTPUT_FILE, args, elapsed)?;
+            let json = serde_json::from_str(&std::fs::read_to_string(OUTPUT_FILE)?).unwrap();
+            std::fs::remove_file(OUTPUT_FILE)?;
+            Ok(json)
+        };
+        let json = exported(&ARGS_TIMED)?;
+        assert!(json["elapsed_secs"].is_f64(), "timed exports should record the elapsed seconds: {json}");
+        assert_eq!(json["counts"]["directories"], 1);
+        assert_eq!(json["counts"]["files"], 2);
+
+        let json = exported(&ARGS)?;
+        assert!(json.get("elapsed_secs").is_none(), "untimed exports should not record the elapsed seconds");
+        assert_eq!(json["counts"]["files"], 2);
+        test_dir.clean()
+    }
+    #[test]
+    /// Runs `rippy fake-compact --compact` and `rippy fake-compact --compact --table`.
+    /// Testing functionality of `[--compact]` to start every line at column 0 without a trailing blank line.
+    pub fn test_compact() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-compact";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "--compact", "--no-color"]));
+        static ARGS_TABLE: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "--compact", "--table", "--no-color"]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("src/main.rs", Some("fn main() {}"))?;
+        test_dir.generate("notes.txt", Some("notes"))?;
+
+        for args in [&*ARGS, &*ARGS_TABLE] {
+            let mut tree_output = tree::build_tree_from_paths(crawl::crawl_directory(args)?.paths, args);
+            let mut counts = tree::TreeCounts::new();
+            let mut buffer: Vec<u8> = Vec::new();
+            tree::write_results_to_buf(&mut tree_output, args, &mut counts, &mut buffer)?;
+            let output = String::from_utf8(buffer).unwrap();
+            assert!(!output.ends_with("\n\n"), "compact output should not end with a blank line: {output:?}");
+            for line in output.lines() {
+                assert!(!line.is_empty() && !line.starts_with(' '), "compact lines should start at column 0: {line:?}");
+            }
+        }
+        test_dir.clean()
+    }
+    #[test]
+    /// Runs `rippy 'fake-glob-root/projects/*/src'` over projects with and without a `src` directory.
+    /// Testing functionality of a glob given as `[DIRECTORY]` to crawl every directory it matches from the directory the glob starts in.
+    pub fn test_glob_root() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-glob-root";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "fake-glob-root/projects/*/src"]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("projects/alpha/src/main.rs", Some("fn main() {}"))?;
+        test_dir.generate("projects/beta/src/lib.rs", Some("pub fn lib() {}"))?;
+        test_dir.generate("projects/alpha/docs/guide.md", Some("guide"))?;
+        test_dir.generate("projects/beta/README.md", Some("readme"))?;
+
+        assert_eq!(ARGS.directory, std::path::PathBuf::from("fake-glob-root/projects"), "the glob should be crawled from its literal base");
+        assert_eq!(ARGS.glob_roots.as_ref().map(Vec::len), Some(2), "both src directories should be matched");
+        let tree_output = tree::build_tree_from_paths(crawl::crawl_directory(&ARGS)?.paths, &ARGS);
+        let names: Vec<&str> = tree_output.iter().map(|entry| entry.display.as_str()).collect();
+        assert!(names.contains(&"main.rs") && names.contains(&"lib.rs"), "files in both src directories should be scanned: {names:?}");
+        assert!(!names.contains(&"guide.md") && !names.contains(&"README.md") && !names.contains(&"docs"), "entries outside the matched directories should be left out: {names:?}");
+        test_dir.clean()
+    }
+    #[test]
+    #[cfg(unix)]
+    /// Runs `rippy fake-atime-older --atime-older 90d` with files last used long ago, recently accessed and recently modified.
+    /// Testing functionality of `[--atime-older]` to keep only the files not accessed within the age, taking a recent modification as a use.
+    pub fn test_atime_older() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-atime-older";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "--atime-older", "90d"]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        let old = std::time::SystemTime::now() - std::time::Duration::from_secs(200 * 86_400);
+        let now = std::time::SystemTime::now();
+        let set_times = |file: &str, accessed: std::time::SystemTime, modified: std::time::SystemTime| -> Result<(), DirError> {
+            test_dir.generate(file, Some("contents"))?;
+            let path = std::path::Path::new(ROOT_TEST_DIR).join(file);
+            std::fs::File::options().write(true).open(path)?.set_times(std::fs::FileTimes::new().set_accessed(accessed).set_modified(modified))?;
+            Ok(())
+        };
+        set_times("stale/unused.log", old, old)?;
+        set_times("read.txt", now, old)?;
+        set_times("edited.txt", old, now)?;
+
+        let mut names: Vec<String> = crawl::crawl_directory(&ARGS)?.paths.into_iter().map(|leaf| leaf.name).collect();
+        names.sort();
+        assert_eq!(names, vec!["stale", "unused.log"], "only the file not used within 90 days and its directory should be kept");
+        test_dir.clean()
+    }
+    #[test]
+    /// Runs `rippy fake-diff -s --diff fake-diff.json` after adding, removing and changing files since the snapshot was exported.
+    /// Testing functionality of `[--diff]` to mark entries with `+`, `-` and `~` prefixes, colored unless grayscale.
+    pub fn test_diff() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-diff";
+        const SNAPSHOT_FILE: &str = "fake-diff.json";
+        static ARGS_SNAPSHOT: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "-s"]));
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "-s", "--diff", SNAPSHOT_FILE]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("src/main.rs", Some("fn main() {}"))?;
+        test_dir.generate("notes.txt", Some("notes"))?;
+        test_dir.generate("old/gone.txt", Some("gone"))?;
+        let snapshot = tree::build_tree_from_paths(crawl::crawl_directory(&ARGS_SNAPSHOT)?.paths, &ARGS_SNAPSHOT);
+        snapshot.write_to_file(SNAPSHOT_FILE, &ARGS_SNAPSHOT)?;
+
+        std::fs::remove_dir_all(format!("{ROOT_TEST_DIR}/old"))?;
+        test_dir.generate("src/lib.rs", Some("pub mod lib;"))?;
+        std::fs::write(format!("{ROOT_TEST_DIR}/notes.txt"), "more notes")?;
+        let mut tree_output = tree::build_tree_from_paths(crawl::crawl_directory(&ARGS)?.paths, &ARGS);
+        tree_output.diff_against(ARGS.diff.as_ref().unwrap(), &ARGS);
+        let mut counts = tree::TreeCounts::new();
+        let mut buffer: Vec<u8> = Vec::new();
+        tree::write_tree(&mut tree_output, &ARGS, &mut counts, &mut buffer)?;
+        std::fs::remove_file(SNAPSHOT_FILE)?;
+
+        let output = String::from_utf8(buffer).unwrap();
+        let plain = Regex::new("\u{1b}\\[[0-9;]*m").unwrap().replace_all(&output, "");
+        for expected in ["+ lib.rs", "- old", "- gone.txt", "~ notes.txt", "  main.rs", "  src"] {
+            assert!(plain.contains(expected), "expected '{expected}' in the diff: {plain}");
+        }
+        assert_eq!(counts, tree::TreeCounts{ dir_count: 1, file_count: 3 }, "removed entries should not be counted");
+        if let Some(added_color) = ARGS.colors.added {
+            assert!(output.contains(&format!("{added_color}+ lib.rs")), "added entries should take the added color: {output:?}");
+        }
+        test_dir.clean()
+    }
+    #[test]
+    /// Runs `rippy fake-no-pipe --no-pipe` with stdout captured rather than a terminal, along with `--gray` and without the flag.
+    /// Testing functionality of `[--no-pipe]` to keep colors when output is piped, still overridden by `[--gray]`.
+    pub fn test_no_pipe() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-no-pipe";
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("src/main.rs", Some("fn main() {}"))?;
+
+        let run = |flags: &[&str]| std::process::Command::new(env!("CARGO_BIN_EXE_rippy")).arg(ROOT_TEST_DIR).args(flags).output();
+        let piped = String::from_utf8(run(&["--no-pipe"])?.stdout).unwrap();
+        assert!(piped.contains('\u{1b}'), "colors should be kept when piped with --no-pipe: {piped:?}");
+        let gray = String::from_utf8(run(&["--no-pipe", "--gray"])?.stdout).unwrap();
+        assert!(!gray.contains('\u{1b}'), "--gray should still drop colors: {gray:?}");
+        let plain = String::from_utf8(run(&[])?.stdout).unwrap();
+        assert!(!plain.contains('\u{1b}'), "colors should be dropped when piped by default: {plain:?}");
+        test_dir.clean()
+    }
+    #[test]
+    /// Runs `rippy fake-max-total-size --max-total-size 100` over five files of 40 bytes each.
+    /// Testing functionality of `[--max-total-size]` to stop the crawl once the files visited add up to more than the budget, flagging the results as truncated.
+    pub fn test_max_total_size() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-max-total-size";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "--max-total-size", "100"]));
+        static ARGS_UNBOUNDED: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "--max-total-size", "1K"]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        for i in 0..5 {
+            test_dir.generate(format!("data/part-{i}.bin"), Some(&"x".repeat(40)))?;
+        }
+
+        let crawl_results = crawl::crawl_directory(&ARGS)?;
+        let files = crawl_results.paths.iter().filter(|leaf| !leaf.is_dir).count();
+        assert!(crawl_results.is_truncated, "the crawl should be flagged as truncated once over budget");
+        assert_eq!(files, 2, "only the files within 100 bytes should be kept");
+
+        let crawl_results = crawl::crawl_directory(&ARGS_UNBOUNDED)?;
+        assert!(!crawl_results.is_truncated);
+        assert_eq!(crawl_results.paths.iter().filter(|leaf| !leaf.is_dir).count(), 5);
+        test_dir.clean()
+    }
+    #[test]
+    /// Runs `rippy fake-window-sides needle --window-before 5 --window-after 15` over a line with plenty of context on either side.
+    /// Testing functionality of `[--window-before]` and `[--window-after]` to bound the window on each side of the match independently.
+    pub fn test_window_sides() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-window-sides";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "needle", "--window-before", "5", "--window-after", "15"]));
+        static ARGS_AFTER: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "needle", "-r", "3", "--window-after", "0"]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("haystack.txt", Some("0123456789abcdefghij needle ABCDEFGHIJKLMNOPQRST"))?;
+
+        let window = |args: &'static rippy::args::RippyArgs| -> Result<String, DirError> {
+            let crawl_results = crawl::crawl_directory(args)?;
+            Ok(crawl_results.paths.into_iter().find_map(|leaf| leaf.window_match).map(|window| window.text).unwrap_or_default())
+        };
+        assert_eq!(window(&ARGS)?, "...ghij needle ABCDEFGHIJKLMN...");
+        assert_eq!(window(&ARGS_AFTER)?, "...ij needle...", "the window radius should still apply to the side not given");
+        test_dir.clean()
+    }
+    #[test]
+    /// Runs `rippy fake-paths-from-null --paths-from-null` with NUL separated paths piped to stdin, one of them holding a space and another outside the root.
+    /// Testing functionality of `[--paths-from-null]` to build the tree from only the paths listed along with the directories containing them, warning about paths outside the root and failing if none are within it.
+    pub fn test_paths_from_null() -> Result<(), DirError> {
+        use std::io::Write;
+        const ROOT_TEST_DIR: &str = "fake-paths-from-null";
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("notes with space.txt", Some("notes"))?;
+        test_dir.generate("src/main.rs", Some("fn main() {}"))?;
+        test_dir.generate("other/unlisted.txt", Some("unlisted"))?;
+
+        let mut child = std::process::Command::new(env!("CARGO_BIN_EXE_rippy"))
+            .args([ROOT_TEST_DIR, "--paths-from-null", "--gray"])
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .spawn()?;
+        child.stdin.take().unwrap().write_all(format!("{ROOT_TEST_DIR}/notes with space.txt\0{ROOT_TEST_DIR}/src/main.rs\0Cargo.toml\0").as_bytes())?;
+        let result = child.wait_with_output()?;
+        let errors = String::from_utf8(result.stderr).unwrap();
+        assert!(errors.contains("1 path read from stdin was outside of 'fake-paths-from-null' and skipped, such as 'Cargo.toml'"), "{errors}");
+        let output = String::from_utf8(result.stdout).unwrap();
+        assert!(output.contains("notes with space.txt") && output.contains("src") && output.contains("main.rs"), "listed paths should be in the tree: {output}");
+        assert!(!output.contains("other") && !output.contains("unlisted.txt"), "paths not listed should be left out: {output}");
+        assert!(output.contains("1 directory, 2 files"), "{output}");
+
+        let mut child = std::process::Command::new(env!("CARGO_BIN_EXE_rippy"))
+            .args([ROOT_TEST_DIR, "--paths-from-null", "--gray"])
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .spawn()?;
+        child.stdin.take().unwrap().write_all(b"Cargo.toml\0")?;
+        let result = child.wait_with_output()?;
+        assert!(!result.status.success());
+        assert!(String::from_utf8(result.stderr).unwrap().contains("None of the paths read from stdin are within the directory provided"));
+        test_dir.clean()
+    }
+    #[test]
+    /// Runs `rippy fake-matching-dirs -X '*.rs' --matching-dirs` with matching files spread across two directories.
+    /// Testing functionality of `[--matching-dirs]` to list each directory holding a kept file once.
+    pub fn test_matching_dirs() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-matching-dirs";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "-X", "*.rs", "--matching-dirs"]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("src/main.rs", Some("fn main() {}"))?;
+        test_dir.generate("src/lib.rs", Some("pub mod lib;"))?;
+        test_dir.generate("tests/common/mod.rs", Some("pub mod common;"))?;
+        test_dir.generate("docs/guide.md", Some("guide"))?;
+        test_dir.generate("README.md", Some("readme"))?;
+
+        let tree_output = tree::build_tree_from_paths(crawl::crawl_directory(&ARGS)?.paths, &ARGS);
+        assert_eq!(tree_output.matching_dirs(), vec!["fake-matching-dirs/src", "fake-matching-dirs/tests/common"]);
+        test_dir.clean()
+    }
+    #[test]
+    #[cfg(unix)]
+    /// Runs `rippy fake-visible-width needle --no-pipe` and `rippy fake-visible-width needle --no-pipe --table` with a symlink, whose display is styled, alongside plain files.
+    /// Testing functionality of the window alignment to measure names by their visible width so the snippet columns line up in both the tree and the table.
+    pub fn test_visible_width_alignment() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-visible-width";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "needle", "--no-pipe"]));
+        static ARGS_TABLE: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "needle", "--no-pipe", "--table"]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("target.txt", Some("needle here"))?;
+        test_dir.generate("a_much_longer_file_name.txt", Some("needle there"))?;
+        std::os::unix::fs::symlink("target.txt", format!("{ROOT_TEST_DIR}/link.txt"))?;
+
+        let mut tree_output = tree::build_tree_from_paths(crawl::crawl_directory(&ARGS)?.paths, &ARGS);
+        assert!(tree_output.children.values().any(|child| child.display.contains('\u{1b}')), "the symlink display should be styled");
+        tree_output.calculate_fmt_width();
+        let mut counts = tree::TreeCounts::new();
+        let mut buffer: Vec<u8> = Vec::new();
+        tree::write_tree(&mut tree_output, &ARGS, &mut counts, &mut buffer)?;
+        let needle_columns = |buffer: Vec<u8>| {
+            let output = String::from_utf8(buffer).unwrap();
+            let plain = Regex::new("\u{1b}\\[[0-9;]*m").unwrap().replace_all(&output, "").to_string();
+            let columns: Vec<usize> = plain.lines()
+                .filter_map(|line| line.find("needle").map(|index| line[..index].chars().count()))
+                .collect();
+            assert_eq!(columns.len(), 3, "{plain}");
+            assert!(columns.windows(2).all(|pair| pair[0] == pair[1]), "snippet columns should align: {plain}");
+        };
+        needle_columns(buffer);
+
+        let mut table_output = tree::build_tree_from_paths(crawl::crawl_directory(&ARGS_TABLE)?.paths, &ARGS_TABLE);
+        let mut table_buffer: Vec<u8> = Vec::new();
+        tree::write_table_to_buf(&mut table_output, &ARGS_TABLE, &mut tree::TreeCounts::new(), &mut table_buffer)?;
+        needle_columns(table_buffer);
+        assert_eq!(tcolor::visible_width("\u{1b}[38;5;147mlink.txt\u{1b}[0m -> target.txt"), 22);
+        test_dir.clean()
+    }
+    #[test]
+    /// Runs `rippy fake-sort-implied --sort date` and `rippy fake-sort-implied --sort size` without `--date` or `--size`.
+    /// Testing functionality of `[--sort]` to read the modified times and sizes it sorts by even when they aren't displayed.
+    pub fn test_sort_implies_stats() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-sort-implied";
+        static ARGS_DATE: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "--sort", "date"]));
+        static ARGS_SIZE: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", ROOT_TEST_DIR, "--sort", "size"]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        let set_modified = |file: &str, contents: &str, secs: u64| -> Result<(), DirError> {
+            test_dir.generate(file, Some(contents))?;
+            let path = std::path::Path::new(ROOT_TEST_DIR).join(file);
+            std::fs::File::options().write(true).open(path)?.set_modified(std::time::UNIX_EPOCH + std::time::Duration::from_secs(secs))?;
+            Ok(())
+        };
+        set_modified("a-newest.txt", "x", 1_735_689_600)?;
+        set_modified("b-oldest.txt", "xxx", 1_672_531_200)?;
+        set_modified("c-middle.txt", "xx", 1_709_294_400)?;
+
+        let order = |args: &'static rippy::args::RippyArgs| -> Result<Vec<String>, DirError> {
+            let mut tree_output = tree::build_tree_from_paths(crawl::crawl_directory(args)?.paths, args);
+            tree_output.children.sort_by(|_, a, _, b| (args.sort_by)(a, b));
+            Ok(tree_output.children.keys().cloned().collect())
+        };
+        assert!(!ARGS_DATE.show_date && !ARGS_SIZE.show_size, "sorting alone should not display the dates or sizes");
+        assert_eq!(order(&ARGS_DATE)?, vec!["b-oldest.txt", "c-middle.txt", "a-newest.txt"]);
+        assert_eq!(order(&ARGS_SIZE)?, vec!["a-newest.txt", "c-middle.txt", "b-oldest.txt"]);
+        test_dir.clean()
+    }
+    #[test]
+    /// Runs `rippy fake-missing-root/no-such-dir` and `rippy fake-missing-root/no-such-dir TODO` where the directory doesn't exist.
+    /// Testing that a lone positional written as a path is still validated as the `[DIRECTORY]` rather than searched for as the pattern.
+    pub fn test_missing_directory_path() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-missing-root";
+        let no_contents: Option<&str> = None;
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("f1.txt", no_contents)?;
+
+        for args in [vec!["fake-missing-root/no-such-dir"], vec!["fake-missing-root/no-such-dir", "TODO"]] {
+            let output = std::process::Command::new(env!("CARGO_BIN_EXE_rippy")).args(&args).output()?;
+            assert!(!output.status.success(), "a missing directory should fail: {args:?}");
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            assert!(stderr.contains("does not exist"), "expected the missing directory error: {stderr:?}");
+            assert!(output.stdout.is_empty());
+        }
+        test_dir.clean()
+    }
+    #[test]
+    /// Runs `rippy fake-bad-pattern-file --pattern-file fake-bad-pattern-file/patterns.txt` with an unclosed group on the third line of the file.
+    /// Testing functionality of `[--pattern-file]` to report an invalid pattern along with its line rather than panicking.
+    pub fn test_invalid_pattern_file() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-bad-pattern-file";
+        const PATTERN_FILE: &str = "fake-bad-pattern-file/patterns.txt";
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("patterns.txt", Some("TODO\n\nfoo(\n"))?;
+        test_dir.generate("notes.txt", Some("TODO: first"))?;
+
+        let run = |flags: &[&str]| std::process::Command::new(env!("CARGO_BIN_EXE_rippy")).args([ROOT_TEST_DIR, "--pattern-file", PATTERN_FILE]).args(flags).output();
+        let output = run(&[])?;
+        assert!(!output.status.success());
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(!stderr.contains("panicked"), "an invalid pattern should not panic: {stderr:?}");
+        assert!(stderr.contains("'foo(' on line 3 of"), "expected the offending pattern and line: {stderr:?}");
+
+        let output = run(&["--error-format", "json"])?;
+        let error: serde_json::Value = serde_json::from_slice(&output.stderr).expect("error should be JSON");
+        assert_eq!(error["kind"], "invalid_regex");
+        assert_eq!(error["path"], PATTERN_FILE);
+
+        // Escaped literally the same line is valid
+        assert!(run(&["--fixed-strings"])?.status.success());
+        test_dir.clean()
+    }
+    #[test]
+    /// Runs `rippy fake-fuzzy-rank --fuzzy main --gray`, then with `--reverse` and `--sort name`, on test directory to generate:
+    /// 
+    /// ```shell
+    ///  fake-fuzzy-rank
+    ///  ├── main.rs
+    ///  ├── domain.rs
+    ///  ╰── my_admin_notes.rs
+    /// 
+    /// 0 directories, 3 files
+    /// ```
+    /// 
+    /// Testing functionality of `[--fuzzy]` to rank the closest matches first, ordered by `[--sort score]` unless another key is given.
+    pub fn test_fuzzy_ranking() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-fuzzy-rank";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--fuzzy", "main", "--gray", ROOT_TEST_DIR]));
+        static ARGS_REVERSED: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--fuzzy", "main", "--gray", "--reverse", ROOT_TEST_DIR]));
+        static ARGS_NAME: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--fuzzy", "main", "--gray", "--sort", "name", ROOT_TEST_DIR]));
+        let no_contents: Option<&str> = None;
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("my_admin_notes.rs", no_contents)?;
+        test_dir.generate("domain.rs", no_contents)?;
+        test_dir.generate("main.rs", no_contents)?;
+        test_dir.generate("cargo.toml", no_contents)?;
+
+        let ranked = |args: &'static rippy::args::RippyArgs| -> Result<Vec<String>, DirError> {
+            let crawl_results = crawl::crawl_directory(args)?;
+            let mut tree_output = tree::build_tree_from_paths(crawl_results.paths, args);
+            tree_output.retain_fuzzy(args.fuzzy.as_deref().unwrap(), args);
+            tree_output.sort_levels(args);
+            Ok(tree_output.children.values().map(|t| t.name.clone()).collect())
+        };
+        assert_eq!(ranked(&ARGS)?, vec!["main.rs", "domain.rs", "my_admin_notes.rs"]);
+        assert_eq!(ranked(&ARGS_REVERSED)?, vec!["my_admin_notes.rs", "domain.rs", "main.rs"]);
+        assert_eq!(ranked(&ARGS_NAME)?, vec!["domain.rs", "main.rs", "my_admin_notes.rs"]);
+        test_dir.clean()
+    }
+    #[test]
+    /// Runs `rippy fake-html-export --gray -O fake-html-export.html` on test directory:
+    /// 
+    /// ```shell
+    ///  fake-html-export
+    ///  ├── docs
+    ///  │   ╰── notes & todo.md
+    ///  ╰── main.rs
+    /// 
+    /// 1 directory, 2 files
+    /// ```
+    /// 
+    /// Testing functionality of `[--output]` to export the tree as an HTML document of nested lists when the file has an `.html` extension, escaping names and marking directories.
+    pub fn test_write_tree_to_html() -> Result<(), DirError> {
+        const ROOT_TEST_DIR: &str = "fake-html-export";
+        const OUTPUT_FILE: &str = "fake-html-export.html";
+        static ARGS: LazyLock<rippy::args::RippyArgs> = LazyLock::new(|| generate_args_from(vec!["rippy", "--gray", ROOT_TEST_DIR]));
+        let test_dir = RootDirectory::new(ROOT_TEST_DIR);
+        test_dir.generate("main.rs", Some("fn main() {}"))?;
+        test_dir.generate("docs/notes & todo.md", Some("# Notes"))?;
+
+        let tree_output = tree::build_tree_from_paths(crawl::crawl_directory(&ARGS)?.paths, &ARGS);
+        tree_output.write_to_file(OUTPUT_FILE, &ARGS)?;
+        let html = std::fs::read_to_string(OUTPUT_FILE)?;
+        std::fs::remove_file(OUTPUT_FILE)?;
+        assert_eq!(html, tree_output.to_html(&ARGS));
+        assert!(html.starts_with("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>fake-html-export</title>"));
+        assert!(html.contains("<h1>fake-html-export</h1>\n<ul>\n<li>docs/\n<ul>\n<li>notes &amp; todo.md</li>\n</ul>\n</li>\n<li>main.rs</li>\n</ul>\n"));
+        assert!(html.ends_with("</body>\n</html>\n"));
+        assert_eq!(html.matches("<ul>").count(), html.matches("</ul>").count());
+        test_dir.clean()
+    }
+}